@@ -0,0 +1,136 @@
+//! Feeds arbitrary sequences of per-sensor obstruction flags through every
+//! `EstimationModel` exercised by `tests/estimation_invariants.rs` and checks
+//! the same cross-cutting invariants (finite, bounded, NoData/Degraded
+//! wiring). A crash or assertion failure here is a model bug the proptest
+//! suite's randomized-but-seeded cases didn't happen to hit.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mariam_flow::estimation::ewma::{EwmaModel, EwmaParams};
+use mariam_flow::estimation::low_pass::{LowPassModel, LowPassParams};
+use mariam_flow::estimation::model::{EstimationModel, OccupancyConfig};
+use mariam_flow::estimation::obstruction_count_v1::{ObstructionCountModel, ObstructionCountParams};
+use mariam_flow::estimation::occupancy_smooth::{OccupancySmoothModel, OccupancySmoothParams};
+use mariam_flow::estimation::pid_obstruction::{PidObstructionModel, PidObstructionParams};
+use mariam_flow::estimation::smoothed_obstruction::{
+    SmoothedObstructionModel, SmoothedObstructionParams,
+};
+use mariam_flow::state::{SensorObstruction, WaitTimeErrorCode, WaitTimeStatus};
+use std::time::{Duration, UNIX_EPOCH};
+
+const MIN_WAIT_MINUTES: u32 = 1;
+const MAX_WAIT_MINUTES: u32 = 30;
+
+#[derive(Debug, Arbitrary)]
+struct FuzzObstruction {
+    obstructed: Option<bool>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzBatch {
+    flags: Vec<FuzzObstruction>,
+    dt_secs: u8,
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzInput {
+    batches: Vec<FuzzBatch>,
+}
+
+fn models() -> Vec<Box<dyn EstimationModel>> {
+    let occupancy_config = OccupancyConfig::default();
+    vec![
+        Box::new(ObstructionCountModel::new(
+            ObstructionCountParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..ObstructionCountParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..SmoothedObstructionParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(PidObstructionModel::new(
+            PidObstructionParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..PidObstructionParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(EwmaModel::new(
+            EwmaParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..EwmaParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(LowPassModel::new(
+            LowPassParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..LowPassParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(OccupancySmoothModel::new(
+            OccupancySmoothParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..OccupancySmoothParams::default()
+            },
+            occupancy_config,
+        )),
+    ]
+}
+
+fuzz_target!(|input: FuzzInput| {
+    for model in models() {
+        let mut timestamp = UNIX_EPOCH;
+        for batch in input.batches.iter().take(64) {
+            timestamp += Duration::from_secs(batch.dt_secs as u64);
+            let obstructions: Vec<SensorObstruction> = batch
+                .flags
+                .iter()
+                .take(32)
+                .enumerate()
+                .map(|(sensor_id, flag)| SensorObstruction {
+                    sensor_id: sensor_id as u32,
+                    obstructed: flag.obstructed,
+                    timestamp,
+                })
+                .collect();
+
+            let valid_count = obstructions.iter().filter(|o| o.obstructed.is_some()).count();
+            let error_count = obstructions.len() - valid_count;
+
+            let estimate = model.compute_wait_time(&obstructions, timestamp);
+
+            if let Some(minutes) = estimate.wait_time_minutes {
+                assert!(minutes.is_finite());
+                assert!(minutes >= MIN_WAIT_MINUTES as f64 - 1e-9);
+                assert!(minutes <= MAX_WAIT_MINUTES as f64 + 1e-9);
+            }
+
+            if valid_count == 0 {
+                assert_eq!(estimate.wait_time_minutes, None);
+                assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+                assert_eq!(estimate.error_code, Some(WaitTimeErrorCode::NoData));
+            } else {
+                assert_eq!(estimate.error_code, None);
+                if error_count > 0 {
+                    assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+                }
+            }
+        }
+    }
+});
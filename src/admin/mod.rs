@@ -0,0 +1,375 @@
+//! Runtime calibration/control command server.
+//!
+//! `AppState` already exposes `set_calibration`/`set_model`, but nothing let
+//! an operator reach them without a restart. This module runs a line-oriented
+//! text protocol over a loopback TCP socket so calibration can be tuned in
+//! the field: `GET wait_time`, `SET slope 0.3`, `SET threshold_mm 1100`,
+//! `SELECT model linear_v1`, `RELOAD calibration`. Every mutation rebuilds
+//! the `EstimationModel` from the in-memory `CalibrationFile` and atomically
+//! swaps it into `AppState` via `set_model`, echoing back the effective
+//! params on success so a field tech can confirm the change took.
+
+use crate::estimation::{self, CalibrationFile};
+use crate::state::AppState;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("unknown command: {0}")]
+    UnknownCommand(String),
+    #[error("missing argument for {0}")]
+    MissingArgument(&'static str),
+    #[error("invalid value for {0}: {1}")]
+    InvalidValue(String, String),
+    #[error("failed to read calibration file: {0}")]
+    Read(#[from] std::io::Error),
+    #[error(transparent)]
+    Calibration(#[from] estimation::CalibrationError),
+}
+
+/// Shared, mutable calibration state the command server reads and rewrites.
+struct AdminState {
+    state: Arc<RwLock<AppState>>,
+    calibration: Mutex<CalibrationFile>,
+    calibration_path: PathBuf,
+}
+
+/// Spawn the admin command server on `addr`, serving each accepted
+/// connection as its own concurrent command session.
+pub fn spawn_command_server(
+    addr: SocketAddr,
+    state: Arc<RwLock<AppState>>,
+    calibration: CalibrationFile,
+    calibration_path: PathBuf,
+) -> JoinHandle<()> {
+    let admin_state = Arc::new(AdminState {
+        state,
+        calibration: Mutex::new(calibration),
+        calibration_path,
+    });
+
+    tokio::spawn(async move {
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                warn!(%addr, error = %err, "Failed to bind admin command server");
+                return;
+            }
+        };
+
+        loop {
+            let (socket, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    warn!(error = %err, "Admin command server accept failed");
+                    continue;
+                }
+            };
+
+            let admin_state = Arc::clone(&admin_state);
+            tokio::spawn(async move {
+                if let Err(err) = handle_connection(socket, admin_state).await {
+                    warn!(%peer, error = %err, "Admin connection ended with error");
+                }
+            });
+        }
+    })
+}
+
+async fn handle_connection(
+    socket: tokio::net::TcpStream,
+    admin_state: Arc<AdminState>,
+) -> Result<(), std::io::Error> {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let reply = match handle_command(trimmed, &admin_state) {
+            Ok(reply) => reply,
+            Err(err) => format!("ERR {err}"),
+        };
+
+        writer.write_all(reply.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+
+    Ok(())
+}
+
+fn handle_command(line: &str, admin_state: &AdminState) -> Result<String, AdminError> {
+    let mut parts = line.split_whitespace();
+    let verb = parts.next().unwrap_or_default();
+
+    match verb.to_ascii_uppercase().as_str() {
+        "GET" => {
+            let key = parts.next().ok_or(AdminError::MissingArgument("GET"))?;
+            handle_get(key, admin_state)
+        }
+        "SET" => {
+            let key = parts.next().ok_or(AdminError::MissingArgument("SET"))?;
+            let value = parts.next().ok_or(AdminError::MissingArgument("SET"))?;
+            handle_set(key, value, admin_state)
+        }
+        "SELECT" => {
+            let target = parts.next().ok_or(AdminError::MissingArgument("SELECT"))?;
+            let name = parts.next().ok_or(AdminError::MissingArgument("SELECT"))?;
+            if !target.eq_ignore_ascii_case("model") {
+                return Err(AdminError::UnknownCommand(format!("SELECT {target}")));
+            }
+            handle_select_model(name, admin_state)
+        }
+        "RELOAD" => {
+            let target = parts.next().ok_or(AdminError::MissingArgument("RELOAD"))?;
+            if !target.eq_ignore_ascii_case("calibration") {
+                return Err(AdminError::UnknownCommand(format!("RELOAD {target}")));
+            }
+            handle_reload_calibration(admin_state)
+        }
+        other => Err(AdminError::UnknownCommand(other.to_string())),
+    }
+}
+
+fn handle_get(key: &str, admin_state: &AdminState) -> Result<String, AdminError> {
+    match key.to_ascii_lowercase().as_str() {
+        "wait_time" => {
+            let guard = admin_state.state.read().unwrap_or_else(|p| p.into_inner());
+            Ok(match guard.wait_time() {
+                Some(estimate) => format!(
+                    "OK wait_time_minutes={} status={:?}",
+                    estimate
+                        .wait_time_minutes
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "none".to_string()),
+                    estimate.status
+                ),
+                None => "OK wait_time_minutes=none status=unknown".to_string(),
+            })
+        }
+        "calibration" => {
+            let calibration = admin_state
+                .calibration
+                .lock()
+                .unwrap_or_else(|p| p.into_inner());
+            Ok(format!(
+                "OK model={} params={}",
+                calibration.model, calibration.params
+            ))
+        }
+        "model" => {
+            let calibration = admin_state
+                .calibration
+                .lock()
+                .unwrap_or_else(|p| p.into_inner());
+            Ok(format!("OK model={}", calibration.model))
+        }
+        other => Err(AdminError::UnknownCommand(format!("GET {other}"))),
+    }
+}
+
+fn handle_set(key: &str, value: &str, admin_state: &AdminState) -> Result<String, AdminError> {
+    let mut calibration = admin_state
+        .calibration
+        .lock()
+        .unwrap_or_else(|p| p.into_inner());
+    let mut candidate = calibration.clone();
+
+    match key.to_ascii_lowercase().as_str() {
+        "threshold_mm" => {
+            let parsed: u16 = value
+                .parse()
+                .map_err(|_| AdminError::InvalidValue(key.to_string(), value.to_string()))?;
+            candidate.occupancy_threshold_mm = Some(parsed);
+        }
+        "sensor_min_mm" => {
+            let parsed: u16 = value
+                .parse()
+                .map_err(|_| AdminError::InvalidValue(key.to_string(), value.to_string()))?;
+            candidate.sensor_min_mm = Some(parsed);
+        }
+        "sensor_max_mm" => {
+            let parsed: u16 = value
+                .parse()
+                .map_err(|_| AdminError::InvalidValue(key.to_string(), value.to_string()))?;
+            candidate.sensor_max_mm = Some(parsed);
+        }
+        param_key => {
+            let parsed: f64 = value
+                .parse()
+                .map_err(|_| AdminError::InvalidValue(key.to_string(), value.to_string()))?;
+            if !parsed.is_finite() {
+                return Err(AdminError::InvalidValue(key.to_string(), value.to_string()));
+            }
+            let object = candidate
+                .params
+                .as_object_mut()
+                .ok_or_else(|| AdminError::InvalidValue(key.to_string(), value.to_string()))?;
+            object.insert(param_key.to_string(), serde_json::json!(parsed));
+        }
+    }
+
+    let model = estimation::create_model(&candidate)?;
+    let occupancy_config = model.occupancy_config().clone();
+
+    {
+        let mut guard = admin_state.state.write().unwrap_or_else(|p| p.into_inner());
+        guard.set_model(Arc::from(model));
+        guard.set_calibration(calibration_params_from(&candidate));
+    }
+    *calibration = candidate;
+
+    Ok(format!(
+        "OK model={} threshold_mm={} params={}",
+        calibration.model, occupancy_config.threshold_mm, calibration.params
+    ))
+}
+
+/// Extracts a `CalibrationParams` summary from a calibration file's raw
+/// params, for operators inspecting `AppState::calibration()` directly.
+/// Models without a slope/intercept formula simply have no summary.
+fn calibration_params_from(calibration: &CalibrationFile) -> Option<crate::state::CalibrationParams> {
+    let slope = calibration.params.get("slope")?.as_f64()?;
+    let intercept = calibration.params.get("intercept")?.as_f64()?;
+    Some(crate::state::CalibrationParams {
+        slope,
+        intercept,
+        min_wait_minutes: calibration
+            .params
+            .get("min_wait_minutes")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32),
+        max_wait_minutes: calibration
+            .params
+            .get("max_wait_minutes")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32),
+    })
+}
+
+fn handle_select_model(name: &str, admin_state: &AdminState) -> Result<String, AdminError> {
+    let mut calibration = admin_state
+        .calibration
+        .lock()
+        .unwrap_or_else(|p| p.into_inner());
+    let mut candidate = calibration.clone();
+    candidate.model = name.to_string();
+
+    let model = estimation::create_model(&candidate)?;
+
+    {
+        let mut guard = admin_state.state.write().unwrap_or_else(|p| p.into_inner());
+        guard.set_model(Arc::from(model));
+        guard.set_calibration(calibration_params_from(&candidate));
+    }
+    *calibration = candidate;
+
+    info!(model = name, "Admin command selected a new estimation model");
+    Ok(format!("OK model={}", calibration.model))
+}
+
+fn handle_reload_calibration(admin_state: &AdminState) -> Result<String, AdminError> {
+    let contents = std::fs::read_to_string(&admin_state.calibration_path)?;
+    let reloaded: CalibrationFile = serde_json::from_str(&contents)
+        .map_err(estimation::CalibrationError::Parse)?;
+    let model = estimation::create_model(&reloaded)?;
+
+    {
+        let mut guard = admin_state.state.write().unwrap_or_else(|p| p.into_inner());
+        guard.set_model(Arc::from(model));
+        guard.set_calibration(calibration_params_from(&reloaded));
+    }
+    let mut calibration = admin_state
+        .calibration
+        .lock()
+        .unwrap_or_else(|p| p.into_inner());
+    *calibration = reloaded;
+
+    info!("Admin command reloaded calibration from disk");
+    Ok(format!("OK model={} reloaded=true", calibration.model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::RwLock;
+
+    fn admin_state_with(model: &str, params: serde_json::Value) -> AdminState {
+        AdminState {
+            state: Arc::new(RwLock::new(AppState::new())),
+            calibration: Mutex::new(CalibrationFile {
+                model: model.to_string(),
+                occupancy_threshold_mm: None,
+                sensor_min_mm: None,
+                sensor_max_mm: None,
+                distance_median_window: None,
+                occupancy_hysteresis_mm: None,
+                occupancy_debounce_n: None,
+                occupancy_debounce_m: None,
+                params,
+            }),
+            calibration_path: PathBuf::from("/dev/null"),
+        }
+    }
+
+    #[test]
+    fn set_threshold_mm_rebuilds_and_swaps_model() {
+        let admin_state = admin_state_with(
+            "linear_v2",
+            serde_json::json!({
+                "breakpoints": [{"occupancy_percent": 0.0, "wait_minutes": 0.0}, {"occupancy_percent": 100.0, "wait_minutes": 20.0}]
+            }),
+        );
+
+        let reply = handle_command("SET threshold_mm 1500", &admin_state).expect("command ok");
+
+        assert!(reply.starts_with("OK"));
+        assert!(reply.contains("threshold_mm=1500"));
+    }
+
+    #[test]
+    fn set_non_numeric_param_value_is_rejected() {
+        let admin_state = admin_state_with("deglitch_v1", serde_json::json!({"window_len": 5}));
+
+        let err = handle_command("SET window_len notanumber", &admin_state).unwrap_err();
+
+        assert!(matches!(err, AdminError::InvalidValue(_, _)));
+    }
+
+    #[test]
+    fn select_unknown_model_is_rejected() {
+        let admin_state = admin_state_with("deglitch_v1", serde_json::json!({"window_len": 5}));
+
+        let err = handle_command("SELECT model not_a_model", &admin_state).unwrap_err();
+
+        assert!(matches!(err, AdminError::Calibration(_)));
+    }
+
+    #[test]
+    fn get_model_reports_current_selection() {
+        let admin_state = admin_state_with("ewma_v1", serde_json::json!({"tau_secs": 30.0, "slope": 0.2, "intercept": 0.0}));
+
+        let reply = handle_command("GET model", &admin_state).expect("command ok");
+
+        assert_eq!(reply, "OK model=ewma_v1");
+    }
+
+    #[test]
+    fn unknown_verb_is_rejected() {
+        let admin_state = admin_state_with("ewma_v1", serde_json::json!({}));
+
+        let err = handle_command("FROB whatever", &admin_state).unwrap_err();
+
+        assert!(matches!(err, AdminError::UnknownCommand(_)));
+    }
+}
@@ -1,17 +1,32 @@
 use crate::api::responses::{
-    HealthErrorCode, HealthErrorResponse, HealthStatus, HealthSuccessResponse, QueueErrorCode,
-    QueueErrorResponse, QueueSuccessResponse, SensorErrorCode, SensorStatus, SensorStatusResponse,
-    SensorsErrorCode, SensorsErrorResponse, SensorsSuccessResponse,
+    ConfigEntryResponse, ConfigErrorCode, ConfigErrorResponse, ConfigSuccessResponse,
+    HealthErrorCode, HealthErrorResponse, HealthStatus, HealthSuccessResponse, HistoryErrorCode,
+    HistoryErrorResponse, HistoryPointResponse, HistorySuccessResponse, QueueErrorCode,
+    QueueErrorResponse, QueueSuccessResponse, ReadingsSnapshotResponse, SensorErrorCode,
+    SensorHistoryErrorCode, SensorHistoryErrorResponse, SensorHistoryPointResponse,
+    SensorHistorySuccessResponse, SensorStatus, SensorStatusResponse, SensorsErrorCode,
+    SensorsErrorResponse, SensorsSuccessResponse, SnapshotResponse,
+};
+use crate::runtime_config::RuntimeConfigStore;
+use crate::sensor::{
+    DeviceSensorError, I2C_7BIT_MAX, SensorAddress, SensorId, SensorStatus as DeviceSensorStatus,
 };
-use crate::sensor::{I2C_7BIT_MAX, SensorStatus as DeviceSensorStatus};
 use crate::state::{AppState, WaitTimeStatus};
+use crate::storage::HistoryStore;
+use crate::telemetry::prometheus::PrometheusMetrics;
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
+use futures::stream::{self, Stream};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::convert::Infallible;
 use std::fmt;
 use std::sync::{Arc, RwLock};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 use tracing::error;
@@ -97,7 +112,7 @@ pub async fn get_sensors(State(state): State<Arc<RwLock<AppState>>>) -> impl Int
     build_sensors_response(state, SystemTime::now())
 }
 
-fn build_queue_response(state: Arc<RwLock<AppState>>) -> QueueResponse {
+pub(crate) fn build_queue_response(state: Arc<RwLock<AppState>>) -> QueueResponse {
     let guard = match state.read() {
         Ok(guard) => guard,
         Err(_) => {
@@ -170,12 +185,12 @@ fn internal_error(message: &str) -> QueueResponse {
     }
 }
 
-fn format_timestamp(timestamp: SystemTime) -> Result<String, TimestampError> {
+pub(crate) fn format_timestamp(timestamp: SystemTime) -> Result<String, TimestampError> {
     let datetime = OffsetDateTime::from(timestamp);
     datetime.format(&Rfc3339).map_err(TimestampError::Format)
 }
 
-fn build_health_response(state: Arc<RwLock<AppState>>, now: SystemTime) -> HealthResponse {
+pub(crate) fn build_health_response(state: Arc<RwLock<AppState>>, now: SystemTime) -> HealthResponse {
     let guard = match state.read() {
         Ok(guard) => guard,
         Err(_) => {
@@ -216,6 +231,7 @@ fn derive_health_status(sensors: &[crate::sensor::SensorInfo]) -> HealthStatus {
         match sensor.status {
             DeviceSensorStatus::Ready => has_ready = true,
             DeviceSensorStatus::Error { .. } => has_error = true,
+            DeviceSensorStatus::Provisional => {}
         }
     }
 
@@ -248,7 +264,7 @@ fn health_internal_error(message: &str) -> HealthResponse {
     }
 }
 
-fn build_sensors_response(state: Arc<RwLock<AppState>>, now: SystemTime) -> SensorsResponse {
+pub(crate) fn build_sensors_response(state: Arc<RwLock<AppState>>, now: SystemTime) -> SensorsResponse {
     let guard = match state.read() {
         Ok(guard) => guard,
         Err(_) => {
@@ -287,28 +303,47 @@ fn build_sensors_response(state: Arc<RwLock<AppState>>, now: SystemTime) -> Sens
     })
 }
 
-fn map_sensor_info(
+pub(crate) fn map_sensor_info(
     sensor: &crate::sensor::SensorInfo,
 ) -> Result<SensorStatusResponse, &'static str> {
-    if sensor.i2c_address > I2C_7BIT_MAX {
-        return Err("invalid i2c address for sensor status response");
+    // The 7-bit range check only means anything for I2C; a 1-Wire ROM id
+    // has no such ceiling to trip.
+    if let SensorAddress::I2c(address) = &sensor.address {
+        if *address > I2C_7BIT_MAX {
+            return Err("invalid i2c address for sensor status response");
+        }
     }
     let (status, error_code) = match &sensor.status {
         DeviceSensorStatus::Ready => (SensorStatus::Ok, None),
-        DeviceSensorStatus::Error { message } => {
-            (SensorStatus::Error, Some(map_sensor_error_code(message)))
-        }
+        DeviceSensorStatus::Provisional => (SensorStatus::Provisional, None),
+        DeviceSensorStatus::Error { error } => (SensorStatus::Error, Some(map_sensor_error_code(error))),
     };
 
     Ok(SensorStatusResponse {
         sensor_id: format!("sensor-{}", sensor.sensor_id),
-        i2c_address: format!("0x{:02x}", sensor.i2c_address),
+        i2c_address: sensor.address.display(),
         status,
         error_code,
     })
 }
 
-fn map_sensor_error_code(message: &str) -> SensorErrorCode {
+pub(crate) fn map_sensor_error_code(error: &DeviceSensorError) -> SensorErrorCode {
+    match error {
+        DeviceSensorError::Timeout => SensorErrorCode::Timeout,
+        DeviceSensorError::OutOfRange | DeviceSensorError::InvalidReading => {
+            SensorErrorCode::InvalidReading
+        }
+        DeviceSensorError::NoResponse => SensorErrorCode::NoResponse,
+        DeviceSensorError::Other(message) => map_sensor_error_code_from_message(message),
+    }
+}
+
+/// Fallback for [`DeviceSensorError::Other`], whose message text is all we
+/// have to go on. Kept only for that case now that the driver layer
+/// classifies everything else directly. Also used directly by
+/// [`crate::storage`], whose recorded readings carry a free-form
+/// `ReadingStatus::Error` reason rather than a [`DeviceSensorError`].
+pub(crate) fn map_sensor_error_code_from_message(message: &str) -> SensorErrorCode {
     let message_lower = message.to_lowercase();
     if message_lower.contains("i2c") {
         SensorErrorCode::I2cError
@@ -357,6 +392,141 @@ fn sensors_internal_error(message: &str) -> SensorsResponse {
     }
 }
 
+#[derive(Clone)]
+pub struct ConfigApiState {
+    pub app_state: Arc<RwLock<AppState>>,
+    pub runtime_config: Arc<RwLock<RuntimeConfigStore>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetConfigEntryRequest {
+    pub value: String,
+}
+
+pub enum ConfigResponse {
+    Success(ConfigSuccessResponse),
+    Error {
+        status: StatusCode,
+        body: ConfigErrorResponse,
+    },
+}
+
+impl IntoResponse for ConfigResponse {
+    fn into_response(self) -> Response {
+        match self {
+            ConfigResponse::Success(body) => (StatusCode::OK, Json(body)).into_response(),
+            ConfigResponse::Error { status, body } => (status, Json(body)).into_response(),
+        }
+    }
+}
+
+pub async fn get_config(State(state): State<ConfigApiState>) -> impl IntoResponse {
+    build_config_response(state)
+}
+
+pub async fn put_config_entry(
+    State(state): State<ConfigApiState>,
+    Path(key): Path<String>,
+    Json(body): Json<SetConfigEntryRequest>,
+) -> impl IntoResponse {
+    build_set_config_response(state, key, body.value)
+}
+
+pub async fn delete_config_entry(
+    State(state): State<ConfigApiState>,
+    Path(key): Path<String>,
+) -> impl IntoResponse {
+    build_remove_config_response(state, key)
+}
+
+fn build_config_response(state: ConfigApiState) -> ConfigResponse {
+    let guard = match state.runtime_config.read() {
+        Ok(guard) => guard,
+        Err(_) => {
+            return config_internal_error("runtime config lock poisoned while reading entries");
+        }
+    };
+    let entries = guard
+        .entries()
+        .map(|(key, value)| ConfigEntryResponse {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+        .collect();
+    drop(guard);
+
+    match format_timestamp(SystemTime::now()) {
+        Ok(formatted) => ConfigResponse::Success(ConfigSuccessResponse {
+            entries,
+            timestamp: formatted,
+        }),
+        Err(_) => config_internal_error("timestamp formatting failure"),
+    }
+}
+
+fn build_set_config_response(state: ConfigApiState, key: String, value: String) -> ConfigResponse {
+    {
+        let mut guard = match state.runtime_config.write() {
+            Ok(guard) => guard,
+            Err(_) => {
+                return config_internal_error("runtime config lock poisoned while writing entry");
+            }
+        };
+        if let Err(err) = guard.set(&key, &value) {
+            error!(error = %err, "Failed to persist runtime config entry");
+            return config_internal_error("failed to persist runtime config entry");
+        }
+    }
+    request_rediscovery(&state);
+    build_config_response(state)
+}
+
+fn build_remove_config_response(state: ConfigApiState, key: String) -> ConfigResponse {
+    {
+        let mut guard = match state.runtime_config.write() {
+            Ok(guard) => guard,
+            Err(_) => {
+                return config_internal_error("runtime config lock poisoned while removing entry");
+            }
+        };
+        if let Err(err) = guard.remove(&key) {
+            error!(error = %err, "Failed to persist runtime config removal");
+            return config_internal_error("failed to persist runtime config removal");
+        }
+    }
+    request_rediscovery(&state);
+    build_config_response(state)
+}
+
+fn request_rediscovery(state: &ConfigApiState) {
+    match state.app_state.write() {
+        Ok(mut guard) => guard.request_rediscovery(),
+        Err(_) => error!("state lock poisoned while requesting rediscovery"),
+    }
+}
+
+fn config_internal_error(message: &str) -> ConfigResponse {
+    error!(
+        message = message,
+        "Internal error while handling /api/config"
+    );
+    let formatted = format_timestamp(SystemTime::now()).unwrap_or_else(|err| {
+        error!(error = %err, "Failed to format config error timestamp");
+        OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+    });
+
+    ConfigResponse::Error {
+        status: StatusCode::INTERNAL_SERVER_ERROR,
+        body: ConfigErrorResponse {
+            error_code: ConfigErrorCode::InternalError,
+            error_message: INTERNAL_ERROR_MESSAGE.to_string(),
+            timestamp: formatted,
+        },
+    }
+}
+
 // Debug Readings Handler
 
 use crate::state::ReadingStatus;
@@ -373,7 +543,14 @@ pub struct DebugReadingsResponse {
 pub struct DebugSensorReading {
     pub sensor_id: u32,
     pub distance_mm: u16,
+    /// Bare `distance_mm <= threshold_mm` comparison, ignoring hysteresis
+    /// and debouncing - what a single raw reading would report in
+    /// isolation.
     pub obstructed: Option<bool>,
+    /// The debounced value actually used for occupancy, as computed by
+    /// [`crate::bus::hysteresis::HysteresisDebouncer`] in the refresh
+    /// pipeline.
+    pub obstructed_debounced: Option<bool>,
     pub status: String,
 }
 
@@ -404,6 +581,7 @@ fn build_debug_readings_response(
         .unwrap_or(1200);
 
     let readings = guard.readings();
+    let obstructions = guard.obstructions();
     let mut sensors = Vec::with_capacity(readings.len());
     for reading in readings {
         let (status_str, is_valid) = match &reading.status {
@@ -417,10 +595,16 @@ fn build_debug_readings_response(
             None
         };
 
+        let obstructed_debounced = obstructions
+            .iter()
+            .find(|obstruction| obstruction.sensor_id == reading.sensor_id)
+            .and_then(|obstruction| obstruction.obstructed);
+
         sensors.push(DebugSensorReading {
             sensor_id: reading.sensor_id,
             distance_mm: reading.distance_mm,
             obstructed,
+            obstructed_debounced,
             status: status_str,
         });
     }
@@ -438,6 +622,656 @@ fn build_debug_readings_response(
     )
 }
 
+// Snapshot Handler
+
+/// Bumped whenever a section's shape or the top-level envelope changes, so
+/// archivers can detect a layout change instead of guessing from the field
+/// set.
+const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotParams {
+    /// Comma-separated subset of `queue,health,sensors,readings` to
+    /// include; all four when omitted.
+    pub groups: Option<String>,
+}
+
+pub async fn get_snapshot(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Query(params): Query<SnapshotParams>,
+) -> impl IntoResponse {
+    let groups = TelemetryGroups::parse(params.groups.as_deref());
+    build_snapshot_response(state, groups, SystemTime::now())
+}
+
+fn build_snapshot_response(
+    state: Arc<RwLock<AppState>>,
+    groups: TelemetryGroups,
+    now: SystemTime,
+) -> Response {
+    let guard = match state.read() {
+        Ok(guard) => guard,
+        Err(_) => {
+            error!("state lock poisoned while building snapshot");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "state lock poisoned"})),
+            )
+                .into_response();
+        }
+    };
+
+    // Everything below reads from this single guard, so the sections all
+    // describe the same moment instead of racing four separate lock
+    // acquisitions.
+    let queue = groups.queue.then(|| snapshot_queue_section(&guard));
+    let health = groups.health.then(|| snapshot_health_section(&guard, now));
+    let sensors = groups.sensors.then(|| snapshot_sensors_section(&guard, now));
+    let readings = groups.readings.then(|| snapshot_readings_section(&guard, now));
+    drop(guard);
+
+    let timestamp = format_timestamp(now).unwrap_or_else(|_| "unknown".to_string());
+
+    Json(SnapshotResponse {
+        schema_version: SNAPSHOT_SCHEMA_VERSION,
+        timestamp,
+        queue,
+        health,
+        sensors,
+        readings,
+    })
+    .into_response()
+}
+
+fn snapshot_queue_section(guard: &AppState) -> serde_json::Value {
+    let response = match guard.wait_time().cloned() {
+        Some(estimate) if estimate.status == WaitTimeStatus::Ok => {
+            match estimate.wait_time_minutes {
+                Some(wait_time_minutes) if wait_time_minutes.is_finite() && wait_time_minutes >= 0.0 => {
+                    success_response(wait_time_minutes, estimate.timestamp)
+                }
+                _ => internal_error("wait_time status ok but value missing or invalid"),
+            }
+        }
+        Some(estimate) => no_data_response(estimate.timestamp),
+        None => no_data_response(SystemTime::now()),
+    };
+
+    match response {
+        QueueResponse::Success(body) => serde_json::to_value(body),
+        QueueResponse::Error { body, .. } => serde_json::to_value(body),
+    }
+    .unwrap_or_else(|err| {
+        error!(error = %err, "Failed to serialize queue snapshot section");
+        serde_json::json!({"error": "serialization failure"})
+    })
+}
+
+fn snapshot_health_section(guard: &AppState, now: SystemTime) -> serde_json::Value {
+    let status = derive_health_status(guard.sensors());
+    let response = match format_timestamp(now) {
+        Ok(timestamp) => HealthResponse::Success {
+            status: StatusCode::OK,
+            body: HealthSuccessResponse { status, timestamp },
+        },
+        Err(_) => health_internal_error("timestamp formatting failure"),
+    };
+
+    match response {
+        HealthResponse::Success { body, .. } => serde_json::to_value(body),
+        HealthResponse::Error { body, .. } => serde_json::to_value(body),
+    }
+    .unwrap_or_else(|err| {
+        error!(error = %err, "Failed to serialize health snapshot section");
+        serde_json::json!({"error": "serialization failure"})
+    })
+}
+
+fn snapshot_sensors_section(guard: &AppState, now: SystemTime) -> serde_json::Value {
+    let sensors = guard.sensors();
+    let response = if sensors.is_empty() {
+        sensors_unavailable_response(now)
+    } else {
+        let mut mapped_sensors = Vec::with_capacity(sensors.len());
+        let mut mapping_error = None;
+        for sensor in sensors {
+            match map_sensor_info(sensor) {
+                Ok(mapped) => mapped_sensors.push(mapped),
+                Err(message) => {
+                    mapping_error = Some(message);
+                    break;
+                }
+            }
+        }
+
+        match mapping_error {
+            Some(message) => sensors_internal_error(message),
+            None => match format_timestamp(now) {
+                Ok(timestamp) => SensorsResponse::Success(SensorsSuccessResponse {
+                    sensors: mapped_sensors,
+                    timestamp,
+                }),
+                Err(_) => sensors_internal_error("timestamp formatting failure"),
+            },
+        }
+    };
+
+    match response {
+        SensorsResponse::Success(body) => serde_json::to_value(body),
+        SensorsResponse::Error { body, .. } => serde_json::to_value(body),
+    }
+    .unwrap_or_else(|err| {
+        error!(error = %err, "Failed to serialize sensors snapshot section");
+        serde_json::json!({"error": "serialization failure"})
+    })
+}
+
+fn snapshot_readings_section(guard: &AppState, now: SystemTime) -> serde_json::Value {
+    let threshold_mm = guard.model().occupancy_config().threshold_mm;
+    let readings = guard.readings();
+    let obstructions = guard.obstructions();
+
+    let mut sensors = Vec::with_capacity(readings.len());
+    for reading in readings {
+        let (status_str, is_valid) = match &reading.status {
+            ReadingStatus::Ok { range_status } => (format!("ok ({:?})", range_status), true),
+            ReadingStatus::Error { reason } => (format!("error: {}", reason), false),
+        };
+
+        let obstructed = if is_valid {
+            Some(reading.distance_mm <= threshold_mm)
+        } else {
+            None
+        };
+
+        let obstructed_debounced = obstructions
+            .iter()
+            .find(|obstruction| obstruction.sensor_id == reading.sensor_id)
+            .and_then(|obstruction| obstruction.obstructed);
+
+        sensors.push(DebugSensorReading {
+            sensor_id: reading.sensor_id,
+            distance_mm: reading.distance_mm,
+            obstructed,
+            obstructed_debounced,
+            status: status_str,
+        });
+    }
+
+    let timestamp = format_timestamp(now).unwrap_or_else(|_| "unknown".to_string());
+    serde_json::to_value(DebugReadingsResponse {
+        occupancy_threshold_mm: threshold_mm,
+        sensors,
+        timestamp,
+    })
+    .unwrap_or_else(|err| {
+        error!(error = %err, "Failed to serialize readings snapshot section");
+        serde_json::json!({"error": "serialization failure"})
+    })
+}
+
+// History Handler
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQueryParams {
+    /// Only return samples at or after this many seconds ago.
+    pub window_secs: Option<u64>,
+    /// Downsample to at most this many points, evenly spanning the window.
+    pub max_samples: Option<usize>,
+}
+
+pub async fn get_history(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Query(params): Query<HistoryQueryParams>,
+) -> impl IntoResponse {
+    build_history_response(state, params, SystemTime::now())
+}
+
+fn build_history_response(
+    state: Arc<RwLock<AppState>>,
+    params: HistoryQueryParams,
+    now: SystemTime,
+) -> Response {
+    let guard = match state.read() {
+        Ok(guard) => guard,
+        Err(_) => return history_internal_error("state lock poisoned while reading history"),
+    };
+
+    let since = params
+        .window_secs
+        .map(|window_secs| now - Duration::from_secs(window_secs));
+    let estimates = guard.history_query(since, params.max_samples);
+    drop(guard);
+
+    let mut points = Vec::with_capacity(estimates.len());
+    for estimate in estimates {
+        let timestamp = match format_timestamp(estimate.timestamp) {
+            Ok(formatted) => formatted,
+            Err(_) => return history_internal_error("timestamp formatting failure"),
+        };
+        points.push(HistoryPointResponse {
+            wait_time_minutes: estimate.wait_time_minutes,
+            status: estimate.status,
+            error_code: estimate.error_code,
+            timestamp,
+        });
+    }
+
+    match format_timestamp(now) {
+        Ok(timestamp) => {
+            (StatusCode::OK, Json(HistorySuccessResponse { points, timestamp })).into_response()
+        }
+        Err(_) => history_internal_error("timestamp formatting failure"),
+    }
+}
+
+fn history_internal_error(message: &str) -> Response {
+    error!(
+        message = message,
+        "Internal error while handling /api/history"
+    );
+    let timestamp = format_timestamp(SystemTime::now()).unwrap_or_else(|err| {
+        error!(error = %err, "Failed to format history error timestamp");
+        OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+    });
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(HistoryErrorResponse {
+            error_code: HistoryErrorCode::InternalError,
+            error_message: INTERNAL_ERROR_MESSAGE.to_string(),
+            timestamp,
+        }),
+    )
+        .into_response()
+}
+
+// Per-sensor history handler
+
+#[derive(Debug, Deserialize)]
+pub struct SensorHistoryQueryParams {
+    /// RFC3339 lower bound, inclusive (e.g. `2026-01-11T12:00:00Z`).
+    pub from: Option<String>,
+    /// RFC3339 upper bound, inclusive.
+    pub to: Option<String>,
+    /// Downsample to at most this many points, evenly spanning the range.
+    pub max_samples: Option<usize>,
+}
+
+pub async fn get_sensor_history(
+    State(store): State<Arc<dyn HistoryStore>>,
+    Path(sensor_id): Path<SensorId>,
+    Query(params): Query<SensorHistoryQueryParams>,
+) -> impl IntoResponse {
+    build_sensor_history_response(store, sensor_id, params, SystemTime::now())
+}
+
+fn build_sensor_history_response(
+    store: Arc<dyn HistoryStore>,
+    sensor_id: SensorId,
+    params: SensorHistoryQueryParams,
+    now: SystemTime,
+) -> Response {
+    let from = match parse_query_timestamp(params.from.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return sensor_history_bad_range(message),
+    };
+    let to = match parse_query_timestamp(params.to.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return sensor_history_bad_range(message),
+    };
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return sensor_history_bad_range("`from` must not be after `to`".to_string());
+        }
+    }
+
+    let samples = crate::storage::downsample(store.query(sensor_id, from, to), params.max_samples);
+
+    let mut points = Vec::with_capacity(samples.len());
+    for sample in samples {
+        let timestamp = match format_timestamp(sample.timestamp) {
+            Ok(formatted) => formatted,
+            Err(_) => return sensor_history_internal_error("timestamp formatting failure"),
+        };
+        points.push(SensorHistoryPointResponse {
+            distance_mm: sample.distance_mm,
+            status: sample.status,
+            error_code: sample.error_code,
+            timestamp,
+        });
+    }
+
+    match format_timestamp(now) {
+        Ok(timestamp) => (
+            StatusCode::OK,
+            Json(SensorHistorySuccessResponse {
+                sensor_id,
+                points,
+                timestamp,
+            }),
+        )
+            .into_response(),
+        Err(_) => sensor_history_internal_error("timestamp formatting failure"),
+    }
+}
+
+fn parse_query_timestamp(raw: Option<&str>) -> Result<Option<SystemTime>, String> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    OffsetDateTime::parse(raw, &Rfc3339)
+        .map(|datetime| Some(SystemTime::from(datetime)))
+        .map_err(|_| format!("invalid RFC3339 timestamp: {raw}"))
+}
+
+fn sensor_history_bad_range(message: String) -> Response {
+    let timestamp = format_timestamp(SystemTime::now())
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string());
+
+    (
+        StatusCode::BAD_REQUEST,
+        Json(SensorHistoryErrorResponse {
+            error_code: SensorHistoryErrorCode::InvalidRange,
+            error_message: message,
+            timestamp,
+        }),
+    )
+        .into_response()
+}
+
+fn sensor_history_internal_error(message: &str) -> Response {
+    error!(
+        message = message,
+        "Internal error while handling /api/sensors/{{id}}/history"
+    );
+    let timestamp = format_timestamp(SystemTime::now()).unwrap_or_else(|err| {
+        error!(error = %err, "Failed to format sensor history error timestamp");
+        OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+    });
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(SensorHistoryErrorResponse {
+            error_code: SensorHistoryErrorCode::InternalError,
+            error_message: INTERNAL_ERROR_MESSAGE.to_string(),
+            timestamp,
+        }),
+    )
+        .into_response()
+}
+
+// Prometheus metrics handler
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub async fn get_metrics(State(metrics): State<Arc<PrometheusMetrics>>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        metrics.render(),
+    )
+}
+
+// Telemetry SSE stream handler
+
+/// Periodic full-snapshot cadence used when a client omits `interval_ms`.
+const DEFAULT_TELEMETRY_STREAM_INTERVAL_MS: u64 = 5_000;
+
+#[derive(Debug, Deserialize)]
+pub struct TelemetryStreamParams {
+    /// Cadence, in milliseconds, at which a full snapshot of every enabled
+    /// group is re-sent even without a change - like a housekeeping report's
+    /// enable/disable-able periodic rate.
+    pub interval_ms: Option<u64>,
+    /// Comma-separated subset of `queue,health,sensors,readings` to stream;
+    /// all four when omitted.
+    pub groups: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TelemetryGroups {
+    queue: bool,
+    health: bool,
+    sensors: bool,
+    readings: bool,
+}
+
+impl TelemetryGroups {
+    fn parse(raw: Option<&str>) -> Self {
+        let Some(raw) = raw else {
+            return Self {
+                queue: true,
+                health: true,
+                sensors: true,
+                readings: true,
+            };
+        };
+
+        let mut groups = Self {
+            queue: false,
+            health: false,
+            sensors: false,
+            readings: false,
+        };
+        for name in raw.split(',').map(str::trim) {
+            match name {
+                "queue" => groups.queue = true,
+                "health" => groups.health = true,
+                "sensors" => groups.sensors = true,
+                "readings" => groups.readings = true,
+                _ => {}
+            }
+        }
+        groups
+    }
+}
+
+struct TelemetryStreamState {
+    state: Arc<RwLock<AppState>>,
+    groups: TelemetryGroups,
+    wait_time_rx: tokio::sync::watch::Receiver<Option<crate::state::WaitTimeEstimate>>,
+    sensors_rx: tokio::sync::watch::Receiver<Vec<crate::sensor::SensorInfo>>,
+    readings_rx: tokio::sync::watch::Receiver<Vec<crate::state::SensorReading>>,
+    ticker: tokio::time::Interval,
+    pending: VecDeque<Event>,
+}
+
+pub async fn get_telemetry_stream(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Query(params): Query<TelemetryStreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let groups = TelemetryGroups::parse(params.groups.as_deref());
+    let heartbeat = Duration::from_millis(
+        params
+            .interval_ms
+            .unwrap_or(DEFAULT_TELEMETRY_STREAM_INTERVAL_MS)
+            .max(100),
+    );
+
+    let (wait_time_rx, sensors_rx, readings_rx) = {
+        let guard = state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        (
+            guard.subscribe_wait_time(),
+            guard.subscribe_sensors(),
+            guard.subscribe_readings(),
+        )
+    };
+
+    let pending = snapshot_events(&state, &groups);
+    let ticker = tokio::time::interval_at(tokio::time::Instant::now() + heartbeat, heartbeat);
+
+    let stream_state = TelemetryStreamState {
+        state,
+        groups,
+        wait_time_rx,
+        sensors_rx,
+        readings_rx,
+        ticker,
+        pending,
+    };
+
+    let stream = stream::unfold(stream_state, |mut s| async move {
+        loop {
+            if let Some(event) = s.pending.pop_front() {
+                return Some((Ok(event), s));
+            }
+            tokio::select! {
+                changed = s.wait_time_rx.changed() => {
+                    changed.ok()?;
+                    if s.groups.queue && let Some(event) = queue_event(&s.state) {
+                        s.pending.push_back(event);
+                    }
+                }
+                changed = s.sensors_rx.changed() => {
+                    changed.ok()?;
+                    let now = SystemTime::now();
+                    if s.groups.health && let Some(event) = health_event(&s.state, now) {
+                        s.pending.push_back(event);
+                    }
+                    if s.groups.sensors && let Some(event) = sensors_event(&s.state, now) {
+                        s.pending.push_back(event);
+                    }
+                }
+                changed = s.readings_rx.changed() => {
+                    changed.ok()?;
+                    if s.groups.readings && let Some(event) = readings_event(&s.state, SystemTime::now()) {
+                        s.pending.push_back(event);
+                    }
+                }
+                _ = s.ticker.tick() => {
+                    s.pending.extend(snapshot_events(&s.state, &s.groups));
+                }
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+fn snapshot_events(state: &Arc<RwLock<AppState>>, groups: &TelemetryGroups) -> VecDeque<Event> {
+    let now = SystemTime::now();
+    let mut events = VecDeque::new();
+    if groups.queue && let Some(event) = queue_event(state) {
+        events.push_back(event);
+    }
+    if groups.health && let Some(event) = health_event(state, now) {
+        events.push_back(event);
+    }
+    if groups.sensors && let Some(event) = sensors_event(state, now) {
+        events.push_back(event);
+    }
+    if groups.readings && let Some(event) = readings_event(state, now) {
+        events.push_back(event);
+    }
+    events
+}
+
+fn queue_event(state: &Arc<RwLock<AppState>>) -> Option<Event> {
+    let payload = match build_queue_response(Arc::clone(state)) {
+        QueueResponse::Success(body) => serde_json::to_value(body),
+        QueueResponse::Error { body, .. } => serde_json::to_value(body),
+    };
+    json_event("queue", payload)
+}
+
+fn health_event(state: &Arc<RwLock<AppState>>, now: SystemTime) -> Option<Event> {
+    let payload = match build_health_response(Arc::clone(state), now) {
+        HealthResponse::Success { body, .. } => serde_json::to_value(body),
+        HealthResponse::Error { body, .. } => serde_json::to_value(body),
+    };
+    json_event("health", payload)
+}
+
+fn sensors_event(state: &Arc<RwLock<AppState>>, now: SystemTime) -> Option<Event> {
+    let payload = match build_sensors_response(Arc::clone(state), now) {
+        SensorsResponse::Success(body) => serde_json::to_value(body),
+        SensorsResponse::Error { body, .. } => serde_json::to_value(body),
+    };
+    json_event("sensors", payload)
+}
+
+fn readings_event(state: &Arc<RwLock<AppState>>, now: SystemTime) -> Option<Event> {
+    let guard = state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let readings = guard.readings().to_vec();
+    drop(guard);
+    let timestamp = format_timestamp(now).unwrap_or_else(|_| "unknown".to_string());
+    let payload = serde_json::to_value(ReadingsSnapshotResponse { readings, timestamp });
+    json_event("readings", payload)
+}
+
+fn json_event(name: &'static str, payload: serde_json::Result<serde_json::Value>) -> Option<Event> {
+    match payload {
+        Ok(value) => Event::default().event(name).json_data(value).ok(),
+        Err(err) => {
+            error!(error = %err, group = name, "Failed to serialize telemetry event");
+            None
+        }
+    }
+}
+
+// Sensors SSE stream handler
+
+struct SensorsStreamState {
+    state: Arc<RwLock<AppState>>,
+    sensors_rx: tokio::sync::watch::Receiver<Vec<crate::sensor::SensorInfo>>,
+    pending: Option<Event>,
+}
+
+/// Streams the same body `SensorsResponse::Success` serializes, one `data:`
+/// frame per `set_sensors` update. `AppState`'s sensors channel is a
+/// [`tokio::sync::watch`], not a `broadcast` - so a slow consumer already
+/// gets the coalescing-to-latest behavior a `broadcast` receiver would need
+/// `RecvError::Lagged` handling for: `watch::Receiver::changed` only ever
+/// reports the newest value once the consumer catches up, dropping
+/// anything superseded in between for free.
+pub async fn get_sensors_stream(
+    State(state): State<Arc<RwLock<AppState>>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let sensors_rx = {
+        let guard = state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard.subscribe_sensors()
+    };
+
+    let pending = sensors_stream_event(&state, SystemTime::now());
+
+    let stream_state = SensorsStreamState {
+        state,
+        sensors_rx,
+        pending,
+    };
+
+    let stream = stream::unfold(stream_state, |mut s| async move {
+        loop {
+            if let Some(event) = s.pending.take() {
+                return Some((Ok(event), s));
+            }
+            s.sensors_rx.changed().await.ok()?;
+            s.pending = sensors_stream_event(&s.state, SystemTime::now());
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+fn sensors_stream_event(state: &Arc<RwLock<AppState>>, now: SystemTime) -> Option<Event> {
+    let payload = match build_sensors_response(Arc::clone(state), now) {
+        SensorsResponse::Success(body) => serde_json::to_value(body),
+        SensorsResponse::Error { body, .. } => serde_json::to_value(body),
+    };
+    match payload {
+        Ok(value) => Event::default().json_data(value).ok(),
+        Err(err) => {
+            error!(error = %err, "Failed to serialize sensors stream event");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -549,7 +1383,7 @@ mod tests {
         crate::sensor::SensorInfo {
             sensor_id,
             xshut_pin: 17,
-            i2c_address,
+            address: SensorAddress::I2c(i2c_address),
             status,
         }
     }
@@ -590,7 +1424,7 @@ mod tests {
                 sensor_info_with_address(
                     2,
                     DeviceSensorStatus::Error {
-                        message: "no response".to_string(),
+                        error: DeviceSensorError::NoResponse,
                     },
                     0x31,
                 ),
@@ -620,7 +1454,7 @@ mod tests {
             .set_sensors(vec![sensor_info_with_address(
                 1,
                 DeviceSensorStatus::Error {
-                    message: "failed".to_string(),
+                    error: DeviceSensorError::NoResponse,
                 },
                 0x30,
             )])
@@ -693,7 +1527,7 @@ mod tests {
                 sensor_info_with_address(
                     2,
                     DeviceSensorStatus::Error {
-                        message: "i2c failure".to_string(),
+                        error: DeviceSensorError::Other("i2c failure".to_string()),
                     },
                     0x31,
                 ),
@@ -789,8 +1623,98 @@ mod tests {
         }
     }
 
+    fn config_api_state(store: RuntimeConfigStore) -> ConfigApiState {
+        ConfigApiState {
+            app_state: Arc::new(RwLock::new(AppState::new())),
+            runtime_config: Arc::new(RwLock::new(store)),
+        }
+    }
+
+    #[test]
+    fn config_handler_lists_entries() {
+        let path = std::env::temp_dir().join(format!(
+            "mariam-handlers-config-list-{}.txt",
+            std::process::id()
+        ));
+        let mut store = RuntimeConfigStore::load(&path).expect("load runtime config");
+        store
+            .set("i2c_base_address", "0x40")
+            .expect("set base address");
+        let state = config_api_state(store);
+
+        let response = build_config_response(state);
+        let _ = std::fs::remove_file(&path);
+
+        match response {
+            ConfigResponse::Success(body) => {
+                assert_eq!(body.entries.len(), 1);
+                assert_eq!(body.entries[0].key, "i2c_base_address");
+                assert_eq!(body.entries[0].value, "0x40");
+            }
+            ConfigResponse::Error { status, .. } => {
+                panic!("expected success response, got error: {status}");
+            }
+        }
+    }
+
+    #[test]
+    fn config_handler_set_entry_persists_and_requests_rediscovery() {
+        let path = std::env::temp_dir().join(format!(
+            "mariam-handlers-config-set-{}.txt",
+            std::process::id()
+        ));
+        let store = RuntimeConfigStore::load(&path).expect("load runtime config");
+        let state = config_api_state(store);
+        let app_state = Arc::clone(&state.app_state);
+
+        let response =
+            build_set_config_response(state, "i2c_base_address".to_string(), "0x40".to_string());
+        let _ = std::fs::remove_file(&path);
+
+        match response {
+            ConfigResponse::Success(body) => {
+                assert_eq!(body.entries.len(), 1);
+                assert_eq!(body.entries[0].value, "0x40");
+            }
+            ConfigResponse::Error { status, .. } => {
+                panic!("expected success response, got error: {status}");
+            }
+        }
+        assert!(
+            app_state
+                .write()
+                .expect("app state lock")
+                .take_rediscovery_request()
+        );
+    }
+
+    #[test]
+    fn config_handler_remove_entry_persists_removal() {
+        let path = std::env::temp_dir().join(format!(
+            "mariam-handlers-config-remove-{}.txt",
+            std::process::id()
+        ));
+        let mut store = RuntimeConfigStore::load(&path).expect("load runtime config");
+        store
+            .set("i2c_base_address", "0x40")
+            .expect("set base address");
+        let state = config_api_state(store);
+
+        let response = build_remove_config_response(state, "i2c_base_address".to_string());
+        let _ = std::fs::remove_file(&path);
+
+        match response {
+            ConfigResponse::Success(body) => {
+                assert!(body.entries.is_empty());
+            }
+            ConfigResponse::Error { status, .. } => {
+                panic!("expected success response, got error: {status}");
+            }
+        }
+    }
+
     #[test]
-    fn sensors_handler_maps_error_codes_by_message() {
+    fn sensors_handler_maps_typed_error_codes() {
         let mut app_state = AppState::new();
         let _receiver = app_state.subscribe_sensors();
         app_state
@@ -798,28 +1722,28 @@ mod tests {
                 sensor_info_with_address(
                     1,
                     DeviceSensorStatus::Error {
-                        message: "timeout while reading".to_string(),
+                        error: DeviceSensorError::Timeout,
                     },
                     0x30,
                 ),
                 sensor_info_with_address(
                     2,
                     DeviceSensorStatus::Error {
-                        message: "range out of bounds".to_string(),
+                        error: DeviceSensorError::OutOfRange,
                     },
                     0x31,
                 ),
                 sensor_info_with_address(
                     3,
                     DeviceSensorStatus::Error {
-                        message: "invalid reading".to_string(),
+                        error: DeviceSensorError::InvalidReading,
                     },
                     0x32,
                 ),
                 sensor_info_with_address(
                     4,
                     DeviceSensorStatus::Error {
-                        message: "no response".to_string(),
+                        error: DeviceSensorError::NoResponse,
                     },
                     0x33,
                 ),
@@ -850,4 +1774,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn sensors_handler_falls_back_to_message_matching_for_other_errors() {
+        let mut app_state = AppState::new();
+        let _receiver = app_state.subscribe_sensors();
+        app_state
+            .set_sensors(vec![sensor_info_with_address(
+                1,
+                DeviceSensorStatus::Error {
+                    error: DeviceSensorError::Other("i2c nack".to_string()),
+                },
+                0x30,
+            )])
+            .expect("set sensors");
+        let state = Arc::new(RwLock::new(app_state));
+
+        let response = build_sensors_response(state, UNIX_EPOCH + Duration::from_secs(13));
+
+        match response {
+            SensorsResponse::Success(body) => {
+                assert_eq!(body.sensors[0].error_code, Some(SensorErrorCode::I2cError));
+            }
+            SensorsResponse::Error { status, .. } => {
+                panic!("expected success response, got error: {status}");
+            }
+        }
+    }
 }
@@ -1,4 +1,7 @@
+use crate::runtime_config::RuntimeConfigStore;
 use crate::state::AppState;
+use crate::storage::HistoryStore;
+use crate::telemetry::prometheus::PrometheusMetrics;
 use axum::Router;
 use axum::routing::get;
 use std::sync::{Arc, RwLock};
@@ -6,11 +9,46 @@ use std::sync::{Arc, RwLock};
 pub mod handlers;
 pub mod responses;
 
-pub fn router(state: Arc<RwLock<AppState>>) -> Router {
+pub fn router(
+    state: Arc<RwLock<AppState>>,
+    runtime_config: Arc<RwLock<RuntimeConfigStore>>,
+    metrics: Arc<PrometheusMetrics>,
+    history_store: Arc<dyn HistoryStore>,
+) -> Router {
+    let config_state = handlers::ConfigApiState {
+        app_state: Arc::clone(&state),
+        runtime_config,
+    };
+    let config_routes = Router::new()
+        .route("/api/config", get(handlers::get_config))
+        .route(
+            "/api/config/{key}",
+            axum::routing::put(handlers::put_config_entry).delete(handlers::delete_config_entry),
+        )
+        .with_state(config_state);
+
+    let metrics_routes = Router::new()
+        .route("/api/metrics", get(handlers::get_metrics))
+        .with_state(metrics);
+
+    let sensor_history_routes = Router::new()
+        .route(
+            "/api/sensors/{id}/history",
+            get(handlers::get_sensor_history),
+        )
+        .with_state(history_store);
+
     Router::new()
         .route("/api/queue", get(handlers::get_queue))
         .route("/api/health", get(handlers::get_health))
         .route("/api/sensors", get(handlers::get_sensors))
+        .route("/api/sensors/stream", get(handlers::get_sensors_stream))
         .route("/api/debug/readings", get(handlers::get_debug_readings))
+        .route("/api/history", get(handlers::get_history))
+        .route("/api/telemetry/stream", get(handlers::get_telemetry_stream))
+        .route("/api/snapshot", get(handlers::get_snapshot))
         .with_state(state)
+        .merge(config_routes)
+        .merge(metrics_routes)
+        .merge(sensor_history_routes)
 }
@@ -44,6 +44,7 @@ pub struct HealthErrorResponse {
 #[serde(rename_all = "lowercase")]
 pub enum SensorStatus {
     Ok,
+    Provisional,
     Error,
 }
 
@@ -58,6 +59,8 @@ pub struct SensorsSuccessResponse {
 #[serde(rename_all = "snake_case")]
 pub struct SensorStatusResponse {
     pub sensor_id: String,
+    /// `0xNN` for an I2C sensor, or the bare 1-Wire ROM id for a temperature
+    /// probe - see [`crate::sensor::SensorAddress::display`].
     pub i2c_address: String,
     pub status: SensorStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -101,6 +104,130 @@ pub enum QueueErrorCode {
     InternalError,
 }
 
+#[derive(Debug, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigEntryResponse {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigSuccessResponse {
+    pub entries: Vec<ConfigEntryResponse>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigErrorResponse {
+    pub error_code: ConfigErrorCode,
+    pub error_message: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConfigErrorCode {
+    InternalError,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct HistoryPointResponse {
+    pub wait_time_minutes: Option<f64>,
+    pub status: crate::state::WaitTimeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<crate::state::WaitTimeErrorCode>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HistorySuccessResponse {
+    pub points: Vec<HistoryPointResponse>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HistoryErrorResponse {
+    pub error_code: HistoryErrorCode,
+    pub error_message: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HistoryErrorCode {
+    InternalError,
+}
+
+#[derive(Debug, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SensorHistoryPointResponse {
+    pub distance_mm: u16,
+    pub status: crate::state::ReadingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<SensorErrorCode>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SensorHistorySuccessResponse {
+    pub sensor_id: crate::sensor::SensorId,
+    pub points: Vec<SensorHistoryPointResponse>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SensorHistoryErrorResponse {
+    pub error_code: SensorHistoryErrorCode,
+    pub error_message: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SensorHistoryErrorCode {
+    InvalidRange,
+    InternalError,
+}
+
+/// `readings` SSE group payload for `/api/telemetry/stream` - a raw
+/// snapshot of the latest per-sensor readings, not filtered through any
+/// estimation model.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReadingsSnapshotResponse {
+    pub readings: Vec<crate::state::SensorReading>,
+    pub timestamp: String,
+}
+
+/// `GET /api/snapshot` response: one consistent frame combining whichever
+/// sections `?groups=` requested, all read under a single `state.read()`
+/// guard so they describe the same moment. `schema_version` lets
+/// downstream archivers detect a layout change without guessing from the
+/// field set. Each section embeds exactly the body its standalone endpoint
+/// (`/api/queue`, `/api/health`, `/api/sensors`, `/api/debug/readings`)
+/// would have returned, success or error.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotResponse {
+    pub schema_version: u32,
+    pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensors: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readings: Option<serde_json::Value>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,4 +385,211 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn config_success_response_serializes_entries() {
+        let response = ConfigSuccessResponse {
+            entries: vec![ConfigEntryResponse {
+                key: "i2c_base_address".to_string(),
+                value: "0x40".to_string(),
+            }],
+            timestamp: "2026-01-11T12:36:00Z".to_string(),
+        };
+
+        let value = serde_json::to_value(response).expect("serialize config success response");
+        assert_eq!(
+            value,
+            json!({
+                "entries": [
+                    { "key": "i2c_base_address", "value": "0x40" }
+                ],
+                "timestamp": "2026-01-11T12:36:00Z"
+            })
+        );
+    }
+
+    #[test]
+    fn config_error_response_uses_screaming_snake_case_code() {
+        let response = ConfigErrorResponse {
+            error_code: ConfigErrorCode::InternalError,
+            error_message: "boom".to_string(),
+            timestamp: "2026-01-11T12:37:00Z".to_string(),
+        };
+
+        let value = serde_json::to_value(response).expect("serialize config error response");
+        assert_eq!(
+            value,
+            json!({
+                "error_code": "INTERNAL_ERROR",
+                "error_message": "boom",
+                "timestamp": "2026-01-11T12:37:00Z"
+            })
+        );
+    }
+
+    #[test]
+    fn history_success_response_serializes_points() {
+        use crate::state::{WaitTimeErrorCode, WaitTimeStatus};
+
+        let response = HistorySuccessResponse {
+            points: vec![
+                HistoryPointResponse {
+                    wait_time_minutes: Some(7.0),
+                    status: WaitTimeStatus::Ok,
+                    error_code: None,
+                    timestamp: "2026-01-11T12:38:00Z".to_string(),
+                },
+                HistoryPointResponse {
+                    wait_time_minutes: None,
+                    status: WaitTimeStatus::Degraded,
+                    error_code: Some(WaitTimeErrorCode::NoData),
+                    timestamp: "2026-01-11T12:38:05Z".to_string(),
+                },
+            ],
+            timestamp: "2026-01-11T12:38:10Z".to_string(),
+        };
+
+        let value = serde_json::to_value(response).expect("serialize history success response");
+        assert_eq!(
+            value,
+            json!({
+                "points": [
+                    {
+                        "wait_time_minutes": 7.0,
+                        "status": "ok",
+                        "timestamp": "2026-01-11T12:38:00Z"
+                    },
+                    {
+                        "wait_time_minutes": null,
+                        "status": "degraded",
+                        "error_code": "NO_DATA",
+                        "timestamp": "2026-01-11T12:38:05Z"
+                    }
+                ],
+                "timestamp": "2026-01-11T12:38:10Z"
+            })
+        );
+    }
+
+    #[test]
+    fn readings_snapshot_response_serializes_readings() {
+        use crate::sensor::SensorRangeStatus;
+        use crate::state::{ReadingStatus, SensorReading};
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let response = ReadingsSnapshotResponse {
+            readings: vec![SensorReading {
+                sensor_id: 1,
+                distance_mm: 250,
+                timestamp: UNIX_EPOCH + Duration::from_secs(1),
+                status: ReadingStatus::Ok {
+                    range_status: SensorRangeStatus::Valid,
+                },
+            }],
+            timestamp: "2026-01-11T12:40:00Z".to_string(),
+        };
+
+        let value = serde_json::to_value(response).expect("serialize readings snapshot response");
+        assert_eq!(
+            value["readings"][0]["sensor_id"],
+            json!(1)
+        );
+        assert_eq!(value["timestamp"], json!("2026-01-11T12:40:00Z"));
+    }
+
+    #[test]
+    fn snapshot_response_omits_unrequested_sections() {
+        let response = SnapshotResponse {
+            schema_version: 1,
+            timestamp: "2026-01-11T12:41:00Z".to_string(),
+            queue: Some(json!({"wait_time_minutes": 7.0})),
+            health: None,
+            sensors: None,
+            readings: None,
+        };
+
+        let value = serde_json::to_value(response).expect("serialize snapshot response");
+        assert_eq!(
+            value,
+            json!({
+                "schema_version": 1,
+                "timestamp": "2026-01-11T12:41:00Z",
+                "queue": {"wait_time_minutes": 7.0}
+            })
+        );
+    }
+
+    #[test]
+    fn history_error_response_uses_screaming_snake_case_code() {
+        let response = HistoryErrorResponse {
+            error_code: HistoryErrorCode::InternalError,
+            error_message: "boom".to_string(),
+            timestamp: "2026-01-11T12:39:00Z".to_string(),
+        };
+
+        let value = serde_json::to_value(response).expect("serialize history error response");
+        assert_eq!(
+            value,
+            json!({
+                "error_code": "INTERNAL_ERROR",
+                "error_message": "boom",
+                "timestamp": "2026-01-11T12:39:00Z"
+            })
+        );
+    }
+
+    #[test]
+    fn sensor_history_success_response_serializes_points() {
+        use crate::sensor::SensorRangeStatus;
+        use crate::state::ReadingStatus;
+
+        let response = SensorHistorySuccessResponse {
+            sensor_id: 1,
+            points: vec![
+                SensorHistoryPointResponse {
+                    distance_mm: 250,
+                    status: ReadingStatus::Ok {
+                        range_status: SensorRangeStatus::Valid,
+                    },
+                    error_code: None,
+                    timestamp: "2026-01-11T12:42:00Z".to_string(),
+                },
+                SensorHistoryPointResponse {
+                    distance_mm: 0,
+                    status: ReadingStatus::Error {
+                        reason: "timeout while reading".to_string(),
+                    },
+                    error_code: Some(SensorErrorCode::Timeout),
+                    timestamp: "2026-01-11T12:42:05Z".to_string(),
+                },
+            ],
+            timestamp: "2026-01-11T12:42:10Z".to_string(),
+        };
+
+        let value =
+            serde_json::to_value(response).expect("serialize sensor history success response");
+        assert_eq!(value["sensor_id"], json!(1));
+        assert_eq!(value["points"][0]["distance_mm"], json!(250));
+        assert_eq!(value["points"][1]["error_code"], json!("TIMEOUT"));
+    }
+
+    #[test]
+    fn sensor_history_error_response_uses_screaming_snake_case_code() {
+        let response = SensorHistoryErrorResponse {
+            error_code: SensorHistoryErrorCode::InvalidRange,
+            error_message: "from must be before to".to_string(),
+            timestamp: "2026-01-11T12:43:00Z".to_string(),
+        };
+
+        let value =
+            serde_json::to_value(response).expect("serialize sensor history error response");
+        assert_eq!(
+            value,
+            json!({
+                "error_code": "INVALID_RANGE",
+                "error_message": "from must be before to",
+                "timestamp": "2026-01-11T12:43:00Z"
+            })
+        );
+    }
 }
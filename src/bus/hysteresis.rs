@@ -0,0 +1,145 @@
+//! Per-sensor enter/exit hysteresis with an N-of-M debounce applied to the
+//! occupancy threshold comparison.
+//!
+//! A bare `distance_mm <= threshold_mm` flickers whenever someone hovers
+//! near the threshold. [`HysteresisDebouncer`] instead uses two thresholds
+//! - an enter threshold at `threshold_mm` and an exit threshold at
+//! `threshold_mm + hysteresis_mm` - and only flips the reported obstruction
+//! once the last `n` of a rolling `m`-sample window agree, so a single
+//! borderline reading can't move it. Mirrors
+//! [`crate::bus::validator::DataValidator`] and
+//! [`crate::bus::median_filter::MedianDeglitcher`]: a per-sensor map of
+//! state, created once and reused for the lifetime of the refresh
+//! pipeline.
+
+use crate::estimation::model::OccupancyConfig;
+use crate::sensor::SensorId;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Default)]
+struct SensorHysteresisState {
+    debounced: bool,
+    window: VecDeque<Option<bool>>,
+}
+
+#[derive(Debug)]
+pub struct HysteresisDebouncer {
+    threshold_mm: u16,
+    exit_threshold_mm: u16,
+    n: usize,
+    m: usize,
+    sensors: HashMap<SensorId, SensorHysteresisState>,
+}
+
+impl HysteresisDebouncer {
+    /// Reads `threshold_mm`, `hysteresis_mm`, `debounce_n`, and
+    /// `debounce_m` off `config`. `m` is widened to at least `n` - a window
+    /// shorter than the run of agreement it's supposed to hold could never
+    /// confirm a transition.
+    pub fn new(config: &OccupancyConfig) -> Self {
+        let n = config.debounce_n.max(1) as usize;
+        let m = (config.debounce_m.max(1) as usize).max(n);
+        Self {
+            threshold_mm: config.threshold_mm,
+            exit_threshold_mm: config.threshold_mm.saturating_add(config.hysteresis_mm),
+            n,
+            m,
+            sensors: HashMap::new(),
+        }
+    }
+
+    /// Debounced obstruction decision for one valid distance sample.
+    /// Callers keep sensor errors and low-confidence readings out of this
+    /// path entirely, so a cycle that isn't trustworthy just never calls
+    /// this - it neither confirms nor clears the debounced state for that
+    /// sensor, matching a dead-zone reading between the two thresholds.
+    pub fn decide(&mut self, sensor_id: SensorId, distance_mm: u16) -> bool {
+        let raw_vote = if distance_mm <= self.threshold_mm {
+            Some(true)
+        } else if distance_mm > self.exit_threshold_mm {
+            Some(false)
+        } else {
+            None
+        };
+
+        let state = self.sensors.entry(sensor_id).or_default();
+        state.window.push_back(raw_vote);
+        while state.window.len() > self.m {
+            state.window.pop_front();
+        }
+
+        if state.window.len() >= self.n {
+            if state.window.iter().rev().take(self.n).all(|vote| *vote == Some(true)) {
+                state.debounced = true;
+            } else if state.window.iter().rev().take(self.n).all(|vote| *vote == Some(false)) {
+                state.debounced = false;
+            }
+        }
+
+        state.debounced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(hysteresis_mm: u16, n: u32, m: u32) -> OccupancyConfig {
+        OccupancyConfig {
+            threshold_mm: 1000,
+            hysteresis_mm,
+            debounce_n: n,
+            debounce_m: m,
+            ..OccupancyConfig::default()
+        }
+    }
+
+    #[test]
+    fn stays_clear_until_n_consecutive_in_range_readings() {
+        let mut debouncer = HysteresisDebouncer::new(&config(0, 3, 3));
+
+        assert!(!debouncer.decide(1, 500));
+        assert!(!debouncer.decide(1, 500));
+        assert!(debouncer.decide(1, 500));
+    }
+
+    #[test]
+    fn single_borderline_reading_does_not_flip_it() {
+        let mut debouncer = HysteresisDebouncer::new(&config(0, 2, 2));
+
+        assert!(!debouncer.decide(1, 500));
+        assert!(debouncer.decide(1, 500));
+        // One out-of-range reading is not two consecutive, so it holds.
+        assert!(debouncer.decide(1, 2000));
+        assert!(debouncer.decide(1, 500));
+    }
+
+    #[test]
+    fn hysteresis_band_is_a_dead_zone_that_holds_state() {
+        // Enter at <=1000, exit at >1100; readings in (1000, 1100] vote neither way.
+        let mut debouncer = HysteresisDebouncer::new(&config(100, 1, 2));
+
+        assert!(debouncer.decide(1, 900));
+        // In the dead zone: neither confirms nor clears.
+        assert!(debouncer.decide(1, 1050));
+        assert!(debouncer.decide(1, 1050));
+    }
+
+    #[test]
+    fn reverts_to_clear_after_n_consecutive_out_of_range_readings() {
+        let mut debouncer = HysteresisDebouncer::new(&config(0, 2, 2));
+
+        assert!(debouncer.decide(1, 500));
+        assert!(debouncer.decide(1, 500));
+        assert!(debouncer.decide(1, 2000));
+        assert!(!debouncer.decide(1, 2000));
+    }
+
+    #[test]
+    fn sensors_are_tracked_independently() {
+        let mut debouncer = HysteresisDebouncer::new(&config(0, 1, 1));
+
+        assert!(debouncer.decide(1, 500));
+        assert!(!debouncer.decide(2, 2000));
+    }
+}
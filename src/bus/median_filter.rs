@@ -0,0 +1,145 @@
+//! Per-sensor sliding-window median filter applied to raw distances ahead
+//! of the occupancy threshold comparison.
+//!
+//! [`crate::estimation::deglitch::DeglitchModel`] majority-votes the
+//! *boolean* obstruction classification after thresholding, which has to
+//! wait out `window_len` cycles before a real transition is accepted. This
+//! instead median-filters the *raw distance* before it's ever compared to
+//! the threshold, so a lone reflection or range glitch never flips
+//! `obstructed` for a single cycle in the first place, without the lag a
+//! majority vote or average would add to a real transition.
+
+use crate::sensor::SensorId;
+use crate::state::{ReadingStatus, SensorReading};
+use std::collections::{HashMap, VecDeque};
+
+/// Keeps a short per-sensor ring buffer of recent valid distances and
+/// replaces each cycle's raw `distance_mm` with the buffer's median. Meant
+/// to be created once and reused for the lifetime of the refresh pipeline so
+/// the window carries over between cycles.
+#[derive(Debug)]
+pub struct MedianDeglitcher {
+    window_len: usize,
+    windows: HashMap<SensorId, VecDeque<u16>>,
+}
+
+impl MedianDeglitcher {
+    /// `window_len` is clamped to at least `1`, where the filter is a no-op.
+    pub fn new(window_len: usize) -> Self {
+        Self {
+            window_len: window_len.max(1),
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Returns the distance to use for the threshold comparison for each of
+    /// `readings`, in order. `Error` readings pass through unchanged and
+    /// are not added to their sensor's window.
+    pub fn filter(&mut self, readings: &[SensorReading]) -> Vec<u16> {
+        readings
+            .iter()
+            .map(|reading| self.filter_one(reading))
+            .collect()
+    }
+
+    fn filter_one(&mut self, reading: &SensorReading) -> u16 {
+        if matches!(reading.status, ReadingStatus::Error { .. }) {
+            return reading.distance_mm;
+        }
+
+        let window = self.windows.entry(reading.sensor_id).or_default();
+        window.push_back(reading.distance_mm);
+        while window.len() > self.window_len {
+            window.pop_front();
+        }
+
+        // Degrade gracefully while the window is still filling: fall back
+        // to the raw value instead of taking the median of a short window.
+        if window.len() < self.window_len {
+            return reading.distance_mm;
+        }
+
+        median(window)
+    }
+}
+
+fn median(distances: &VecDeque<u16>) -> u16 {
+    let mut sorted: Vec<u16> = distances.iter().copied().collect();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn ok_reading(sensor_id: SensorId, distance_mm: u16) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm,
+            timestamp: UNIX_EPOCH,
+            status: ReadingStatus::Ok {
+                range_status: crate::sensor::SensorRangeStatus::Valid,
+            },
+        }
+    }
+
+    fn error_reading(sensor_id: SensorId) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm: 0,
+            timestamp: UNIX_EPOCH,
+            status: ReadingStatus::Error {
+                reason: "read failed".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn raw_value_is_used_while_the_window_is_filling() {
+        let mut deglitcher = MedianDeglitcher::new(3);
+
+        let first = deglitcher.filter(&[ok_reading(1, 500)]);
+        let second = deglitcher.filter(&[ok_reading(1, 520)]);
+
+        assert_eq!(first, vec![500]);
+        assert_eq!(second, vec![520]);
+    }
+
+    #[test]
+    fn single_frame_spike_inside_a_clear_window_does_not_register() {
+        let mut deglitcher = MedianDeglitcher::new(3);
+
+        let _ = deglitcher.filter(&[ok_reading(1, 1500)]);
+        let _ = deglitcher.filter(&[ok_reading(1, 1500)]);
+        // A single-cycle spike well under the threshold, flanked by clear readings.
+        let spike = deglitcher.filter(&[ok_reading(1, 50)]);
+        let recovered = deglitcher.filter(&[ok_reading(1, 1500)]);
+
+        assert_eq!(spike, vec![1500]);
+        assert_eq!(recovered, vec![1500]);
+    }
+
+    #[test]
+    fn error_readings_pass_through_and_do_not_enter_the_window() {
+        let mut deglitcher = MedianDeglitcher::new(3);
+
+        let _ = deglitcher.filter(&[ok_reading(1, 1500)]);
+        let _ = deglitcher.filter(&[ok_reading(1, 1500)]);
+        let errored = deglitcher.filter(&[error_reading(1)]);
+        let still_filled = deglitcher.filter(&[ok_reading(1, 1500)]);
+
+        assert_eq!(errored, vec![0]);
+        assert_eq!(still_filled, vec![1500]);
+    }
+
+    #[test]
+    fn sensors_are_tracked_independently() {
+        let mut deglitcher = MedianDeglitcher::new(1);
+
+        let result = deglitcher.filter(&[ok_reading(1, 500), ok_reading(2, 900)]);
+
+        assert_eq!(result, vec![500, 900]);
+    }
+}
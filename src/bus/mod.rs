@@ -0,0 +1,7 @@
+pub mod hysteresis;
+pub mod median_filter;
+pub mod readings;
+pub mod relay;
+pub mod stream;
+pub mod validator;
+pub mod xshut;
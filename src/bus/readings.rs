@@ -1,6 +1,6 @@
 use crate::error::AppError;
 use crate::estimation::model::EstimationModel;
-use crate::sensor::{SensorDriver, SensorDriverFactory, SensorRangeStatus, SensorStatus};
+use crate::sensor::{SensorDriver, SensorDriverFactory, SensorId, SensorRangeStatus, SensorStatus};
 use crate::state::{AppState, ReadingStatus, SensorReading};
 use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
@@ -23,12 +23,21 @@ where
             continue;
         }
 
-        let mut driver = match factory.create_for_address(sensor.i2c_address) {
+        let Some(i2c_address) = sensor.address.as_i2c() else {
+            debug!(
+                sensor_id = sensor.sensor_id,
+                address = %sensor.address.display(),
+                "Skipping non-I2C sensor"
+            );
+            continue;
+        };
+
+        let mut driver = match factory.create_for_address(i2c_address) {
             Ok(driver) => driver,
             Err(err) => {
                 warn!(
                     sensor_id = sensor.sensor_id,
-                    address = format_args!("{:#04x}", sensor.i2c_address),
+                    address = format_args!("{:#04x}", i2c_address),
                     error = %err,
                     "Failed to create sensor driver for reading"
                 );
@@ -49,7 +58,7 @@ where
             Err(err) => {
                 warn!(
                     sensor_id = sensor.sensor_id,
-                    address = format_args!("{:#04x}", sensor.i2c_address),
+                    address = format_args!("{:#04x}", i2c_address),
                     error = %err,
                     "Failed to read distance"
                 );
@@ -69,7 +78,7 @@ where
         if let ReadingStatus::Error { ref reason } = status {
             warn!(
                 sensor_id = sensor.sensor_id,
-                address = format_args!("{:#04x}", sensor.i2c_address),
+                address = format_args!("{:#04x}", i2c_address),
                 distance_mm = measurement.distance_mm,
                 range_status = format_args!("{:?}", measurement.range_status),
                 error = reason,
@@ -90,7 +99,120 @@ where
     Ok(readings)
 }
 
-fn validate_measurement(
+/// Polls `driver.read_distance_nb()` until it latches a reading or errors,
+/// yielding to the executor between `WouldBlock` polls instead of blocking
+/// a thread on the sensor's integration time.
+async fn read_distance_polling<D: SensorDriver>(
+    driver: &mut D,
+) -> Result<crate::sensor::DistanceMeasurement, AppError> {
+    loop {
+        match driver.read_distance_nb() {
+            Ok(measurement) => return Ok(measurement),
+            Err(nb::Error::WouldBlock) => tokio::task::yield_now().await,
+            Err(nb::Error::Other(err)) => return Err(err),
+        }
+    }
+}
+
+/// Async counterpart to `read_and_store_distances`: polls every ready sensor
+/// concurrently via `read_distance_nb` and only writes state once every
+/// outstanding read has resolved or errored.
+pub async fn read_and_store_distances_async<F>(
+    factory: &mut F,
+    sensors: &mut [crate::sensor::SensorInfo],
+    state: &Arc<RwLock<AppState>>,
+    model: &dyn EstimationModel,
+) -> Result<Vec<SensorReading>, AppError>
+where
+    F: SensorDriverFactory,
+{
+    struct Pending<D> {
+        sensor_id: SensorId,
+        address: u8,
+        driver: Result<D, AppError>,
+    }
+
+    let mut pending = Vec::new();
+    for sensor in sensors.iter() {
+        if !matches!(sensor.status, SensorStatus::Ready) {
+            debug!(sensor_id = sensor.sensor_id, "Skipping sensor not ready");
+            continue;
+        }
+
+        let Some(i2c_address) = sensor.address.as_i2c() else {
+            debug!(
+                sensor_id = sensor.sensor_id,
+                address = %sensor.address.display(),
+                "Skipping non-I2C sensor"
+            );
+            continue;
+        };
+
+        pending.push(Pending {
+            sensor_id: sensor.sensor_id,
+            address: i2c_address,
+            driver: factory.create_for_address(i2c_address),
+        });
+    }
+
+    let results = futures::future::join_all(pending.into_iter().map(|mut item| async move {
+        let result = match &mut item.driver {
+            Ok(driver) => read_distance_polling(driver).await,
+            Err(err) => Err(AppError::Sensor(format!("driver create failed: {err}"))),
+        };
+        (item.sensor_id, item.address, result)
+    }))
+    .await;
+
+    let mut readings = Vec::with_capacity(results.len());
+    for (sensor_id, address, result) in results {
+        let reading = match result {
+            Ok(measurement) => {
+                let status =
+                    validate_measurement(measurement.distance_mm, measurement.range_status, model);
+                if let ReadingStatus::Error { ref reason } = status {
+                    warn!(
+                        sensor_id,
+                        address = format_args!("{:#04x}", address),
+                        distance_mm = measurement.distance_mm,
+                        range_status = format_args!("{:?}", measurement.range_status),
+                        error = reason,
+                        "Invalid distance reading"
+                    );
+                }
+                SensorReading {
+                    sensor_id,
+                    distance_mm: measurement.distance_mm,
+                    timestamp: SystemTime::now(),
+                    status,
+                }
+            }
+            Err(err) => {
+                warn!(
+                    sensor_id,
+                    address = format_args!("{:#04x}", address),
+                    error = %err,
+                    "Failed to read distance (async)"
+                );
+                SensorReading {
+                    sensor_id,
+                    distance_mm: 0,
+                    timestamp: SystemTime::now(),
+                    status: ReadingStatus::Error {
+                        reason: err.to_string(),
+                    },
+                }
+            }
+        };
+        readings.push(reading);
+    }
+
+    let mut guard = state.write().map_err(|_| AppError::StateLock)?;
+    guard.set_readings(readings.clone())?;
+    Ok(readings)
+}
+
+pub(crate) fn validate_measurement(
     distance_mm: u16,
     range_status: SensorRangeStatus,
     model: &dyn EstimationModel,
@@ -119,7 +241,7 @@ mod tests {
     use super::*;
     use crate::estimation::model::{EstimationModel, OccupancyConfig};
     use crate::sensor::mock::{MockSensorBehavior, MockSensorFactory};
-    use crate::sensor::{SensorInfo, SensorStatus};
+    use crate::sensor::{SensorAddress, SensorInfo, SensorStatus};
     use std::time::UNIX_EPOCH;
 
     #[test]
@@ -134,6 +256,7 @@ mod tests {
             threshold_mm: 1000,
             sensor_min_mm: 40,
             sensor_max_mm: 4000,
+            ..OccupancyConfig::default()
         });
 
         let state = Arc::new(RwLock::new(AppState::new()));
@@ -150,19 +273,19 @@ mod tests {
             SensorInfo {
                 sensor_id: 1,
                 xshut_pin: 17,
-                i2c_address: 0x30,
+                address: SensorAddress::I2c(0x30),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 2,
                 xshut_pin: 27,
-                i2c_address: 0x31,
+                address: SensorAddress::I2c(0x31),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 3,
                 xshut_pin: 22,
-                i2c_address: 0x32,
+                address: SensorAddress::I2c(0x32),
                 status: SensorStatus::Ready,
             },
         ];
@@ -231,19 +354,19 @@ mod tests {
             SensorInfo {
                 sensor_id: 1,
                 xshut_pin: 17,
-                i2c_address: 0x30,
+                address: SensorAddress::I2c(0x30),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 2,
                 xshut_pin: 27,
-                i2c_address: 0x31,
+                address: SensorAddress::I2c(0x31),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 3,
                 xshut_pin: 22,
-                i2c_address: 0x32,
+                address: SensorAddress::I2c(0x32),
                 status: SensorStatus::Ready,
             },
         ];
@@ -273,6 +396,42 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn async_read_cycle_waits_out_would_block_before_storing() -> Result<(), AppError> {
+        let behaviors = vec![
+            MockSensorBehavior::with_would_block(180, SensorRangeStatus::Valid, 3),
+            MockSensorBehavior::with_reading(250, SensorRangeStatus::Valid),
+        ];
+        let mut factory = MockSensorFactory::new(behaviors);
+        let model = TestModel::new(OccupancyConfig::default());
+
+        let state = Arc::new(RwLock::new(AppState::new()));
+        let mut sensors = vec![
+            SensorInfo {
+                sensor_id: 1,
+                xshut_pin: 17,
+                address: SensorAddress::I2c(0x30),
+                status: SensorStatus::Ready,
+            },
+            SensorInfo {
+                sensor_id: 2,
+                xshut_pin: 27,
+                address: SensorAddress::I2c(0x31),
+                status: SensorStatus::Ready,
+            },
+        ];
+
+        let readings =
+            read_and_store_distances_async(&mut factory, &mut sensors, &state, &model).await?;
+
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].distance_mm, 180);
+        assert_eq!(readings[1].distance_mm, 250);
+        assert!(readings.iter().all(|r| matches!(r.status, ReadingStatus::Ok { .. })));
+
+        Ok(())
+    }
+
     #[derive(Debug)]
     struct TestModel {
         occupancy_config: OccupancyConfig,
@@ -0,0 +1,226 @@
+//! Reverse-tunnel relay client for serving the HTTP API from behind NAT.
+//!
+//! A device on a cellular link or behind a home router can't accept inbound
+//! connections, so instead of (or alongside) binding `0.0.0.0:port` directly,
+//! this module dials out to a relay server and parks a long-lived connection
+//! there. When the relay receives an inbound HTTP request addressed to this
+//! device's id, it streams the request down the parked connection; the
+//! device dispatches it into the same `api::router` used by the local
+//! listener and streams the response back up. The relay is expected to queue
+//! requests while the device connection is down and match requests to
+//! responses by `request_id`.
+//!
+//! The wire protocol is a sequence of length-prefixed JSON frames
+//! (`u32` big-endian byte length + payload) over one TCP connection:
+//! a `Register` frame identifies the device immediately after connecting,
+//! then `Request`/`Response` frames flow for the lifetime of the session.
+//! Like [`crate::telemetry::mqtt`], the client reconnects with exponential
+//! backoff on any session error and never returns on its own.
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Method, Request};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// `host:port` of the relay server's device-facing listener.
+    pub relay_addr: String,
+    /// Stable id this device registers under; the relay routes inbound
+    /// requests addressed to this id back to our parked connection.
+    pub device_id: String,
+}
+
+#[derive(Debug, Error)]
+pub enum RelayError {
+    #[error("failed to connect to relay: {0}")]
+    Connect(std::io::Error),
+    #[error("relay connection error: {0}")]
+    Io(std::io::Error),
+    #[error("failed to encode/decode relay frame: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unexpected message from relay: {0}")]
+    UnexpectedMessage(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum RelayMessage {
+    Register {
+        device_id: String,
+    },
+    Request {
+        request_id: u64,
+        method: String,
+        path: String,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    },
+    Response {
+        request_id: u64,
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    },
+}
+
+/// Spawn the background relay client task.
+///
+/// The task reconnects with exponential backoff on disconnect and never
+/// returns on its own; drop the returned handle to stop it.
+pub fn spawn_relay_client(config: RelayConfig, router: Router) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        run_client(config, router).await;
+    })
+}
+
+async fn run_client(config: RelayConfig, router: Router) {
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        match run_session(&config, router.clone()).await {
+            Ok(()) => {
+                info!(relay_addr = %config.relay_addr, "Relay session ended cleanly, reconnecting");
+                backoff = MIN_BACKOFF;
+            }
+            Err(err) => {
+                warn!(
+                    error = %err,
+                    backoff_secs = backoff.as_secs(),
+                    "Relay session lost, retrying"
+                );
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn run_session(config: &RelayConfig, router: Router) -> Result<(), RelayError> {
+    let mut stream = TcpStream::connect(&config.relay_addr)
+        .await
+        .map_err(RelayError::Connect)?;
+
+    write_frame(
+        &mut stream,
+        &RelayMessage::Register {
+            device_id: config.device_id.clone(),
+        },
+    )
+    .await?;
+    info!(device_id = %config.device_id, relay_addr = %config.relay_addr, "Registered with relay");
+
+    loop {
+        match read_frame(&mut stream).await? {
+            RelayMessage::Request {
+                request_id,
+                method,
+                path,
+                headers,
+                body,
+            } => {
+                let (status, headers, body) =
+                    dispatch(&router, &method, &path, headers, body).await;
+                write_frame(
+                    &mut stream,
+                    &RelayMessage::Response {
+                        request_id,
+                        status,
+                        headers,
+                        body,
+                    },
+                )
+                .await?;
+            }
+            other => return Err(RelayError::UnexpectedMessage(format!("{other:?}"))),
+        }
+    }
+}
+
+/// Runs a tunneled request through the same router the local HTTP listener
+/// uses, returning the status/headers/body to relay back.
+async fn dispatch(
+    router: &Router,
+    method: &str,
+    path: &str,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+) -> (u16, Vec<(String, String)>, Vec<u8>) {
+    let mut builder = Request::builder()
+        .method(Method::from_bytes(method.as_bytes()).unwrap_or(Method::GET))
+        .uri(path);
+    for (name, value) in &headers {
+        builder = builder.header(name, value);
+    }
+
+    let request = match builder.body(Body::from(body)) {
+        Ok(request) => request,
+        Err(err) => {
+            return (
+                502,
+                Vec::new(),
+                format!("bad tunneled request: {err}").into_bytes(),
+            );
+        }
+    };
+
+    let response = router
+        .clone()
+        .oneshot(request)
+        .await
+        .unwrap_or_else(|err: std::convert::Infallible| match err {});
+
+    let status = response.status().as_u16();
+    let response_headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                value.to_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    let response_body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map(|bytes| bytes.to_vec())
+        .unwrap_or_default();
+
+    (status, response_headers, response_body)
+}
+
+async fn write_frame(stream: &mut TcpStream, message: &RelayMessage) -> Result<(), RelayError> {
+    let payload = serde_json::to_vec(message)?;
+    let len = payload.len() as u32;
+    stream
+        .write_all(&len.to_be_bytes())
+        .await
+        .map_err(RelayError::Io)?;
+    stream.write_all(&payload).await.map_err(RelayError::Io)?;
+    Ok(())
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Result<RelayMessage, RelayError> {
+    let mut len_buf = [0u8; 4];
+    stream
+        .read_exact(&mut len_buf)
+        .await
+        .map_err(RelayError::Io)?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+
+    let mut payload = vec![0u8; len];
+    stream
+        .read_exact(&mut payload)
+        .await
+        .map_err(RelayError::Io)?;
+    Ok(serde_json::from_slice(&payload)?)
+}
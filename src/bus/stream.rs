@@ -0,0 +1,174 @@
+//! Continuous ranging sessions via `futures::Stream`.
+//!
+//! `read_and_store_distances` is a one-shot cycle: create a driver, read
+//! once, return. A turnstile/queue monitor wants a long-lived ranging
+//! session per sensor instead. `into_reading_stream` turns any
+//! [`AsyncSensorDriver`] into a stream polled on a fixed interval, and
+//! `spawn_reading_stream` merges the per-sensor streams and pushes
+//! `SensorReading`s into `AppState` continuously. Mirrors the pull-to-stream
+//! sensor design used elsewhere (e.g. `Sensor::stream` in the templog crate).
+
+use crate::bus::readings::validate_measurement;
+use crate::error::AppError;
+use crate::estimation::model::EstimationModel;
+use crate::sensor::async_driver::{AsyncSensorDriver, BlockingSensorDriver};
+use crate::sensor::{DistanceMeasurement, SensorDriverFactory, SensorId, SensorInfo, SensorStatus};
+use crate::state::{AppState, ReadingStatus, SensorReading};
+use futures::stream::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::warn;
+
+/// Turns a single async sensor driver into a long-lived stream of distance
+/// measurements, polled every `interval` instead of once per call.
+pub fn into_reading_stream<D>(
+    driver: D,
+    interval: Duration,
+) -> impl Stream<Item = Result<DistanceMeasurement, AppError>>
+where
+    D: AsyncSensorDriver + Send + 'static,
+{
+    futures::stream::unfold(driver, move |mut driver| async move {
+        tokio::time::sleep(interval).await;
+        let measurement = driver.read_distance().await;
+        Some((measurement, driver))
+    })
+}
+
+type BoxedSensorStream = Pin<Box<dyn Stream<Item = (SensorId, Result<DistanceMeasurement, AppError>)> + Send>>;
+
+/// Spawns one ranging stream per ready sensor, merges them, and continuously
+/// pushes the latest known `SensorReading` snapshot into `AppState`.
+pub fn spawn_reading_stream<F>(
+    mut factory: F,
+    sensors: Vec<SensorInfo>,
+    state: Arc<RwLock<AppState>>,
+    model: Arc<dyn EstimationModel>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()>
+where
+    F: SensorDriverFactory + Send + 'static,
+    F::Driver: Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut streams: Vec<BoxedSensorStream> = Vec::new();
+
+        for sensor in &sensors {
+            if !matches!(sensor.status, SensorStatus::Ready) {
+                continue;
+            }
+
+            let Some(i2c_address) = sensor.address.as_i2c() else {
+                warn!(
+                    sensor_id = sensor.sensor_id,
+                    address = %sensor.address.display(),
+                    "Skipping non-I2C sensor for streaming"
+                );
+                continue;
+            };
+
+            let driver = match factory.create_for_address(i2c_address) {
+                Ok(driver) => driver,
+                Err(err) => {
+                    warn!(
+                        sensor_id = sensor.sensor_id,
+                        address = %sensor.address.display(),
+                        error = %err,
+                        "Failed to create sensor driver for streaming"
+                    );
+                    continue;
+                }
+            };
+
+            let sensor_id = sensor.sensor_id;
+            let async_driver = BlockingSensorDriver::new(driver);
+            let stream = into_reading_stream(async_driver, interval)
+                .map(move |result| (sensor_id, result));
+            streams.push(Box::pin(stream));
+        }
+
+        if streams.is_empty() {
+            warn!("Reading stream started with no ready sensors");
+            return;
+        }
+
+        let mut merged = futures::stream::select_all(streams);
+        let mut latest: HashMap<SensorId, SensorReading> = HashMap::new();
+
+        while let Some((sensor_id, result)) = merged.next().await {
+            let reading = match result {
+                Ok(measurement) => {
+                    let status = validate_measurement(
+                        measurement.distance_mm,
+                        measurement.range_status,
+                        model.as_ref(),
+                    );
+                    SensorReading {
+                        sensor_id,
+                        distance_mm: measurement.distance_mm,
+                        timestamp: SystemTime::now(),
+                        status,
+                    }
+                }
+                Err(err) => SensorReading {
+                    sensor_id,
+                    distance_mm: 0,
+                    timestamp: SystemTime::now(),
+                    status: ReadingStatus::Error {
+                        reason: format!("stream read failed: {err}"),
+                    },
+                },
+            };
+
+            latest.insert(sensor_id, reading);
+
+            let mut snapshot: Vec<SensorReading> = latest.values().cloned().collect();
+            snapshot.sort_by_key(|reading| reading.sensor_id);
+
+            if let Ok(mut guard) = state.write() {
+                let _ = guard.set_readings(snapshot);
+            } else {
+                warn!("State lock poisoned while applying streamed readings");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::SensorRangeStatus;
+    use crate::sensor::mock::{MockSensorBehavior, MockSensorDriver};
+
+    #[tokio::test(start_paused = true)]
+    async fn reading_stream_emits_configured_behavior_each_tick() {
+        let driver = BlockingSensorDriver::new(MockSensorDriver::new(MockSensorBehavior::with_reading(
+            150,
+            SensorRangeStatus::Valid,
+        )));
+        let mut stream = Box::pin(into_reading_stream(driver, Duration::from_millis(10)));
+
+        for _ in 0..3 {
+            let measurement = stream
+                .next()
+                .await
+                .expect("stream yields an item")
+                .expect("read ok");
+            assert_eq!(measurement.distance_mm, 150);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reading_stream_surfaces_driver_errors() {
+        let driver = BlockingSensorDriver::new(MockSensorDriver::new(
+            MockSensorBehavior::fail_read_distance(),
+        ));
+        let mut stream = Box::pin(into_reading_stream(driver, Duration::from_millis(10)));
+
+        let result = stream.next().await.expect("stream yields an item");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! Cross-validates sensor readings against their own recent history.
+//!
+//! `validate_measurement` in [`crate::bus::readings`] only catches a bad
+//! reading the driver itself flags as invalid, so a sensor that's stuck
+//! outputting the same value, or quietly drifting out of its working range
+//! while still reporting `ReadingStatus::Ok`, sails straight through to
+//! occupancy. [`DataValidator`] borrows the redundancy-validation approach
+//! flight-controller sensor stacks use: it keeps a short per-sensor history
+//! and derives a `confidence` in `[0, 1]` from three independent checks -
+//! stuck output, error density, and out-of-bounds distance - rather than
+//! trusting every `Ok` reading at face value.
+
+use crate::estimation::model::OccupancyConfig;
+use crate::sensor::SensorId;
+use crate::state::{ReadingStatus, SensorReading, SensorValidation};
+use std::collections::HashMap;
+
+/// Tuning knobs for [`DataValidator`].
+#[derive(Debug, Clone, Copy)]
+pub struct DataValidatorConfig {
+    /// A raw distance identical for more than this many consecutive cycles
+    /// is flagged stale.
+    pub stale_after_cycles: u32,
+    /// Per-cycle decay applied to a sensor's error density before the next
+    /// error (if any) is added.
+    pub error_density_decay: f64,
+    /// Error density above which a sensor is flagged error-dense.
+    pub error_density_threshold: f64,
+    /// Confidence below which a sensor is treated the same as a dropped
+    /// reading by the obstruction pipeline.
+    pub confidence_threshold: f64,
+}
+
+impl Default for DataValidatorConfig {
+    fn default() -> Self {
+        Self {
+            stale_after_cycles: 10,
+            error_density_decay: 0.9,
+            error_density_threshold: 3.0,
+            confidence_threshold: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SensorHistory {
+    last_distance: Option<u16>,
+    stale_cycles: u32,
+    error_density: f64,
+}
+
+/// Per-sensor redundancy check run once per refresh cycle alongside
+/// `validate_measurement`. Owns history across cycles, so it's meant to be
+/// created once and reused for the lifetime of the refresh pipeline rather
+/// than recreated per call.
+#[derive(Debug)]
+pub struct DataValidator {
+    config: DataValidatorConfig,
+    history: HashMap<SensorId, SensorHistory>,
+}
+
+impl DataValidator {
+    pub fn new(config: DataValidatorConfig) -> Self {
+        Self {
+            config,
+            history: HashMap::new(),
+        }
+    }
+
+    pub fn confidence_threshold(&self) -> f64 {
+        self.config.confidence_threshold
+    }
+
+    /// Validate one cycle's readings, updating per-sensor history in place.
+    pub fn validate(
+        &mut self,
+        readings: &[SensorReading],
+        occupancy_config: &OccupancyConfig,
+    ) -> Vec<SensorValidation> {
+        readings
+            .iter()
+            .map(|reading| self.validate_one(reading, occupancy_config))
+            .collect()
+    }
+
+    fn validate_one(
+        &mut self,
+        reading: &SensorReading,
+        occupancy_config: &OccupancyConfig,
+    ) -> SensorValidation {
+        let history = self.history.entry(reading.sensor_id).or_default();
+        let is_error = matches!(reading.status, ReadingStatus::Error { .. });
+
+        history.error_density *= self.config.error_density_decay;
+        if is_error {
+            history.error_density += 1.0;
+        }
+
+        let stale = if is_error {
+            history.last_distance = None;
+            history.stale_cycles = 0;
+            false
+        } else if history.last_distance == Some(reading.distance_mm) {
+            // `stale_cycles` counts repeats, so the value has been identical
+            // for `stale_cycles + 1` consecutive cycles.
+            history.stale_cycles += 1;
+            history.stale_cycles >= self.config.stale_after_cycles
+        } else {
+            history.last_distance = Some(reading.distance_mm);
+            history.stale_cycles = 0;
+            false
+        };
+
+        let out_of_bounds = !is_error
+            && !(occupancy_config.sensor_min_mm..=occupancy_config.sensor_max_mm)
+                .contains(&reading.distance_mm);
+
+        let error_dense = history.error_density > self.config.error_density_threshold;
+
+        let mut confidence = 1.0;
+        if stale {
+            confidence *= 0.5;
+        }
+        if error_dense {
+            confidence *= 0.3;
+        }
+        if out_of_bounds {
+            confidence = 0.0;
+        }
+
+        SensorValidation {
+            sensor_id: reading.sensor_id,
+            confidence,
+            stale,
+            error_density: history.error_density,
+            out_of_bounds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn ok_reading(sensor_id: SensorId, distance_mm: u16) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm,
+            timestamp: UNIX_EPOCH,
+            status: ReadingStatus::Ok {
+                range_status: crate::sensor::SensorRangeStatus::Valid,
+            },
+        }
+    }
+
+    fn error_reading(sensor_id: SensorId) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm: 0,
+            timestamp: UNIX_EPOCH,
+            status: ReadingStatus::Error {
+                reason: "read failed".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn stuck_sensor_is_flagged_stale_after_the_configured_run() {
+        let config = DataValidatorConfig {
+            stale_after_cycles: 2,
+            ..DataValidatorConfig::default()
+        };
+        let mut validator = DataValidator::new(config);
+        let occupancy_config = OccupancyConfig::default();
+
+        let mut last = validator.validate(&[ok_reading(1, 500)], &occupancy_config);
+        assert!(!last[0].stale);
+        last = validator.validate(&[ok_reading(1, 500)], &occupancy_config);
+        assert!(!last[0].stale);
+        last = validator.validate(&[ok_reading(1, 500)], &occupancy_config);
+
+        assert!(last[0].stale);
+        assert!(last[0].confidence < 1.0);
+    }
+
+    #[test]
+    fn changing_distance_resets_the_stale_counter() {
+        let config = DataValidatorConfig {
+            stale_after_cycles: 1,
+            ..DataValidatorConfig::default()
+        };
+        let mut validator = DataValidator::new(config);
+        let occupancy_config = OccupancyConfig::default();
+
+        let _ = validator.validate(&[ok_reading(1, 500)], &occupancy_config);
+        let _ = validator.validate(&[ok_reading(1, 500)], &occupancy_config);
+        let result = validator.validate(&[ok_reading(1, 600)], &occupancy_config);
+
+        assert!(!result[0].stale);
+    }
+
+    #[test]
+    fn repeated_errors_raise_error_density_above_threshold() {
+        let config = DataValidatorConfig {
+            error_density_decay: 1.0,
+            error_density_threshold: 2.5,
+            ..DataValidatorConfig::default()
+        };
+        let mut validator = DataValidator::new(config);
+        let occupancy_config = OccupancyConfig::default();
+
+        let mut result = Vec::new();
+        for _ in 0..3 {
+            result = validator.validate(&[error_reading(1)], &occupancy_config);
+        }
+
+        assert!(result[0].error_density > 2.5);
+    }
+
+    #[test]
+    fn out_of_bounds_distance_drives_confidence_to_zero() {
+        let mut validator = DataValidator::new(DataValidatorConfig::default());
+        let occupancy_config = OccupancyConfig {
+            threshold_mm: 1000,
+            sensor_min_mm: 40,
+            sensor_max_mm: 4000,
+            ..OccupancyConfig::default()
+        };
+
+        let result = validator.validate(&[ok_reading(1, 10)], &occupancy_config);
+
+        assert!(result[0].out_of_bounds);
+        assert_eq!(result[0].confidence, 0.0);
+    }
+}
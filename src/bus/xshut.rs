@@ -1,9 +1,12 @@
 use crate::error::AppError;
 use crate::sensor::{
-    ADDRESS_BASE_7BIT, DEFAULT_I2C_ADDRESS_7BIT, I2C_7BIT_MAX, SensorConfig, SensorDriver,
-    SensorDriverFactory, SensorId, SensorInfo, SensorStatus,
+    ADDRESS_BASE_7BIT, DEFAULT_I2C_ADDRESS_7BIT, I2C_7BIT_MAX, SensorAddress, SensorConfig,
+    SensorDriver, SensorDriverFactory, SensorId, SensorInfo, SensorStatus,
 };
 use crate::state::AppState;
+use embedded_hal::digital::OutputPin;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::{Arc, RwLock};
 use tracing::{debug, info, warn};
 
@@ -65,6 +68,27 @@ pub fn allocate_addresses(
     Ok(addressed)
 }
 
+/// Self-test tuning for [`discover_and_address_sensors_with_self_test`] and
+/// [`reinitialize_sensor_with_self_test`]: after a sensor is addressed and
+/// ranging starts, read `sample_count` consecutive samples and require each
+/// to report a valid range before committing the sensor as `Ready`,
+/// power-cycling and retrying the whole bring-up sequence up to
+/// `max_retries` times if it doesn't pass.
+#[derive(Debug, Clone, Copy)]
+pub struct SelfTestConfig {
+    pub sample_count: u32,
+    pub max_retries: u32,
+}
+
+impl Default for SelfTestConfig {
+    fn default() -> Self {
+        Self {
+            sample_count: 3,
+            max_retries: 2,
+        }
+    }
+}
+
 /// Discover sensors by toggling XSHUT and assigning unique 7-bit I2C addresses.
 pub fn discover_and_address_sensors<X, F>(
     xshut: &mut X,
@@ -75,12 +99,38 @@ where
     X: XshutController,
     F: SensorDriverFactory,
 {
-    let addressed = allocate_addresses(ADDRESS_BASE_7BIT, sensors)?;
+    discover_and_address_sensors_with_self_test(
+        xshut,
+        factory,
+        sensors,
+        ADDRESS_BASE_7BIT,
+        &SelfTestConfig::default(),
+    )
+}
+
+/// Self-test-aware variant of [`discover_and_address_sensors`]: borrows the
+/// firmware-updater "swap, then self-test before marking booted" pattern,
+/// so a sensor that ACKs its new address but returns garbage distances
+/// isn't reported as healthy. `base_address` overrides the compile-time
+/// [`ADDRESS_BASE_7BIT`] default so operators can re-plan the I2C address
+/// layout at runtime.
+pub fn discover_and_address_sensors_with_self_test<X, F>(
+    xshut: &mut X,
+    factory: &mut F,
+    sensors: &[SensorConfig],
+    base_address: u8,
+    self_test: &SelfTestConfig,
+) -> Result<Vec<SensorInfo>, AppError>
+where
+    X: XshutController,
+    F: SensorDriverFactory,
+{
+    let addressed = allocate_addresses(base_address, sensors)?;
     xshut.set_all_low()?;
     info!(
         count = addressed.len(),
         default_address = format_args!("{DEFAULT_I2C_ADDRESS_7BIT:#04x}"),
-        base_address = format_args!("{ADDRESS_BASE_7BIT:#04x}"),
+        base_address = format_args!("{base_address:#04x}"),
         "Starting XSHUT sequencing"
     );
 
@@ -95,69 +145,268 @@ where
             "Sensor XSHUT enabled"
         );
 
-        let mut driver = match factory.create_default() {
-            Ok(driver) => driver,
-            Err(err) => {
-                warn!(
-                    sensor_id = sensor.sensor_id,
-                    error = %err,
-                    "Failed to create sensor driver"
-                );
-                results.push(error_info(&sensor, err));
-                continue;
+        let mut attempt = 0;
+        let info = loop {
+            match initialize_sensor(factory, &sensor, self_test, &mut |_provisional| {}) {
+                Ok(info) => break info,
+                Err(err) => {
+                    if attempt >= self_test.max_retries {
+                        break error_info(&sensor, err);
+                    }
+                    attempt += 1;
+                    warn!(
+                        sensor_id = sensor.sensor_id,
+                        attempt,
+                        error = %err,
+                        "Bring-up or self-test failed, power-cycling sensor and retrying"
+                    );
+                    if let Err(cycle_err) = xshut.power_cycle_sensor(sensor.xshut_pin) {
+                        break error_info(&sensor, cycle_err);
+                    }
+                }
             }
         };
+        results.push(info);
+    }
 
-        if let Err(err) = driver.init_default() {
-            warn!(
-                sensor_id = sensor.sensor_id,
-                error = %err,
-                "Failed to initialize sensor on default address"
-            );
-            results.push(error_info(&sensor, err));
-            continue;
-        }
+    Ok(results)
+}
 
-        if let Err(err) = driver.set_address(sensor.i2c_address) {
-            warn!(
-                sensor_id = sensor.sensor_id,
-                new_address = format_args!("{:#04x}", sensor.i2c_address),
-                error = %err,
-                "Failed to assign sensor address"
-            );
-            results.push(error_info(&sensor, err));
-            continue;
-        }
+/// Run the create/init/set_address/verify/start_ranging/self-test sequence
+/// for one already-XSHUT-enabled sensor. `on_provisional` fires once the
+/// sensor has ACKed its new address and started ranging but before the
+/// self-test has confirmed it, so a caller with access to `AppState` can
+/// publish `SensorStatus::Provisional` ahead of the final `Ready`/`Error`
+/// verdict. Shared by [`discover_and_address_sensors_with_self_test`] and
+/// [`discover_and_address_sensors_async`].
+fn initialize_sensor<F, C>(
+    factory: &mut F,
+    sensor: &AddressedSensor,
+    self_test: &SelfTestConfig,
+    on_provisional: &mut C,
+) -> Result<SensorInfo, AppError>
+where
+    F: SensorDriverFactory,
+    C: FnMut(SensorInfo),
+{
+    let mut driver = factory.create_default().inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            error = %err,
+            "Failed to create sensor driver"
+        );
+    })?;
+
+    driver.init_default().inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            error = %err,
+            "Failed to initialize sensor on default address"
+        );
+    })?;
 
-        if let Err(err) = driver.verify() {
-            warn!(
-                sensor_id = sensor.sensor_id,
-                address = format_args!("{:#04x}", sensor.i2c_address),
-                error = %err,
-                "Failed to verify sensor after address assignment"
-            );
-            results.push(error_info(&sensor, err));
-            continue;
+    driver.set_address(sensor.i2c_address).inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            new_address = format_args!("{:#04x}", sensor.i2c_address),
+            error = %err,
+            "Failed to assign sensor address"
+        );
+    })?;
+
+    driver.verify().inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            address = format_args!("{:#04x}", sensor.i2c_address),
+            error = %err,
+            "Failed to verify sensor after address assignment"
+        );
+    })?;
+
+    // Start continuous ranging mode
+    driver.start_ranging().inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            address = format_args!("{:#04x}", sensor.i2c_address),
+            error = %err,
+            "Failed to start ranging on sensor"
+        );
+    })?;
+
+    on_provisional(SensorInfo {
+        sensor_id: sensor.sensor_id,
+        xshut_pin: sensor.xshut_pin,
+        address: SensorAddress::I2c(sensor.i2c_address),
+        status: SensorStatus::Provisional,
+    });
+
+    run_self_test(&mut driver, self_test.sample_count).inspect_err(|err| {
+        warn!(
+            sensor_id = sensor.sensor_id,
+            address = format_args!("{:#04x}", sensor.i2c_address),
+            error = %err,
+            "Sensor failed post-address self-test"
+        );
+    })?;
+
+    Ok(SensorInfo {
+        sensor_id: sensor.sensor_id,
+        xshut_pin: sensor.xshut_pin,
+        address: SensorAddress::I2c(sensor.i2c_address),
+        status: SensorStatus::Ready,
+    })
+}
+
+/// Read `sample_count` consecutive rangings from a freshly addressed sensor
+/// and require each to report a valid range before it can be committed as
+/// `Ready`.
+fn run_self_test<D>(driver: &mut D, sample_count: u32) -> Result<(), AppError>
+where
+    D: SensorDriver,
+{
+    for sample in 0..sample_count {
+        let measurement = driver.read_distance()?;
+        if !measurement.range_status.is_valid() {
+            return Err(AppError::Sensor(format!(
+                "self-test sample {sample} reported invalid range status {:?}",
+                measurement.range_status
+            )));
         }
+    }
+    Ok(())
+}
+
+/// Pluggable async delay so discovery can await a cooperative timer (e.g. an
+/// `embassy_time::Timer`-backed implementation) instead of blocking the
+/// executor thread with `std::thread::sleep`.
+pub trait AsyncDelay {
+    fn delay_ms(&mut self, ms: u64) -> impl Future<Output = ()> + Send;
+}
 
-        // Start continuous ranging mode
-        if let Err(err) = driver.start_ranging() {
-            warn!(
-                sensor_id = sensor.sensor_id,
-                address = format_args!("{:#04x}", sensor.i2c_address),
-                error = %err,
-                "Failed to start ranging on sensor"
-            );
-            results.push(error_info(&sensor, err));
-            continue;
+/// [`AsyncDelay`] backed by `tokio::time::sleep`, for use on the Tokio runtime.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioDelay;
+
+impl AsyncDelay for TokioDelay {
+    async fn delay_ms(&mut self, ms: u64) {
+        tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+    }
+}
+
+/// Async counterpart to [`XshutController`] so the XSHUT-sequencing state
+/// machine can run entirely on the async executor instead of a dedicated
+/// blocking thread (see [`spawn_discovery_thread`]).
+pub trait AsyncXshutController {
+    fn set_all_low(&mut self) -> impl Future<Output = Result<(), AppError>> + Send;
+    fn set_high(&mut self, pin: u8) -> impl Future<Output = Result<(), AppError>> + Send;
+    fn power_cycle_sensor(&mut self, pin: u8) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// Drives a blocking [`XshutController`] on the blocking thread pool so it
+/// can satisfy [`AsyncXshutController`] without the controller itself
+/// needing to change - the same adapter shape as
+/// [`crate::sensor::async_driver::BlockingSensorDriver`].
+pub struct BlockingXshutController<X> {
+    controller: Option<X>,
+}
+
+impl<X> BlockingXshutController<X> {
+    pub fn new(controller: X) -> Self {
+        Self {
+            controller: Some(controller),
         }
+    }
 
-        results.push(SensorInfo {
-            sensor_id: sensor.sensor_id,
-            xshut_pin: sensor.xshut_pin,
-            i2c_address: sensor.i2c_address,
-            status: SensorStatus::Ready,
-        });
+    async fn with_controller<F, T>(&mut self, f: F) -> Result<T, AppError>
+    where
+        X: XshutController + Send + 'static,
+        F: FnOnce(&mut X) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut controller = self
+            .controller
+            .take()
+            .expect("BlockingXshutController used after a prior panic");
+
+        let (result, controller) = tokio::task::spawn_blocking(move || {
+            let result = f(&mut controller);
+            (result, controller)
+        })
+        .await
+        .unwrap_or_else(|_| panic!("blocking xshut task panicked"));
+
+        self.controller = Some(controller);
+        result
+    }
+}
+
+impl<X> AsyncXshutController for BlockingXshutController<X>
+where
+    X: XshutController + Send + 'static,
+{
+    async fn set_all_low(&mut self) -> Result<(), AppError> {
+        self.with_controller(|controller| controller.set_all_low())
+            .await
+    }
+
+    async fn set_high(&mut self, pin: u8) -> Result<(), AppError> {
+        self.with_controller(move |controller| controller.set_high(pin))
+            .await
+    }
+
+    async fn power_cycle_sensor(&mut self, pin: u8) -> Result<(), AppError> {
+        self.with_controller(move |controller| controller.power_cycle_sensor(pin))
+            .await
+    }
+}
+
+/// Async counterpart to [`discover_and_address_sensors`]: awaits a pluggable
+/// [`AsyncDelay`] instead of blocking the executor thread, and yields to the
+/// runtime between sensors. The blocking create/init/set_address/verify/
+/// start_ranging sequence for each sensor still runs on `spawn_blocking`
+/// rather than the caller's task, so a single-threaded runtime stays
+/// responsive to the HTTP/MQTT servers throughout discovery.
+pub async fn discover_and_address_sensors_async<X, F, D>(
+    xshut: &mut X,
+    mut factory: F,
+    sensors: &[SensorConfig],
+    delay: &mut D,
+) -> Result<Vec<SensorInfo>, AppError>
+where
+    X: AsyncXshutController,
+    F: SensorDriverFactory + Send + 'static,
+    D: AsyncDelay,
+{
+    let addressed = allocate_addresses(ADDRESS_BASE_7BIT, sensors)?;
+    xshut.set_all_low().await?;
+    info!(
+        count = addressed.len(),
+        default_address = format_args!("{DEFAULT_I2C_ADDRESS_7BIT:#04x}"),
+        base_address = format_args!("{ADDRESS_BASE_7BIT:#04x}"),
+        "Starting async XSHUT sequencing"
+    );
+
+    let mut results = Vec::with_capacity(addressed.len());
+    for sensor in addressed {
+        xshut.set_high(sensor.xshut_pin).await?;
+        // Allow sensor boot time after XSHUT release (2ms per VL53L1X datasheet)
+        delay.delay_ms(2).await;
+        debug!(
+            sensor_id = sensor.sensor_id,
+            xshut_pin = sensor.xshut_pin,
+            "Sensor XSHUT enabled"
+        );
+
+        let self_test = SelfTestConfig::default();
+        let (outcome, returned_factory) = tokio::task::spawn_blocking(move || {
+            let outcome = initialize_sensor(&mut factory, &sensor, &self_test, &mut |_| {})
+                .unwrap_or_else(|err| error_info(&sensor, err));
+            (outcome, factory)
+        })
+        .await
+        .unwrap_or_else(|_| panic!("sensor discovery task panicked"));
+        factory = returned_factory;
+        results.push(outcome);
     }
 
     Ok(results)
@@ -169,6 +418,24 @@ pub fn reinitialize_sensor<X, F>(
     factory: &mut F,
     sensor: &SensorInfo,
 ) -> Result<(), AppError>
+where
+    X: XshutController + ?Sized,
+    F: SensorDriverFactory,
+{
+    reinitialize_sensor_with_self_test(xshut, factory, sensor, &SelfTestConfig::default())
+}
+
+/// Self-test-aware variant of [`reinitialize_sensor`]: power-cycles and
+/// retries the full default-address -> set_address -> self-test sequence up
+/// to `self_test.max_retries` times before giving up, using the same
+/// "swap, then self-test before marking booted" pattern as
+/// [`discover_and_address_sensors_with_self_test`].
+pub fn reinitialize_sensor_with_self_test<X, F>(
+    xshut: &mut X,
+    factory: &mut F,
+    sensor: &SensorInfo,
+    self_test: &SelfTestConfig,
+) -> Result<(), AppError>
 where
     X: XshutController + ?Sized,
     F: SensorDriverFactory,
@@ -177,6 +444,48 @@ where
     info!(sensor_id = sensor.sensor_id, "Resetting sensor hardware");
     xshut.power_cycle_sensor(sensor.xshut_pin)?;
 
+    let mut attempt = 0;
+    loop {
+        let result = reinitialize_sensor_once(factory, sensor, self_test);
+        match result {
+            Ok(()) => break,
+            Err(err) => {
+                if attempt >= self_test.max_retries {
+                    warn!(
+                        sensor_id = sensor.sensor_id,
+                        error = %err,
+                        "Sensor failed to re-initialize after self-test retries"
+                    );
+                    return Err(err);
+                }
+                attempt += 1;
+                warn!(
+                    sensor_id = sensor.sensor_id,
+                    attempt,
+                    error = %err,
+                    "Re-init or self-test failed, power-cycling and retrying"
+                );
+                xshut.power_cycle_sensor(sensor.xshut_pin)?;
+            }
+        }
+    }
+
+    info!(
+        sensor_id = sensor.sensor_id,
+        address = %sensor.address.display(),
+        "Sensor re-initialized successfully"
+    );
+    Ok(())
+}
+
+fn reinitialize_sensor_once<F>(
+    factory: &mut F,
+    sensor: &SensorInfo,
+    self_test: &SelfTestConfig,
+) -> Result<(), AppError>
+where
+    F: SensorDriverFactory,
+{
     // 2. Initialize driver on default address
     let mut driver = factory.create_default()?;
     if let Err(e) = driver.init_default() {
@@ -194,26 +503,30 @@ where
     driver.verify()?;
     driver.start_ranging()?;
 
-    info!(
-        sensor_id = sensor.sensor_id,
-        address = format_args!("{:#04x}", sensor.i2c_address),
-        "Sensor re-initialized successfully"
-    );
-    Ok(())
+    // 5. Self-test before committing
+    run_self_test(&mut driver, self_test.sample_count)
 }
 
-/// Discover sensors and persist results in shared state for the rest of the pipeline.
+/// Discover sensors at `base_address` and persist results in shared state
+/// for the rest of the pipeline.
 pub fn discover_and_store_sensors<X, F>(
     xshut: &mut X,
     factory: &mut F,
     sensors: &[SensorConfig],
+    base_address: u8,
     state: &Arc<RwLock<AppState>>,
 ) -> Result<Vec<SensorInfo>, AppError>
 where
     X: XshutController,
     F: SensorDriverFactory,
 {
-    let results = discover_and_address_sensors(xshut, factory, sensors)?;
+    let results = discover_and_address_sensors_with_self_test(
+        xshut,
+        factory,
+        sensors,
+        base_address,
+        &SelfTestConfig::default(),
+    )?;
     let mut guard = state.write().map_err(|_| AppError::StateLock)?;
     guard.set_sensors(results.clone())?;
     Ok(results)
@@ -224,6 +537,7 @@ pub fn spawn_discovery_thread<X, F>(
     mut xshut: X,
     mut factory: F,
     sensors: Vec<SensorConfig>,
+    base_address: u8,
     state: Arc<RwLock<AppState>>,
 ) -> std::thread::JoinHandle<Result<Vec<SensorInfo>, AppError>>
 where
@@ -231,18 +545,42 @@ where
     F: SensorDriverFactory + Send + 'static,
 {
     std::thread::spawn(move || {
-        discover_and_store_sensors(&mut xshut, &mut factory, &sensors, &state)
+        discover_and_store_sensors(&mut xshut, &mut factory, &sensors, base_address, &state)
     })
 }
 
+/// Bundles what the refresh loop needs to re-run discovery against the
+/// latest [`RuntimeConfigStore`] contents when an operator changes the I2C
+/// base address or sensor map at runtime, falling back to the boot-time
+/// TOML configuration for any value the store doesn't override.
+#[derive(Debug, Clone)]
+pub struct RediscoveryConfig {
+    pub runtime_config: Arc<RwLock<crate::runtime_config::RuntimeConfigStore>>,
+    pub fallback_sensors: Vec<SensorConfig>,
+    pub fallback_base_address: u8,
+}
+
+impl RediscoveryConfig {
+    /// Resolves the effective base address and sensor map: whatever the
+    /// runtime config store overrides, falling back to the boot-time values.
+    pub fn resolve(&self) -> Result<(u8, Vec<SensorConfig>), AppError> {
+        let guard = self
+            .runtime_config
+            .read()
+            .map_err(|_| AppError::StateLock)?;
+        Ok((
+            guard.i2c_base_address_or(self.fallback_base_address),
+            guard.sensor_configs_or(&self.fallback_sensors),
+        ))
+    }
+}
+
 fn error_info(sensor: &AddressedSensor, err: AppError) -> SensorInfo {
     SensorInfo {
         sensor_id: sensor.sensor_id,
         xshut_pin: sensor.xshut_pin,
-        i2c_address: sensor.i2c_address,
-        status: SensorStatus::Error {
-            message: err.to_string(),
-        },
+        address: SensorAddress::I2c(sensor.i2c_address),
+        status: SensorStatus::Error { error: err.into() },
     }
 }
 
@@ -301,9 +639,68 @@ impl XshutController for RppalXshutController {
     }
 }
 
+/// XSHUT backend generic over any `embedded-hal` `OutputPin`, so discovery
+/// and `power_cycle_sensor` work on STM32, nRF, RP2040, or a mock board -
+/// not just a Linux Pi via `RppalXshutController`.
+pub struct HalXshutController<P> {
+    pins: HashMap<u8, P>,
+}
+
+impl<P> HalXshutController<P>
+where
+    P: OutputPin,
+{
+    pub fn new(pins: HashMap<u8, P>) -> Self {
+        Self { pins }
+    }
+}
+
+impl<P> XshutController for HalXshutController<P>
+where
+    P: OutputPin,
+{
+    fn set_all_low(&mut self) -> Result<(), AppError> {
+        for pin in self.pins.values_mut() {
+            pin.set_low()
+                .map_err(|err| AppError::Gpio(format!("{err:?}")))?;
+        }
+        Ok(())
+    }
+
+    fn set_high(&mut self, pin: u8) -> Result<(), AppError> {
+        let output = self
+            .pins
+            .get_mut(&pin)
+            .ok_or_else(|| AppError::Xshut(format!("missing XSHUT pin {pin}")))?;
+        output
+            .set_high()
+            .map_err(|err| AppError::Gpio(format!("{err:?}")))
+    }
+
+    fn power_cycle_sensor(&mut self, pin: u8) -> Result<(), AppError> {
+        let output = self
+            .pins
+            .get_mut(&pin)
+            .ok_or_else(|| AppError::Xshut(format!("missing XSHUT pin {pin}")))?;
+
+        // Cycle: Low (OFF) -> Wait -> High (ON)
+        output
+            .set_low()
+            .map_err(|err| AppError::Gpio(format!("{err:?}")))?;
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        output
+            .set_high()
+            .map_err(|err| AppError::Gpio(format!("{err:?}")))?;
+        std::thread::sleep(std::time::Duration::from_millis(10)); // Boot time
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sensor::SensorRangeStatus;
     use crate::sensor::mock::{MockSensorBehavior, MockSensorFactory};
 
     #[derive(Default)]
@@ -455,6 +852,125 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn self_test_failure_power_cycles_and_retries_until_success() -> Result<(), AppError> {
+        let sensors = vec![SensorConfig {
+            sensor_id: 1,
+            xshut_pin: 17,
+        }];
+
+        let behaviors = vec![
+            MockSensorBehavior::with_reading(100, SensorRangeStatus::SignalFailure),
+            MockSensorBehavior::with_reading(100, SensorRangeStatus::SignalFailure),
+            MockSensorBehavior::ok(),
+        ];
+        let mut factory = MockSensorFactory::new(behaviors);
+        let mut xshut = MockXshut::default();
+
+        let results = discover_and_address_sensors_with_self_test(
+            &mut xshut,
+            &mut factory,
+            &sensors,
+            ADDRESS_BASE_7BIT,
+            &SelfTestConfig::default(),
+        )?;
+        assert!(matches!(results[0].status, SensorStatus::Ready));
+        assert_eq!(
+            xshut.actions,
+            vec!["all_low", "high:17", "cycle:17", "cycle:17"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn self_test_failure_exhausts_retries_and_records_error() -> Result<(), AppError> {
+        let sensors = vec![SensorConfig {
+            sensor_id: 1,
+            xshut_pin: 17,
+        }];
+
+        let behaviors = vec![
+            MockSensorBehavior::with_reading(100, SensorRangeStatus::SignalFailure),
+            MockSensorBehavior::with_reading(100, SensorRangeStatus::SignalFailure),
+            MockSensorBehavior::with_reading(100, SensorRangeStatus::SignalFailure),
+        ];
+        let mut factory = MockSensorFactory::new(behaviors);
+        let mut xshut = MockXshut::default();
+
+        let self_test = SelfTestConfig {
+            sample_count: 1,
+            max_retries: 2,
+        };
+        let results = discover_and_address_sensors_with_self_test(
+            &mut xshut,
+            &mut factory,
+            &sensors,
+            ADDRESS_BASE_7BIT,
+            &self_test,
+        )?;
+        assert!(matches!(results[0].status, SensorStatus::Error { .. }));
+        assert_eq!(
+            xshut.actions,
+            vec!["all_low", "high:17", "cycle:17", "cycle:17"]
+        );
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct MockPinError;
+
+    impl embedded_hal::digital::Error for MockPinError {
+        fn kind(&self) -> embedded_hal::digital::ErrorKind {
+            embedded_hal::digital::ErrorKind::Other
+        }
+    }
+
+    #[derive(Default)]
+    struct MockHalPin {
+        high: bool,
+    }
+
+    impl embedded_hal::digital::ErrorType for MockHalPin {
+        type Error = MockPinError;
+    }
+
+    impl OutputPin for MockHalPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.high = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn hal_xshut_controller_drives_mapped_pins() -> Result<(), AppError> {
+        let mut pins = HashMap::new();
+        pins.insert(17u8, MockHalPin::default());
+        pins.insert(27u8, MockHalPin::default());
+        let mut xshut = HalXshutController::new(pins);
+
+        xshut.set_all_low()?;
+        xshut.set_high(17)?;
+        assert!(xshut.pins[&17].high);
+        assert!(!xshut.pins[&27].high);
+
+        xshut.power_cycle_sensor(27)?;
+        assert!(xshut.pins[&27].high);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hal_xshut_controller_reports_missing_pin() {
+        let mut xshut = HalXshutController::<MockHalPin>::new(HashMap::new());
+        let result = xshut.set_high(99);
+        assert!(matches!(result, Err(AppError::Xshut(_))));
+    }
+
     #[test]
     fn discovery_updates_shared_state() -> Result<(), AppError> {
         let sensors = vec![
@@ -478,10 +994,100 @@ mod tests {
             guard.subscribe_sensors()
         };
 
-        let results = discover_and_store_sensors(&mut xshut, &mut factory, &sensors, &state)?;
+        let results =
+            discover_and_store_sensors(&mut xshut, &mut factory, &sensors, ADDRESS_BASE_7BIT, &state)?;
         let updated = receiver.borrow_and_update().clone();
 
         assert_eq!(updated.len(), results.len());
         Ok(())
     }
+
+    #[test]
+    fn discover_and_store_sensors_honors_custom_base_address() -> Result<(), AppError> {
+        let sensors = vec![SensorConfig {
+            sensor_id: 1,
+            xshut_pin: 17,
+        }];
+
+        let behaviors = vec![MockSensorBehavior::ok()];
+        let mut factory = MockSensorFactory::new(behaviors);
+        let mut xshut = MockXshut::default();
+        let state = Arc::new(RwLock::new(AppState::new()));
+
+        let results =
+            discover_and_store_sensors(&mut xshut, &mut factory, &sensors, 0x40, &state)?;
+
+        assert_eq!(results[0].address.as_i2c(), Some(0x40));
+        Ok(())
+    }
+
+    #[test]
+    fn rediscovery_config_resolve_prefers_runtime_config_over_fallback() -> Result<(), AppError> {
+        use crate::runtime_config::RuntimeConfigStore;
+
+        let path = std::env::temp_dir().join(format!(
+            "mariam-rediscovery-resolve-{}.txt",
+            std::process::id()
+        ));
+        let mut store = RuntimeConfigStore::load(&path).expect("load runtime config");
+        store
+            .set("i2c_base_address", "0x40")
+            .expect("set base address");
+        store
+            .set("sensor.1.xshut_pin", "27")
+            .expect("set sensor pin");
+
+        let rediscovery = RediscoveryConfig {
+            runtime_config: Arc::new(RwLock::new(store)),
+            fallback_sensors: vec![SensorConfig {
+                sensor_id: 1,
+                xshut_pin: 17,
+            }],
+            fallback_base_address: ADDRESS_BASE_7BIT,
+        };
+
+        let (base_address, configs) = rediscovery.resolve()?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(base_address, 0x40);
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].xshut_pin, 27);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn async_discovery_continues_on_sensor_error() -> Result<(), AppError> {
+        let sensors = vec![
+            SensorConfig {
+                sensor_id: 1,
+                xshut_pin: 17,
+            },
+            SensorConfig {
+                sensor_id: 2,
+                xshut_pin: 27,
+            },
+            SensorConfig {
+                sensor_id: 3,
+                xshut_pin: 22,
+            },
+        ];
+
+        let behaviors = vec![
+            MockSensorBehavior::ok(),
+            MockSensorBehavior::fail_init(),
+            MockSensorBehavior::ok(),
+        ];
+        let factory = MockSensorFactory::new(behaviors);
+        let mut xshut = BlockingXshutController::new(MockXshut::default());
+        let mut delay = TokioDelay;
+
+        let results =
+            discover_and_address_sensors_async(&mut xshut, factory, &sensors, &mut delay).await?;
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0].status, SensorStatus::Ready));
+        assert!(matches!(results[1].status, SensorStatus::Error { .. }));
+        assert!(matches!(results[2].status, SensorStatus::Ready));
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use crate::sensor::{SensorConfig, build_sensor_configs};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use thiserror::Error;
@@ -8,8 +8,23 @@ pub const DEFAULT_CONFIG_PATH: &str = "config/config.toml";
 pub const DEFAULT_SERVER_PORT: u16 = 8080;
 pub const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 5;
 
-#[derive(Debug, Deserialize, Clone)]
+/// The schema version `migrate_to_current` upgrades older documents to.
+/// Bump this and add a `migrate_vN_to_vN+1` step whenever a config
+/// document's shape changes in a way older files can't just
+/// `#[serde(default)]` their way past.
+pub const CURRENT_CONFIG_VERSION: u16 = 2;
+
+fn default_config_version() -> u16 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    /// Schema version of this document. Absent in files written before
+    /// versioning existed, which are treated as version 1 and migrated up
+    /// to [`CURRENT_CONFIG_VERSION`] on load.
+    #[serde(default = "default_config_version")]
+    pub version: u16,
     pub app: AppSection,
     pub logging: LoggingSection,
     #[serde(default)]
@@ -18,56 +33,486 @@ pub struct Config {
     pub sensors: Option<SensorsSection>,
     #[serde(default)]
     pub server: Option<ServerSection>,
+    #[serde(default)]
+    pub mqtt: Option<MqttSection>,
+    #[serde(default)]
+    pub admin: Option<AdminSection>,
+    #[serde(default)]
+    pub runtime: Option<RuntimeSection>,
+    #[serde(default)]
+    pub scpi: Option<ScpiSection>,
+    #[serde(default)]
+    pub relay: Option<RelaySection>,
+    #[serde(default)]
+    pub otel: Option<OtelSection>,
+    #[serde(default)]
+    pub history: Option<HistorySection>,
+    #[serde(default)]
+    pub watchdog: Option<WatchdogSection>,
+    #[serde(default)]
+    pub storage: Option<StorageSection>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppSection {
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LoggingSection {
     pub level: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CalibrationSettings {
     pub path: Option<PathBuf>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SensorsSection {
-    /// GPIO pin numbers for XSHUT control, in sensor order
+    /// GPIO pin numbers for XSHUT control, in sensor order. Superseded by
+    /// `sensors` below, which additionally names each sensor and lets it
+    /// specify a target I2C address and mounting offset/position; kept as
+    /// a fallback that auto-generates "sensor-N" names when `sensors` is
+    /// empty.
+    #[serde(default)]
     pub xshut_pins: Vec<u8>,
+    /// Named, individually-configured sensors, e.g. `[[sensors.sensors]]`
+    /// entries with `name = "inlet"`. Takes precedence over `xshut_pins`
+    /// when non-empty.
+    #[serde(default)]
+    pub sensors: Vec<SensorEntry>,
+}
+
+/// One named, individually-addressed sensor under `[sensors]`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SensorEntry {
+    /// Human-readable label, e.g. "inlet"/"outlet" - surfaced by
+    /// [`Config::sensor_names`] so readings/API output can be labeled per
+    /// sensor instead of by index.
+    pub name: String,
+    pub xshut_pin: u8,
+    /// Target 7-bit I2C address for this sensor. Parsed and kept with the
+    /// entry only: [`Config::sensor_configs`] does not pass it on, so
+    /// discovery still assigns addresses sequentially from the base address.
+    pub address: Option<u8>,
+    /// Mounting offset from the reference point, in millimeters. Parsed and
+    /// kept with the entry only; the estimation pipeline does not read it
+    /// and raw ranging distances are not corrected by it.
+    pub offset_mm: Option<i32>,
+    /// Free-form mounting position/location label (e.g. "ceiling-left").
+    /// Parsed and kept with the entry only; the estimation pipeline does
+    /// not read it.
+    pub position: Option<String>,
+}
+
+impl SensorsSection {
+    /// The effective per-sensor entries: `sensors` as configured, or one
+    /// auto-generated, unnamed entry per `xshut_pins` pin as a fallback.
+    fn entries(&self) -> Vec<SensorEntry> {
+        if !self.sensors.is_empty() {
+            return self.sensors.clone();
+        }
+        self.xshut_pins
+            .iter()
+            .enumerate()
+            .map(|(index, pin)| SensorEntry {
+                name: format!("sensor-{}", index + 1),
+                xshut_pin: *pin,
+                address: None,
+                offset_mm: None,
+                position: None,
+            })
+            .collect()
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct ServerSection {
     /// Port to listen on (default: 8080)
     pub port: Option<u16>,
-    /// Refresh interval in seconds for the estimation pipeline (default: 5)
+    /// Refresh interval for the estimation pipeline as a human-readable
+    /// duration string, e.g. `"500ms"`, `"5s"`, `"2m"`, `"1h30m"` (default: 5s).
+    /// Takes precedence over the deprecated `refresh_interval_secs`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_duration_opt",
+        serialize_with = "serialize_duration_opt"
+    )]
+    pub refresh_interval: Option<Duration>,
+    /// Deprecated: use `refresh_interval` instead. Refresh interval in
+    /// plain seconds, kept only for backward compatibility.
     pub refresh_interval_secs: Option<u64>,
 }
 
+/// Parses a human-readable duration string by scanning `<number><unit>`
+/// segments (`ms`, `s`, `m`, `h`, `d`) and summing each into a `Duration`,
+/// e.g. `"1h30m"` -> 90 minutes. Errors on an unknown unit or empty input.
+fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("empty duration string".to_string());
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let digits_len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits_len == 0 {
+            return Err(format!("expected a number in duration {input:?}, found {rest:?}"));
+        }
+        let (number_str, after_number) = rest.split_at(digits_len);
+        let number: u64 = number_str
+            .parse()
+            .map_err(|_| format!("invalid number {number_str:?} in duration {input:?}"))?;
+
+        let unit_len = after_number
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(after_number.len());
+        if unit_len == 0 {
+            return Err(format!("duration segment {number_str:?} is missing a unit in {input:?}"));
+        }
+        let (unit, remainder) = after_number.split_at(unit_len);
+
+        total += match unit {
+            "ms" => Duration::from_millis(number),
+            "s" => Duration::from_secs(number),
+            "m" => Duration::from_secs(number * 60),
+            "h" => Duration::from_secs(number * 3600),
+            "d" => Duration::from_secs(number * 86400),
+            other => return Err(format!("unknown duration unit {other:?} in {input:?}")),
+        };
+        rest = remainder;
+    }
+
+    Ok(total)
+}
+
+fn deserialize_duration_opt<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Option<String> = Option::deserialize(deserializer)?;
+    raw.map(|s| parse_duration(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// The inverse of [`deserialize_duration_opt`] - whole seconds as `"Ns"`,
+/// or milliseconds as `"Nms"` when the duration isn't a whole number of
+/// seconds, so a round-tripped config stays in the same human-readable form.
+fn serialize_duration_opt<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Some(duration) if duration.subsec_millis() == 0 => {
+            serializer.serialize_some(&format!("{}s", duration.as_secs()))
+        }
+        Some(duration) => serializer.serialize_some(&format!("{}ms", duration.as_millis())),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MqttSection {
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    #[serde(default = "default_mqtt_topic_prefix")]
+    pub topic_prefix: String,
+    pub site: String,
+    /// MQTT QoS level (0, 1, or 2) applied to telemetry publishes; values
+    /// outside that range fall back to 1 (at-least-once). Default: 1.
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Whether telemetry publishes (wait_time/readings/obstructions/sensors)
+    /// are retained by the broker. Health is always retained regardless of
+    /// this setting, since it's what a newly-connecting consumer needs
+    /// immediately. Default: false.
+    #[serde(default)]
+    pub retain: bool,
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_topic_prefix() -> String {
+    "mariam".to_string()
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdminSection {
+    /// Loopback-only port for the runtime calibration/control command server.
+    #[serde(default = "default_admin_port")]
+    pub port: u16,
+}
+
+fn default_admin_port() -> u16 {
+    7171
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScpiSection {
+    /// Loopback-only port for the SCPI-style sensor control/query console.
+    #[serde(default = "default_scpi_port")]
+    pub port: u16,
+}
+
+fn default_scpi_port() -> u16 {
+    5025
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RelaySection {
+    /// `host:port` of the relay server's device-facing listener.
+    pub addr: String,
+    /// Stable id this device registers under with the relay.
+    pub device_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OtelSection {
+    /// OTLP/gRPC collector endpoint, e.g. `http://localhost:4317`. Only read
+    /// when built with the `otel_metrics` feature.
+    pub otlp_endpoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistorySection {
+    /// Max number of wait-time estimates retained in the `/api/history`
+    /// ring buffer (default: [`crate::state::DEFAULT_HISTORY_CAPACITY`]).
+    pub capacity: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchdogSection {
+    /// How long sensor acquisition may stay fully unhealthy before the
+    /// systemd watchdog heartbeat stops (default: 60).
+    pub unhealthy_grace_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StorageSection {
+    /// Max number of samples retained per sensor in the `/api/sensors/{id}/history`
+    /// ring buffer (default: [`crate::storage::DEFAULT_CAPACITY_PER_SENSOR`]).
+    pub capacity_per_sensor: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuntimeSection {
+    /// Path to the field-writable key=value runtime config (default: see
+    /// [`crate::runtime_config::DEFAULT_RUNTIME_CONFIG_PATH`]).
+    pub path: Option<PathBuf>,
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("failed to read config: {0}")]
     Read(#[from] std::io::Error),
     #[error("failed to parse config: {0}")]
     Parse(#[from] toml::de::Error),
+    #[error(
+        "no config file found; searched: {}",
+        .0.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    NotFound(Vec<PathBuf>),
+    #[error("invalid value for {0}: {1}")]
+    EnvOverride(String, String),
+    #[error(
+        "config file is version {0}, but this binary only understands up to version {CURRENT_CONFIG_VERSION}"
+    )]
+    UnsupportedVersion(u16),
+}
+
+/// The `MARIAM_FLOW_CONFIG` env var, if set, names a config file that
+/// takes precedence over every standard location below.
+pub const CONFIG_PATH_ENV_VAR: &str = "MARIAM_FLOW_CONFIG";
+
+/// Probes the standard locations for a config file, in priority order:
+/// `MARIAM_FLOW_CONFIG`, `./config/config.toml`, `$XDG_CONFIG_HOME` (or
+/// platform equivalent) `/mariam-flow/config.toml`, `~/.config/mariam-flow/config.toml`,
+/// then `/etc/mariam-flow/config.toml`. Returns the first that exists, or
+/// every candidate tried so the caller can tell the user exactly where it
+/// looked.
+pub fn discover_config_path() -> Result<PathBuf, Vec<PathBuf>> {
+    let mut candidates = Vec::new();
+
+    if let Ok(env_path) = std::env::var(CONFIG_PATH_ENV_VAR) {
+        candidates.push(PathBuf::from(env_path));
+    }
+    candidates.push(PathBuf::from(DEFAULT_CONFIG_PATH));
+    if let Some(config_dir) = dirs::config_dir() {
+        candidates.push(config_dir.join("mariam-flow").join("config.toml"));
+    }
+    if let Some(home_dir) = dirs::home_dir() {
+        candidates.push(home_dir.join(".config").join("mariam-flow").join("config.toml"));
+    }
+    candidates.push(PathBuf::from("/etc/mariam-flow/config.toml"));
+
+    match candidates.iter().find(|candidate| candidate.is_file()) {
+        Some(found) => Ok(found.clone()),
+        None => Err(candidates),
+    }
+}
+
+/// Resolves the config path to try at startup, the way `main` needs it
+/// before it can even construct a `ConfigError` - falls back to the first
+/// (highest-priority) candidate when nothing was found, so the subsequent
+/// `load_from_path` still reports a sensible `Read` error for that path.
+pub fn resolve_config_path() -> PathBuf {
+    discover_config_path().unwrap_or_else(|candidates| {
+        candidates
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH))
+    })
 }
 
 pub fn load_default() -> Result<Config, ConfigError> {
-    load_from_path(DEFAULT_CONFIG_PATH)
+    let path = discover_config_path().map_err(ConfigError::NotFound)?;
+    load_from_path(path)
 }
 
-pub fn load_from_path(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+/// `MARIAM_FLOW__<name>` prefix for the env-var overrides `load_with_env`
+/// applies after parsing the TOML file, e.g. `MARIAM_FLOW__SERVER__PORT`.
+pub const ENV_OVERRIDE_PREFIX: &str = "MARIAM_FLOW__";
+
+/// One `MARIAM_FLOW__<name>` env var and how to parse+apply its value onto
+/// an already-parsed `Config`.
+type EnvOverride = (&'static str, fn(&mut Config, &str) -> Result<(), String>);
+
+/// The `SECTION__KEY` names (after [`ENV_OVERRIDE_PREFIX`]) this loader
+/// understands, and how each one is parsed into its `Config` field. Add an
+/// entry here for every field ops should be able to tune without editing
+/// the TOML file.
+const ENV_OVERRIDES: &[EnvOverride] = &[
+    ("SERVER__PORT", |config, value| {
+        let port: u16 = value.parse().map_err(|err| format!("{err}"))?;
+        config.server.get_or_insert_with(ServerSection::default).port = Some(port);
+        Ok(())
+    }),
+    ("SERVER__REFRESH_INTERVAL", |config, value| {
+        let interval = parse_duration(value)?;
+        config
+            .server
+            .get_or_insert_with(ServerSection::default)
+            .refresh_interval = Some(interval);
+        Ok(())
+    }),
+    ("LOGGING__LEVEL", |config, value| {
+        config.logging.level = value.to_string();
+        Ok(())
+    }),
+];
+
+fn apply_env_overrides(config: &mut Config) -> Result<(), ConfigError> {
+    for (name, apply) in ENV_OVERRIDES {
+        let env_name = format!("{ENV_OVERRIDE_PREFIX}{name}");
+        if let Ok(value) = std::env::var(&env_name) {
+            apply(config, &value).map_err(|reason| ConfigError::EnvOverride(env_name, reason))?;
+        }
+    }
+    Ok(())
+}
+
+/// Folds a legacy flat `xshut_pins` list into the named `sensors` table
+/// introduced alongside it, so a version-1 document keeps loading the same
+/// sensors (now auto-named `"sensor-N"`) under the current schema. A
+/// no-op if `sensors.sensors` is already populated.
+fn migrate_v1_to_v2(mut value: toml::Value) -> toml::Value {
+    if let Some(table) = value.as_table_mut() {
+        if let Some(sensors) = table.get_mut("sensors").and_then(toml::Value::as_table_mut) {
+            let has_named_sensors = sensors
+                .get("sensors")
+                .and_then(toml::Value::as_array)
+                .is_some_and(|entries| !entries.is_empty());
+            if !has_named_sensors {
+                if let Some(toml::Value::Array(pins)) = sensors.remove("xshut_pins") {
+                    let entries: Vec<toml::Value> = pins
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, pin)| {
+                            let mut entry = toml::map::Map::new();
+                            entry.insert("name".to_string(), toml::Value::String(format!("sensor-{}", index + 1)));
+                            entry.insert("xshut_pin".to_string(), pin);
+                            toml::Value::Table(entry)
+                        })
+                        .collect();
+                    sensors.insert("sensors".to_string(), toml::Value::Array(entries));
+                }
+            }
+        }
+        table.insert("version".to_string(), toml::Value::Integer(CURRENT_CONFIG_VERSION as i64));
+    }
+    value
+}
+
+/// Applies each `migrate_vN_to_vN+1` step in turn until `value` is at
+/// [`CURRENT_CONFIG_VERSION`], or rejects a document from a newer binary
+/// outright rather than risk silently misparsing it.
+fn migrate_to_current(mut value: toml::Value, version: u16) -> Result<toml::Value, ConfigError> {
+    if version > CURRENT_CONFIG_VERSION {
+        return Err(ConfigError::UnsupportedVersion(version));
+    }
+    if version < 2 {
+        value = migrate_v1_to_v2(value);
+    }
+    Ok(value)
+}
+
+fn read_config_file(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
     let contents = std::fs::read_to_string(path)?;
-    let config: Config = toml::from_str(&contents)?;
+    let raw: toml::Value = toml::from_str(&contents)?;
+    let version = raw
+        .get("version")
+        .and_then(toml::Value::as_integer)
+        .map(|value| value as u16)
+        .unwrap_or(1);
+    let migrated = migrate_to_current(raw, version)?;
+    let config: Config = migrated.try_into()?;
+    Ok(config)
+}
+
+/// Parses the TOML file at `path`, then layers `MARIAM_FLOW__SECTION__KEY`
+/// environment variables on top (see [`ENV_OVERRIDES`]), so a
+/// containerized/embedded deployment can tune a handful of fields without
+/// editing the file.
+pub fn load_with_env(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+    let mut config = read_config_file(path)?;
+    apply_env_overrides(&mut config)?;
     Ok(config)
 }
 
+pub fn load_from_path(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+    load_with_env(path)
+}
+
+/// The canonical starter config, fully commented, embedded at compile time
+/// so first-run setup doesn't require hand-authoring TOML from scratch.
+const EMBEDDED_DEFAULT_CONFIG: &str = include_str!("defconfig.toml");
+
 impl Config {
+    /// Parses [`EMBEDDED_DEFAULT_CONFIG`]. Panics if the embedded file
+    /// doesn't parse, since that would mean the binary shipped with a
+    /// broken default - a build-time bug, not a runtime one.
+    pub fn default_embedded() -> Config {
+        toml::from_str(EMBEDDED_DEFAULT_CONFIG).expect("embedded default config must parse")
+    }
+
+    /// Writes the canonical starter config to `path`, for an `init`
+    /// subcommand or other first-run setup to materialize a valid,
+    /// documented file instead of the operator hand-authoring one.
+    pub fn write_default(path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, EMBEDDED_DEFAULT_CONFIG)
+    }
+
     pub fn calibration_path(&self) -> Option<&Path> {
         let path = self.calibration.as_ref()?.path.as_deref()?;
         if path.as_os_str().is_empty() {
@@ -77,13 +522,26 @@ impl Config {
         }
     }
 
-    /// Returns sensor configurations built from xshut_pins, or empty vec if not configured.
+    /// Returns sensor configurations built from the `sensors` section's
+    /// entries (named `sensors` entries if given, else `xshut_pins`), or
+    /// empty vec if not configured.
     pub fn sensor_configs(&self) -> Vec<SensorConfig> {
         match &self.sensors {
-            Some(section) if !section.xshut_pins.is_empty() => {
-                build_sensor_configs(&section.xshut_pins)
+            Some(section) => {
+                let pins: Vec<u8> = section.entries().iter().map(|entry| entry.xshut_pin).collect();
+                build_sensor_configs(&pins)
             }
-            _ => Vec::new(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the configured name for each sensor, in the same order as
+    /// [`Config::sensor_configs`] - `"sensor-N"` (1-indexed) for entries
+    /// that only came from `xshut_pins`.
+    pub fn sensor_names(&self) -> Vec<String> {
+        match &self.sensors {
+            Some(section) => section.entries().into_iter().map(|entry| entry.name).collect(),
+            None => Vec::new(),
         }
     }
 
@@ -103,14 +561,107 @@ impl Config {
             .unwrap_or(DEFAULT_SERVER_PORT)
     }
 
-    /// Returns the refresh interval as Duration (default: 5 seconds)
+    /// Returns the refresh interval as Duration (default: 5 seconds).
+    /// `[server].refresh_interval` takes precedence over the deprecated
+    /// `refresh_interval_secs` alias.
     pub fn refresh_interval(&self) -> Duration {
-        let secs = self
-            .server
+        let server = self.server.as_ref();
+        server
+            .and_then(|s| s.refresh_interval)
+            .or_else(|| {
+                server
+                    .and_then(|s| s.refresh_interval_secs)
+                    .map(Duration::from_secs)
+            })
+            .unwrap_or_else(|| Duration::from_secs(DEFAULT_REFRESH_INTERVAL_SECS))
+    }
+
+    /// Returns the MQTT telemetry configuration, if configured.
+    #[cfg(feature = "mqtt")]
+    pub fn mqtt_config(&self) -> Option<crate::telemetry::mqtt::MqttConfig> {
+        let section = self.mqtt.as_ref()?;
+        Some(crate::telemetry::mqtt::MqttConfig {
+            host: section.host.clone(),
+            port: section.port,
+            username: section.username.clone(),
+            password: section.password.clone(),
+            topic_prefix: section.topic_prefix.clone(),
+            site: section.site.clone(),
+            client_id: format!("mariam-flow-{}", section.site),
+            qos: crate::telemetry::mqtt::qos_from_level(section.qos),
+            retain: section.retain,
+        })
+    }
+
+    /// Returns the loopback address for the admin command server, if enabled.
+    pub fn admin_addr(&self) -> Option<std::net::SocketAddr> {
+        let section = self.admin.as_ref()?;
+        Some(std::net::SocketAddr::from(([127, 0, 0, 1], section.port)))
+    }
+
+    /// Returns the loopback address for the SCPI command console, if enabled.
+    pub fn scpi_addr(&self) -> Option<std::net::SocketAddr> {
+        let section = self.scpi.as_ref()?;
+        Some(std::net::SocketAddr::from(([127, 0, 0, 1], section.port)))
+    }
+
+    /// Returns the reverse-tunnel relay client configuration, if enabled.
+    pub fn relay_config(&self) -> Option<crate::bus::relay::RelayConfig> {
+        let section = self.relay.as_ref()?;
+        Some(crate::bus::relay::RelayConfig {
+            relay_addr: section.addr.clone(),
+            device_id: section.device_id.clone(),
+        })
+    }
+
+    /// Returns the OTLP collector endpoint for the OpenTelemetry metrics
+    /// bridge, if configured.
+    #[cfg(feature = "otel_metrics")]
+    pub fn otlp_endpoint(&self) -> Option<String> {
+        Some(self.otel.as_ref()?.otlp_endpoint.clone())
+    }
+
+    /// Returns the `/api/history` ring buffer capacity (default:
+    /// [`crate::state::DEFAULT_HISTORY_CAPACITY`]).
+    pub fn history_capacity(&self) -> usize {
+        self.history
+            .as_ref()
+            .and_then(|section| section.capacity)
+            .unwrap_or(crate::state::DEFAULT_HISTORY_CAPACITY)
+    }
+
+    /// Returns the systemd watchdog's unhealthy-sensors grace period,
+    /// falling back to [`crate::watchdog::WatchdogConfig::default`] if
+    /// unset.
+    pub fn watchdog_config(&self) -> crate::watchdog::WatchdogConfig {
+        let unhealthy_grace = self
+            .watchdog
+            .as_ref()
+            .and_then(|section| section.unhealthy_grace_secs)
+            .map(Duration::from_secs)
+            .unwrap_or_else(|| crate::watchdog::WatchdogConfig::default().unhealthy_grace);
+        crate::watchdog::WatchdogConfig { unhealthy_grace }
+    }
+
+    /// Returns the per-sensor history retention for `/api/sensors/{id}/history`,
+    /// falling back to [`crate::storage::DEFAULT_CAPACITY_PER_SENSOR`] if
+    /// unset.
+    pub fn storage_retention(&self) -> crate::storage::RetentionConfig {
+        let capacity_per_sensor = self
+            .storage
+            .as_ref()
+            .and_then(|section| section.capacity_per_sensor)
+            .unwrap_or(crate::storage::DEFAULT_CAPACITY_PER_SENSOR);
+        crate::storage::RetentionConfig { capacity_per_sensor }
+    }
+
+    /// Returns the path to the field-writable runtime config, falling back
+    /// to [`crate::runtime_config::DEFAULT_RUNTIME_CONFIG_PATH`] if unset.
+    pub fn runtime_config_path(&self) -> PathBuf {
+        self.runtime
             .as_ref()
-            .and_then(|s| s.refresh_interval_secs)
-            .unwrap_or(DEFAULT_REFRESH_INTERVAL_SECS);
-        Duration::from_secs(secs)
+            .and_then(|section| section.path.clone())
+            .unwrap_or_else(|| PathBuf::from(crate::runtime_config::DEFAULT_RUNTIME_CONFIG_PATH))
     }
 }
 
@@ -199,4 +750,397 @@ level = "info"
         assert!(matches!(result, Err(ConfigError::Parse(_))));
         Ok(())
     }
+
+    #[test]
+    fn runtime_config_path_defaults_when_section_missing() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let config = load_default()?;
+        assert_eq!(
+            config.runtime_config_path(),
+            PathBuf::from(crate::runtime_config::DEFAULT_RUNTIME_CONFIG_PATH)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn runtime_config_path_honors_override() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-runtime-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[runtime]
+path = "/tmp/custom-runtime.txt"
+"#;
+        fs::write(&path, contents)?;
+
+        let result = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            result.runtime_config_path(),
+            PathBuf::from("/tmp/custom-runtime.txt")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn history_capacity_defaults_when_section_missing() -> Result<(), Box<dyn std::error::Error>> {
+        let config = load_default()?;
+        assert_eq!(
+            config.history_capacity(),
+            crate::state::DEFAULT_HISTORY_CAPACITY
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn history_capacity_honors_override() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-history-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[history]
+capacity = 500
+"#;
+        fs::write(&path, contents)?;
+
+        let result = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(result.history_capacity(), 500);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_duration_accumulates_mixed_units() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("5s"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(
+            parse_duration("1h30m"),
+            Ok(Duration::from_secs(90 * 60))
+        );
+        assert_eq!(parse_duration("1d"), Ok(Duration::from_secs(86_400)));
+    }
+
+    #[test]
+    fn parse_duration_rejects_empty_and_unknown_units() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("   ").is_err());
+        assert!(parse_duration("5").is_err());
+        assert!(parse_duration("5x").is_err());
+    }
+
+    #[test]
+    fn refresh_interval_accepts_human_readable_string() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-refresh-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[server]
+refresh_interval = "1h30m"
+"#;
+        fs::write(&path, contents)?;
+
+        let result = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(result.refresh_interval(), Duration::from_secs(90 * 60));
+        Ok(())
+    }
+
+    #[test]
+    fn refresh_interval_falls_back_to_deprecated_secs_field() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-refresh-secs-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[server]
+refresh_interval_secs = 10
+"#;
+        fs::write(&path, contents)?;
+
+        let result = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(result.refresh_interval(), Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn discover_config_path_prefers_env_var_override() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-discover-{unique}.toml"));
+        fs::write(&path, "[app]\nname = \"mariam-flow\"\n\n[logging]\nlevel = \"info\"\n")?;
+
+        unsafe {
+            std::env::set_var(CONFIG_PATH_ENV_VAR, &path);
+        }
+        let result = discover_config_path();
+        unsafe {
+            std::env::remove_var(CONFIG_PATH_ENV_VAR);
+        }
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(result, Ok(path));
+        Ok(())
+    }
+
+    #[test]
+    fn config_not_found_error_lists_every_candidate() {
+        let candidates = vec![
+            PathBuf::from("config/config.toml"),
+            PathBuf::from("/etc/mariam-flow/config.toml"),
+        ];
+        let error = ConfigError::NotFound(candidates);
+
+        let message = error.to_string();
+        assert!(message.contains("config/config.toml"));
+        assert!(message.contains("/etc/mariam-flow/config.toml"));
+    }
+
+    #[test]
+    fn env_overrides_apply_on_top_of_the_parsed_file() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-env-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[server]
+port = 8080
+"#;
+        fs::write(&path, contents)?;
+
+        unsafe {
+            std::env::set_var("MARIAM_FLOW__SERVER__PORT", "9090");
+            std::env::set_var("MARIAM_FLOW__SERVER__REFRESH_INTERVAL", "10s");
+            std::env::set_var("MARIAM_FLOW__LOGGING__LEVEL", "debug");
+        }
+        let result = load_with_env(&path);
+        unsafe {
+            std::env::remove_var("MARIAM_FLOW__SERVER__PORT");
+            std::env::remove_var("MARIAM_FLOW__SERVER__REFRESH_INTERVAL");
+            std::env::remove_var("MARIAM_FLOW__LOGGING__LEVEL");
+        }
+        let _ = fs::remove_file(&path);
+        let config = result?;
+
+        assert_eq!(config.server_port(), 9090);
+        assert_eq!(config.refresh_interval(), Duration::from_secs(10));
+        assert_eq!(config.logging.level, "debug");
+        Ok(())
+    }
+
+    #[test]
+    fn env_override_with_invalid_value_returns_env_override_error() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-env-invalid-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+"#;
+        fs::write(&path, contents)?;
+
+        unsafe {
+            std::env::set_var("MARIAM_FLOW__SERVER__PORT", "not-a-port");
+        }
+        let result = load_with_env(&path);
+        unsafe {
+            std::env::remove_var("MARIAM_FLOW__SERVER__PORT");
+        }
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(ConfigError::EnvOverride(_, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn named_sensors_take_precedence_and_carry_metadata() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-named-sensors-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[[sensors.sensors]]
+name = "inlet"
+xshut_pin = 17
+address = 41
+offset_mm = -12
+position = "ceiling-left"
+
+[[sensors.sensors]]
+name = "outlet"
+xshut_pin = 27
+"#;
+        fs::write(&path, contents)?;
+
+        let config = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(config.sensor_names(), vec!["inlet", "outlet"]);
+        let sensor_configs = config.sensor_configs();
+        assert_eq!(sensor_configs.len(), 2);
+        assert_eq!(sensor_configs[0].xshut_pin, 17);
+        assert_eq!(sensor_configs[1].xshut_pin, 27);
+        Ok(())
+    }
+
+    #[test]
+    fn bare_xshut_pins_fall_back_to_auto_generated_names() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-bare-pins-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[sensors]
+xshut_pins = [17, 27]
+"#;
+        fs::write(&path, contents)?;
+
+        let config = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(config.sensor_names(), vec!["sensor-1", "sensor-2"]);
+        assert_eq!(config.sensor_configs().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn version_1_document_migrates_xshut_pins_into_named_sensors() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-migrate-v1-{unique}.toml"));
+        let contents = r#"
+version = 1
+
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+
+[sensors]
+xshut_pins = [17, 27]
+"#;
+        fs::write(&path, contents)?;
+
+        let config = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.sensor_names(), vec!["sensor-1", "sensor-2"]);
+        Ok(())
+    }
+
+    #[test]
+    fn document_missing_version_is_treated_as_version_1() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-no-version-{unique}.toml"));
+        let contents = r#"
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+"#;
+        fs::write(&path, contents)?;
+
+        let config = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        Ok(())
+    }
+
+    #[test]
+    fn newer_version_than_supported_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-too-new-{unique}.toml"));
+        let contents = format!(
+            r#"
+version = {}
+
+[app]
+name = "mariam-flow"
+
+[logging]
+level = "info"
+"#,
+            CURRENT_CONFIG_VERSION + 1
+        );
+        fs::write(&path, &contents)?;
+
+        let result = load_from_path(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(ConfigError::UnsupportedVersion(v)) if v == CURRENT_CONFIG_VERSION + 1));
+        Ok(())
+    }
+
+    #[test]
+    fn embedded_default_config_parses_and_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+        let config = Config::default_embedded();
+        assert_eq!(config.version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.sensor_names(), vec!["sensor-1", "sensor-2"]);
+        assert!(config.calibration_path().is_some());
+
+        let temp_dir = std::env::temp_dir();
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let path = temp_dir.join(format!("mariam-config-write-default-{unique}.toml"));
+
+        Config::write_default(&path)?;
+        let written = load_from_path(&path)?;
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(written.sensor_names(), config.sensor_names());
+        Ok(())
+    }
 }
@@ -0,0 +1,146 @@
+//! Hot-reload for the resolved config file.
+//!
+//! Polls the file's mtime on a fixed interval and, once it settles after a
+//! change (debounced so an editor's truncate-write-rename dance collapses
+//! into one reload instead of several), re-runs [`config::load_from_path`]
+//! and publishes the result over a `tokio::sync::watch` channel. The only
+//! subscriber is the reload listener in `main.rs`, which applies the new
+//! logging level; the estimation pipeline and server do not subscribe, so
+//! `refresh_interval` and sensor layout changes still need a restart. A read/parse
+//! failure during reload is logged and the last-known-good `Config` keeps
+//! being served - a bad edit should never crash the unit.
+
+use crate::config::{self, Config};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tokio::sync::watch;
+use tracing::{error, info};
+
+/// How often the config file's mtime is polled for changes.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+/// How long the mtime must stay unchanged before a reload fires - absorbs
+/// editors that perform several writes per logical save.
+const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Spawns the watcher task and returns a receiver that always holds the
+/// last-known-good `Config`, starting with `initial`.
+pub fn spawn_config_watcher(
+    path: PathBuf,
+    initial: Config,
+) -> (watch::Receiver<Config>, tokio::task::JoinHandle<()>) {
+    let (tx, rx) = watch::channel(initial);
+    let handle = tokio::spawn(async move {
+        run(path, tx).await;
+    });
+    (rx, handle)
+}
+
+async fn run(path: PathBuf, tx: watch::Sender<Config>) {
+    let mut last_mtime = file_mtime(&path);
+    let mut pending_since: Option<tokio::time::Instant> = None;
+    let mut ticker = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        ticker.tick().await;
+
+        let mtime = file_mtime(&path);
+        if mtime != last_mtime {
+            last_mtime = mtime;
+            pending_since = Some(tokio::time::Instant::now());
+            continue;
+        }
+
+        let Some(since) = pending_since else {
+            continue;
+        };
+        if since.elapsed() < DEBOUNCE {
+            continue;
+        }
+        pending_since = None;
+
+        match config::load_from_path(&path) {
+            Ok(new_config) => {
+                info!(path = %path.display(), "Config file changed, reloaded");
+                if tx.send(new_config).is_err() {
+                    return;
+                }
+            }
+            Err(err) => {
+                error!(
+                    error = %err,
+                    path = %path.display(),
+                    "Failed to reload config after change - keeping last-known-good"
+                );
+            }
+        }
+    }
+}
+
+fn file_mtime(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime as StdSystemTime, UNIX_EPOCH};
+
+    fn temp_path(label: &str) -> PathBuf {
+        let unique = StdSystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos();
+        std::env::temp_dir().join(format!("mariam-config-watcher-{label}-{unique}.toml"))
+    }
+
+    fn write_valid(path: &std::path::Path, port: u16) {
+        fs::write(
+            path,
+            format!(
+                "[app]\nname = \"mariam-flow\"\n\n[logging]\nlevel = \"info\"\n\n[server]\nport = {port}\n"
+            ),
+        )
+        .expect("write fixture config");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reload_publishes_new_config_once_write_settles() {
+        let path = temp_path("reload");
+        write_valid(&path, 8080);
+        let initial = config::load_from_path(&path).expect("load initial config");
+
+        let (mut rx, _handle) = spawn_config_watcher(path.clone(), initial);
+        assert_eq!(rx.borrow().server_port(), 8080);
+
+        tokio::time::advance(POLL_INTERVAL).await;
+        write_valid(&path, 9090);
+        // A couple of closely-spaced writes, like an editor's save, should
+        // still collapse into exactly one reload.
+        tokio::time::advance(std::time::Duration::from_millis(100)).await;
+        write_valid(&path, 9090);
+
+        tokio::time::advance(POLL_INTERVAL + DEBOUNCE).await;
+        rx.changed().await.expect("watcher still running");
+
+        assert_eq!(rx.borrow().server_port(), 9090);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn parse_error_during_reload_keeps_last_known_good() {
+        let path = temp_path("bad-reload");
+        write_valid(&path, 8080);
+        let initial = config::load_from_path(&path).expect("load initial config");
+
+        let (rx, _handle) = spawn_config_watcher(path.clone(), initial);
+
+        tokio::time::advance(POLL_INTERVAL).await;
+        fs::write(&path, "not = [valid").expect("write broken fixture");
+
+        tokio::time::advance(POLL_INTERVAL + DEBOUNCE * 2).await;
+
+        assert_eq!(rx.borrow().server_port(), 8080);
+        let _ = fs::remove_file(&path);
+    }
+}
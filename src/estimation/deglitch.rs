@@ -0,0 +1,207 @@
+//! Temporal deglitch filter for `EstimationModel`.
+//!
+//! ToF sensors occasionally emit a spurious single-frame transition
+//! (`SigmaFailure`/`Wraparound` flicker). This model keeps a short per-sensor
+//! history of obstruction classifications and reports a sensor obstructed
+//! only while a strict majority of the valid samples in its window are
+//! obstructed (a median-edge deglitcher applied to occupancy transitions).
+//! A tied window (even `window_len`, or a window not yet full) reads as
+//! unobstructed; the previous state is not held through a tie.
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig, occupancy_from_obstructions};
+use crate::sensor::SensorId;
+use crate::state::{OccupancyStatus, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for the deglitch model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeglitchParams {
+    /// Number of classifications kept per sensor before a majority vote.
+    pub window_len: usize,
+    pub slope: f64,
+    pub intercept: f64,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for DeglitchParams {
+    fn default() -> Self {
+        Self {
+            window_len: 5,
+            slope: 0.2,
+            intercept: 0.0,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+/// Estimation model that debounces per-sensor obstruction state before
+/// computing occupancy and wait time.
+#[derive(Debug)]
+pub struct DeglitchModel {
+    params: DeglitchParams,
+    occupancy_config: OccupancyConfig,
+    history: Mutex<HashMap<SensorId, VecDeque<bool>>>,
+}
+
+impl DeglitchModel {
+    pub fn new(params: DeglitchParams, occupancy_config: OccupancyConfig) -> Self {
+        Self {
+            params,
+            occupancy_config,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Debounce each sensor's obstruction flag against its recent history.
+    fn debounce(&self, obstructions: &[SensorObstruction]) -> Vec<SensorObstruction> {
+        let mut history = self
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        obstructions
+            .iter()
+            .map(|obstruction| {
+                let debounced = match obstruction.obstructed {
+                    Some(value) => {
+                        let window = history.entry(obstruction.sensor_id).or_default();
+                        window.push_back(value);
+                        while window.len() > self.params.window_len.max(1) {
+                            window.pop_front();
+                        }
+                        majority_vote(window).unwrap_or(value)
+                    }
+                    // Missing sample: don't disturb history, hold the last majority.
+                    None => history
+                        .get(&obstruction.sensor_id)
+                        .and_then(majority_vote)
+                        .unwrap_or(false),
+                };
+
+                SensorObstruction {
+                    sensor_id: obstruction.sensor_id,
+                    obstructed: Some(debounced),
+                    timestamp: obstruction.timestamp,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Strict majority vote over the window; `None` on an empty window.
+fn majority_vote(window: &VecDeque<bool>) -> Option<bool> {
+    if window.is_empty() {
+        return None;
+    }
+    let true_count = window.iter().filter(|value| **value).count();
+    let false_count = window.len() - true_count;
+    Some(true_count > false_count)
+}
+
+impl EstimationModel for DeglitchModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let debounced = self.debounce(obstructions);
+        let occupancy = occupancy_from_obstructions(&debounced, timestamp);
+
+        let Some(occupancy_percent) = occupancy.occupancy_percent else {
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let mut wait_time = self.params.intercept + self.params.slope * occupancy_percent;
+        if let Some(min) = self.params.min_wait_minutes {
+            wait_time = wait_time.max(min as f64);
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            wait_time = wait_time.min(max as f64);
+        }
+
+        let status = match occupancy.status {
+            OccupancyStatus::Degraded => WaitTimeStatus::Degraded,
+            _ => WaitTimeStatus::Ok,
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(wait_time),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn obstruction(sensor_id: SensorId, obstructed: Option<bool>) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id,
+            obstructed,
+            timestamp: UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn single_frame_spike_is_rejected() {
+        let model = DeglitchModel::new(
+            DeglitchParams {
+                window_len: 5,
+                ..DeglitchParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        for _ in 0..4 {
+            let _ = model.debounce(&[obstruction(1, Some(false))]);
+        }
+        let result = model.debounce(&[obstruction(1, Some(true))]);
+
+        assert_eq!(result[0].obstructed, Some(false));
+    }
+
+    #[test]
+    fn sustained_change_eventually_flips() {
+        let model = DeglitchModel::new(
+            DeglitchParams {
+                window_len: 3,
+                ..DeglitchParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        let _ = model.debounce(&[obstruction(1, Some(false))]);
+        let _ = model.debounce(&[obstruction(1, Some(true))]);
+        let result = model.debounce(&[obstruction(1, Some(true))]);
+
+        assert_eq!(result[0].obstructed, Some(true));
+    }
+
+    #[test]
+    fn missing_samples_do_not_pollute_history() {
+        let model = DeglitchModel::new(DeglitchParams::default(), OccupancyConfig::default());
+
+        let _ = model.debounce(&[obstruction(1, Some(true))]);
+        let result = model.debounce(&[obstruction(1, None)]);
+
+        assert_eq!(result[0].obstructed, Some(true));
+    }
+}
@@ -0,0 +1,200 @@
+//! EWMA/PI-smoothed occupancy estimation model.
+//!
+//! `LinearV1Model` reacts instantly to every occupancy sample, which makes
+//! minute-to-minute wait time estimates jittery. This model low-pass filters
+//! occupancy over time before feeding it into the slope/intercept formula,
+//! using a time-constant so irregular sample intervals are handled
+//! correctly: `alpha = 1 - exp(-dt / tau)`.
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig, occupancy_from_obstructions};
+use crate::state::{OccupancyStatus, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus};
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for the EWMA/PI-smoothed model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EwmaParams {
+    /// Time constant (seconds) of the low-pass filter.
+    pub tau_secs: f64,
+    pub slope: f64,
+    pub intercept: f64,
+    /// Integral gain nudging the estimate toward recent trend. Defaults to 0 (pure low-pass).
+    #[serde(default)]
+    pub ki: f64,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for EwmaParams {
+    fn default() -> Self {
+        Self {
+            tau_secs: 30.0,
+            slope: 0.2,
+            intercept: 0.0,
+            ki: 0.0,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FilterState {
+    smoothed_occupancy: Option<f64>,
+    integral: f64,
+    last_timestamp: Option<SystemTime>,
+}
+
+/// Estimation model that low-pass filters occupancy before applying the
+/// slope/intercept wait-time formula.
+#[derive(Debug)]
+pub struct EwmaModel {
+    params: EwmaParams,
+    occupancy_config: OccupancyConfig,
+    state: Mutex<FilterState>,
+}
+
+impl EwmaModel {
+    pub fn new(params: EwmaParams, occupancy_config: OccupancyConfig) -> Self {
+        Self {
+            params,
+            occupancy_config,
+            state: Mutex::new(FilterState::default()),
+        }
+    }
+}
+
+impl EstimationModel for EwmaModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let occupancy = occupancy_from_obstructions(obstructions, timestamp);
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some(occupancy_percent) = occupancy.occupancy_percent else {
+            // Hold the last smoothed value; do not reset the filter.
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let dt = state
+            .last_timestamp
+            .and_then(|previous| timestamp.duration_since(previous).ok())
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or(0.0);
+
+        let smoothed = match state.smoothed_occupancy {
+            Some(previous) if self.params.tau_secs > 0.0 && dt > 0.0 => {
+                let alpha = 1.0 - (-dt / self.params.tau_secs).exp();
+                alpha * occupancy_percent + (1.0 - alpha) * previous
+            }
+            _ => occupancy_percent,
+        };
+
+        state.integral += (occupancy_percent - smoothed) * dt.max(0.0);
+        state.smoothed_occupancy = Some(smoothed);
+        state.last_timestamp = Some(timestamp);
+
+        let mut wait_time =
+            self.params.intercept + self.params.slope * smoothed + self.params.ki * state.integral;
+
+        if let Some(min) = self.params.min_wait_minutes {
+            if wait_time < min as f64 {
+                wait_time = min as f64;
+                state.integral = 0.0; // anti-windup: stop accumulating while pinned
+            }
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            if wait_time > max as f64 {
+                wait_time = max as f64;
+                state.integral = 0.0;
+            }
+        }
+
+        let status = match occupancy.status {
+            OccupancyStatus::Degraded => WaitTimeStatus::Degraded,
+            _ => WaitTimeStatus::Ok,
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(wait_time),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn obstruction(obstructed: Option<bool>, timestamp: SystemTime) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id: 1,
+            obstructed,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn first_sample_passes_through_unsmoothed() {
+        let model = EwmaModel::new(EwmaParams::default(), OccupancyConfig::default());
+
+        let estimate =
+            model.compute_wait_time(&[obstruction(Some(true), UNIX_EPOCH)], UNIX_EPOCH);
+
+        assert_eq!(estimate.wait_time_minutes, Some(0.2 * 100.0));
+    }
+
+    #[test]
+    fn subsequent_samples_are_smoothed_toward_new_value() {
+        let model = EwmaModel::new(
+            EwmaParams {
+                tau_secs: 10.0,
+                ..EwmaParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(10);
+
+        let first = model.compute_wait_time(&[obstruction(Some(false), t0)], t0);
+        let second = model.compute_wait_time(&[obstruction(Some(true), t1)], t1);
+
+        assert_eq!(first.wait_time_minutes, Some(0.0));
+        // Smoothed occupancy should sit strictly between 0 and 100 after one time constant.
+        let wait = second.wait_time_minutes.expect("wait time");
+        assert!(wait > 0.0 && wait < 20.0);
+    }
+
+    #[test]
+    fn no_data_holds_last_value_and_degrades() {
+        let model = EwmaModel::new(EwmaParams::default(), OccupancyConfig::default());
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(5);
+
+        let _ = model.compute_wait_time(&[obstruction(Some(true), t0)], t0);
+        let estimate = model.compute_wait_time(&[obstruction(None, t1)], t1);
+
+        assert_eq!(estimate.wait_time_minutes, None);
+        assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+        assert_eq!(estimate.error_code, Some(WaitTimeErrorCode::NoData));
+    }
+}
@@ -0,0 +1,157 @@
+//! Offline least-squares fitter that derives [`CalibrationParams`] from
+//! logged occupancy/wait-time samples - e.g. a replay log
+//! ([`crate::sensor::replay`]) paired with ground-truth timings - instead of
+//! requiring the slope/intercept to be hand-authored into a calibration
+//! file.
+
+use crate::estimation::CalibrationError;
+use crate::state::CalibrationParams;
+
+/// One observed `(occupancy_percent, measured_wait_minutes)` pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaitTimeSample {
+    pub occupancy_percent: f64,
+    pub wait_minutes: f64,
+}
+
+/// Ordinary-least-squares denominator (`n*Σx² - (Σx)²`) below which `x` is
+/// considered to have no meaningful variance - the fit would otherwise
+/// divide by (near) zero.
+const DENOMINATOR_EPSILON: f64 = 1e-9;
+
+/// Fits `slope`/`intercept` by ordinary least squares over `samples`, with
+/// `x` = occupancy percent and `y` = measured wait minutes:
+///
+/// ```text
+/// slope     = (n*Σxy - Σx*Σy) / (n*Σx² - (Σx)²)
+/// intercept = (Σy - slope*Σx) / n
+/// ```
+///
+/// Requires at least two samples with non-zero variance in `x`; returns
+/// [`CalibrationError::Invalid`] otherwise. When `bound_from_samples` is
+/// true, `min_wait_minutes`/`max_wait_minutes` are set from the observed
+/// range of `wait_minutes`.
+pub fn fit_calibration_params(
+    samples: &[WaitTimeSample],
+    bound_from_samples: bool,
+) -> Result<CalibrationParams, CalibrationError> {
+    if samples.len() < 2 {
+        return Err(CalibrationError::Invalid(
+            "at least two samples are required to fit calibration params".to_string(),
+        ));
+    }
+
+    let n = samples.len() as f64;
+    let sum_x: f64 = samples.iter().map(|sample| sample.occupancy_percent).sum();
+    let sum_y: f64 = samples.iter().map(|sample| sample.wait_minutes).sum();
+    let sum_xy: f64 = samples
+        .iter()
+        .map(|sample| sample.occupancy_percent * sample.wait_minutes)
+        .sum();
+    let sum_xx: f64 = samples
+        .iter()
+        .map(|sample| sample.occupancy_percent * sample.occupancy_percent)
+        .sum();
+
+    let denominator = n * sum_xx - sum_x * sum_x;
+    if denominator.abs() < DENOMINATOR_EPSILON {
+        return Err(CalibrationError::Invalid(
+            "samples have no variance in occupancy percent".to_string(),
+        ));
+    }
+
+    let slope = (n * sum_xy - sum_x * sum_y) / denominator;
+    let intercept = (sum_y - slope * sum_x) / n;
+
+    let (min_wait_minutes, max_wait_minutes) = if bound_from_samples {
+        let min = samples
+            .iter()
+            .map(|sample| sample.wait_minutes)
+            .fold(f64::INFINITY, f64::min);
+        let max = samples
+            .iter()
+            .map(|sample| sample.wait_minutes)
+            .fold(f64::NEG_INFINITY, f64::max);
+        (
+            Some(min.max(0.0).round() as u32),
+            Some(max.max(0.0).round() as u32),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(CalibrationParams {
+        slope,
+        intercept,
+        min_wait_minutes,
+        max_wait_minutes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(occupancy_percent: f64, wait_minutes: f64) -> WaitTimeSample {
+        WaitTimeSample {
+            occupancy_percent,
+            wait_minutes,
+        }
+    }
+
+    #[test]
+    fn fits_an_exact_line_through_two_points() {
+        let samples = vec![sample(0.0, 2.0), sample(100.0, 22.0)];
+
+        let params = fit_calibration_params(&samples, false).expect("fit succeeds");
+
+        assert!((params.slope - 0.2).abs() < 1e-9);
+        assert!((params.intercept - 2.0).abs() < 1e-9);
+        assert_eq!(params.min_wait_minutes, None);
+        assert_eq!(params.max_wait_minutes, None);
+    }
+
+    #[test]
+    fn fits_a_noisy_set_of_samples_by_least_squares() {
+        let samples = vec![
+            sample(0.0, 1.0),
+            sample(25.0, 6.0),
+            sample(50.0, 11.0),
+            sample(75.0, 14.0),
+            sample(100.0, 21.0),
+        ];
+
+        let params = fit_calibration_params(&samples, false).expect("fit succeeds");
+
+        assert!((params.slope - 0.192).abs() < 1e-9);
+        assert!((params.intercept - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_fewer_than_two_samples() {
+        let samples = vec![sample(50.0, 10.0)];
+
+        let err = fit_calibration_params(&samples, false).unwrap_err();
+
+        assert!(matches!(err, CalibrationError::Invalid(_)));
+    }
+
+    #[test]
+    fn rejects_samples_with_no_variance_in_occupancy() {
+        let samples = vec![sample(50.0, 10.0), sample(50.0, 15.0), sample(50.0, 5.0)];
+
+        let err = fit_calibration_params(&samples, false).unwrap_err();
+
+        assert!(matches!(err, CalibrationError::Invalid(_)));
+    }
+
+    #[test]
+    fn bounds_are_set_from_observed_range_when_requested() {
+        let samples = vec![sample(0.0, 2.0), sample(50.0, 12.0), sample(100.0, 22.0)];
+
+        let params = fit_calibration_params(&samples, true).expect("fit succeeds");
+
+        assert_eq!(params.min_wait_minutes, Some(2));
+        assert_eq!(params.max_wait_minutes, Some(22));
+    }
+}
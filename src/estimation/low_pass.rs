@@ -0,0 +1,215 @@
+//! First-order low-pass filtered occupancy estimation model.
+//!
+//! `EwmaModel` already low-pass filters occupancy, but holds its last
+//! smoothed value across a `NoData` cycle and discretizes the filter as a
+//! continuous exponential decay (`alpha = 1 - exp(-dt / tau)`). This model
+//! instead uses the simpler bilinear discretization `alpha = dt / (tau +
+//! dt)`, and resets its filter state to the raw value after a `NoData`
+//! cycle instead of holding it, so a sensor outage doesn't leave a stale
+//! reading smeared across the recovery once sensors come back.
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig, occupancy_from_obstructions};
+use crate::state::{
+    OccupancyStatus, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus,
+};
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for the low-pass filtered model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LowPassParams {
+    /// Time constant (seconds) of the low-pass filter.
+    pub tau_secs: f64,
+    pub slope: f64,
+    pub intercept: f64,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for LowPassParams {
+    fn default() -> Self {
+        Self {
+            tau_secs: 30.0,
+            slope: 0.2,
+            intercept: 0.0,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FilterState {
+    filtered: Option<f64>,
+    last_timestamp: Option<SystemTime>,
+    /// Set after a `NoData` cycle so the next valid sample resets the
+    /// filter instead of blending with a now-stale `filtered` value.
+    reset_pending: bool,
+}
+
+/// Estimation model that low-pass filters occupancy before applying the
+/// slope/intercept wait-time formula.
+#[derive(Debug)]
+pub struct LowPassModel {
+    params: LowPassParams,
+    occupancy_config: OccupancyConfig,
+    state: Mutex<FilterState>,
+}
+
+impl LowPassModel {
+    pub fn new(params: LowPassParams, occupancy_config: OccupancyConfig) -> Self {
+        Self {
+            params,
+            occupancy_config,
+            state: Mutex::new(FilterState::default()),
+        }
+    }
+}
+
+impl EstimationModel for LowPassModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let occupancy = occupancy_from_obstructions(obstructions, timestamp);
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some(occupancy_percent) = occupancy.occupancy_percent else {
+            state.reset_pending = true;
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let dt = state
+            .last_timestamp
+            .and_then(|previous| timestamp.duration_since(previous).ok())
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or(0.0);
+
+        let filtered = match state.filtered {
+            Some(previous) if !state.reset_pending && self.params.tau_secs > 0.0 && dt > 0.0 => {
+                let alpha = dt / (self.params.tau_secs + dt);
+                previous + alpha * (occupancy_percent - previous)
+            }
+            _ => occupancy_percent,
+        };
+
+        state.filtered = Some(filtered);
+        state.last_timestamp = Some(timestamp);
+        state.reset_pending = false;
+
+        let mut wait_time = self.params.intercept + self.params.slope * filtered;
+        if let Some(min) = self.params.min_wait_minutes {
+            wait_time = wait_time.max(min as f64);
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            wait_time = wait_time.min(max as f64);
+        }
+
+        let status = match occupancy.status {
+            OccupancyStatus::Degraded => WaitTimeStatus::Degraded,
+            _ => WaitTimeStatus::Ok,
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(wait_time),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn obstruction(obstructed: Option<bool>, timestamp: SystemTime) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id: 1,
+            obstructed,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn first_sample_passes_through_unsmoothed() {
+        let model = LowPassModel::new(LowPassParams::default(), OccupancyConfig::default());
+
+        let estimate = model.compute_wait_time(&[obstruction(Some(true), UNIX_EPOCH)], UNIX_EPOCH);
+
+        assert_eq!(estimate.wait_time_minutes, Some(0.2 * 100.0));
+    }
+
+    #[test]
+    fn subsequent_samples_are_smoothed_toward_new_value() {
+        let model = LowPassModel::new(
+            LowPassParams {
+                tau_secs: 10.0,
+                ..LowPassParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(10);
+
+        let first = model.compute_wait_time(&[obstruction(Some(false), t0)], t0);
+        let second = model.compute_wait_time(&[obstruction(Some(true), t1)], t1);
+
+        assert_eq!(first.wait_time_minutes, Some(0.0));
+        let wait = second.wait_time_minutes.expect("wait time");
+        assert!(wait > 0.0 && wait < 20.0);
+    }
+
+    #[test]
+    fn no_data_cycle_resets_the_filter_instead_of_holding() {
+        let model = LowPassModel::new(
+            LowPassParams {
+                tau_secs: 10.0,
+                ..LowPassParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(10);
+        let t2 = UNIX_EPOCH + Duration::from_secs(20);
+
+        let _ = model.compute_wait_time(&[obstruction(Some(false), t0)], t0);
+        let no_data = model.compute_wait_time(&[obstruction(None, t1)], t1);
+        let recovered = model.compute_wait_time(&[obstruction(Some(true), t2)], t2);
+
+        assert_eq!(no_data.wait_time_minutes, None);
+        assert_eq!(no_data.status, WaitTimeStatus::Degraded);
+        // Filter reset after NoData, so the first valid sample back passes
+        // straight through instead of blending with the stale pre-outage value.
+        assert_eq!(recovered.wait_time_minutes, Some(0.2 * 100.0));
+    }
+
+    #[test]
+    fn degraded_status_propagates_from_occupancy() {
+        let model = LowPassModel::new(LowPassParams::default(), OccupancyConfig::default());
+        let t0 = UNIX_EPOCH;
+
+        let estimate =
+            model.compute_wait_time(&[obstruction(Some(true), t0), obstruction(None, t0)], t0);
+
+        assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+        assert!(estimate.wait_time_minutes.is_some());
+    }
+}
@@ -1,28 +1,45 @@
+use crate::bus::hysteresis::HysteresisDebouncer;
+use crate::bus::median_filter::MedianDeglitcher;
 use crate::bus::readings::read_and_store_distances;
-use crate::bus::xshut::{XshutController, reinitialize_sensor};
+use crate::bus::validator::{DataValidator, DataValidatorConfig};
+use crate::bus::xshut::{RediscoveryConfig, XshutController, discover_and_store_sensors, reinitialize_sensor};
 use crate::error::AppError;
-use crate::sensor::SensorDriverFactory;
+use crate::sensor::{SensorDriverFactory, SensorId};
 use crate::state::{
-    AppState, ReadingStatus, SensorObstruction, SensorReading, WaitTimeErrorCode, WaitTimeEstimate,
-    WaitTimeStatus,
+    AppState, ReadingStatus, SensorObstruction, SensorReading, SensorValidation, WaitTimeErrorCode,
+    WaitTimeEstimate, WaitTimeStatus,
 };
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
-use tracing::warn;
+use tracing::{info, warn};
 
+pub mod deglitch;
+pub mod ewma;
+pub mod fit;
 pub mod linear_v1;
 pub mod linear_v2;
+pub mod low_pass;
 pub mod model;
 pub mod obstruction_count_v1;
+pub mod occupancy_smooth;
+pub mod pid_obstruction;
+pub mod smoothed_obstruction;
 
+use deglitch::{DeglitchModel, DeglitchParams};
+use ewma::{EwmaModel, EwmaParams};
 use linear_v1::{LinearV1Model, LinearV1Params};
 use linear_v2::{LinearV2Model, LinearV2Params};
+use low_pass::{LowPassModel, LowPassParams};
 use model::{EstimationModel, OccupancyConfig};
 use obstruction_count_v1::{ObstructionCountModel, ObstructionCountParams};
+use occupancy_smooth::{OccupancySmoothModel, OccupancySmoothParams};
+use pid_obstruction::{PidObstructionModel, PidObstructionParams};
+use smoothed_obstruction::{SmoothedObstructionModel, SmoothedObstructionParams};
 
 pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
 
@@ -34,6 +51,10 @@ pub fn create_model(
         threshold_mm: config.occupancy_threshold_mm.unwrap_or(1200),
         sensor_min_mm: config.sensor_min_mm.unwrap_or(40),
         sensor_max_mm: config.sensor_max_mm.unwrap_or(4000),
+        distance_median_window: config.distance_median_window.unwrap_or(1),
+        hysteresis_mm: config.occupancy_hysteresis_mm.unwrap_or(0),
+        debounce_n: config.occupancy_debounce_n.unwrap_or(1),
+        debounce_m: config.occupancy_debounce_m.unwrap_or(1),
     };
 
     match config.model.as_str() {
@@ -52,6 +73,33 @@ pub fn create_model(
                 occupancy_config,
             )))
         }
+        "deglitch_v1" => {
+            let params: DeglitchParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(DeglitchModel::new(params, occupancy_config)))
+        }
+        "ewma_v1" => {
+            let params: EwmaParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(EwmaModel::new(params, occupancy_config)))
+        }
+        "occupancy_smooth_v1" => {
+            let params: OccupancySmoothParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(OccupancySmoothModel::new(params, occupancy_config)))
+        }
+        "low_pass_v1" => {
+            let params: LowPassParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(LowPassModel::new(params, occupancy_config)))
+        }
+        "smoothed_obstruction_v1" => {
+            let params: SmoothedObstructionParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(SmoothedObstructionModel::new(
+                params,
+                occupancy_config,
+            )))
+        }
+        "pid_obstruction_v1" => {
+            let params: PidObstructionParams = serde_json::from_value(config.params.clone())?;
+            Ok(Box::new(PidObstructionModel::new(params, occupancy_config)))
+        }
         other => Err(CalibrationError::Invalid(format!("unknown model: {other}"))),
     }
 }
@@ -59,13 +107,26 @@ pub fn create_model(
 pub fn update_obstructions_from_readings(
     state: &Arc<RwLock<AppState>>,
     model: &dyn EstimationModel,
+    validator: &mut DataValidator,
+    deglitcher: &mut MedianDeglitcher,
+    debouncer: &mut HysteresisDebouncer,
 ) -> Result<Vec<SensorObstruction>, AppError> {
-    update_obstructions_from_readings_at(state, model, SystemTime::now())
+    update_obstructions_from_readings_at(
+        state,
+        model,
+        validator,
+        deglitcher,
+        debouncer,
+        SystemTime::now(),
+    )
 }
 
 fn update_obstructions_from_readings_at(
     state: &Arc<RwLock<AppState>>,
     model: &dyn EstimationModel,
+    validator: &mut DataValidator,
+    deglitcher: &mut MedianDeglitcher,
+    debouncer: &mut HysteresisDebouncer,
     _timestamp: SystemTime,
 ) -> Result<Vec<SensorObstruction>, AppError> {
     let readings = {
@@ -73,9 +134,16 @@ fn update_obstructions_from_readings_at(
         guard.readings().to_vec()
     };
 
-    let threshold_mm = model.occupancy_config().threshold_mm;
-    let (obstructions, valid_count, error_count) =
-        obstructions_from_readings(&readings, threshold_mm);
+    let occupancy_config = model.occupancy_config();
+    let validations = validator.validate(&readings, occupancy_config);
+    let distances = deglitcher.filter(&readings);
+    let (obstructions, valid_count, error_count) = obstructions_from_readings(
+        &readings,
+        &distances,
+        &validations,
+        validator.confidence_threshold(),
+        debouncer,
+    );
 
     if valid_count == 0 {
         warn!("No valid sensor readings available for obstruction calculation");
@@ -85,28 +153,22 @@ fn update_obstructions_from_readings_at(
 
     let mut guard = state.write().map_err(|_| AppError::StateLock)?;
     guard.set_obstructions(obstructions.clone())?;
+    guard.set_validations(validations)?;
 
     Ok(obstructions)
 }
 
-pub fn update_wait_time_from_obstructions(
-    state: &Arc<RwLock<AppState>>,
-    model: &dyn EstimationModel,
-) -> Result<WaitTimeEstimate, AppError> {
-    update_wait_time_from_obstructions_at(state, model, SystemTime::now())
-}
-
-fn update_wait_time_from_obstructions_at(
+/// Runs the model against an already-known obstruction snapshot and stores
+/// the result. Used by the estimation worker spawned by
+/// [`spawn_refresh_thread`], which already has the snapshot in hand from the
+/// producer side of the handoff channel.
+fn apply_wait_time_estimate(
     state: &Arc<RwLock<AppState>>,
     model: &dyn EstimationModel,
+    obstructions: &[SensorObstruction],
     timestamp: SystemTime,
 ) -> Result<WaitTimeEstimate, AppError> {
-    let obstructions = {
-        let guard = state.read().map_err(|_| AppError::StateLock)?;
-        guard.obstructions().to_vec()
-    };
-
-    let wait_time = model.compute_wait_time(&obstructions, timestamp);
+    let wait_time = model.compute_wait_time(obstructions, timestamp);
 
     if matches!(wait_time.status, WaitTimeStatus::Degraded) {
         if matches!(wait_time.error_code, Some(WaitTimeErrorCode::NoData)) {
@@ -122,12 +184,24 @@ fn update_wait_time_from_obstructions_at(
     Ok(wait_time)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct CalibrationFile {
     pub model: String,
     pub occupancy_threshold_mm: Option<u16>,
     pub sensor_min_mm: Option<u16>,
     pub sensor_max_mm: Option<u16>,
+    /// Per-sensor sliding-window size for the raw-distance median
+    /// deglitcher applied ahead of the threshold comparison. `None`/`1`
+    /// disables it.
+    pub distance_median_window: Option<usize>,
+    /// Widens the exit threshold for [`crate::bus::hysteresis::HysteresisDebouncer`].
+    /// `None`/`0` disables the dead zone.
+    pub occupancy_hysteresis_mm: Option<u16>,
+    /// Consecutive agreeing readings required to flip the debounced
+    /// occupancy state. `None`/`1` disables debouncing.
+    pub occupancy_debounce_n: Option<u32>,
+    /// Rolling window size `occupancy_debounce_n` is checked against.
+    pub occupancy_debounce_m: Option<u32>,
     pub params: serde_json::Value,
 }
 
@@ -149,28 +223,43 @@ pub fn load_calibration_from_path(
     create_model(&config)
 }
 
-pub fn run_refresh_cycle(
-    state: &Arc<RwLock<AppState>>,
-    model: &dyn EstimationModel,
-) -> Result<(), AppError> {
-    update_obstructions_from_readings(state, model)?;
-    update_wait_time_from_obstructions(state, model)?;
-    Ok(())
-}
-
 fn obstructions_from_readings(
     readings: &[SensorReading],
-    threshold_mm: u16,
+    distances: &[u16],
+    validations: &[SensorValidation],
+    confidence_threshold: f64,
+    debouncer: &mut HysteresisDebouncer,
 ) -> (Vec<SensorObstruction>, u32, u32) {
     let mut valid_count = 0u32;
     let mut error_count = 0u32;
     let mut obstructions = Vec::with_capacity(readings.len());
 
-    for reading in readings {
+    let confidence_by_sensor: HashMap<SensorId, f64> = validations
+        .iter()
+        .map(|validation| (validation.sensor_id, validation.confidence))
+        .collect();
+
+    for (reading, &distance_mm) in readings.iter().zip(distances) {
+        let low_confidence = confidence_by_sensor
+            .get(&reading.sensor_id)
+            .is_some_and(|confidence| *confidence < confidence_threshold);
+
         let obstructed = match &reading.status {
+            ReadingStatus::Ok { .. } if low_confidence => {
+                // Driver reported Ok, but the validator flagged this cycle
+                // (stuck, drifting out of range, or error-dense) - treat it
+                // the same as a reported sensor error so it doesn't skew
+                // occupancy.
+                error_count += 1;
+                None
+            }
             ReadingStatus::Ok { .. } => {
+                // Compared against the deglitcher's median distance, not
+                // the raw reading, so a single-cycle spike doesn't flip
+                // `obstructed`. The debouncer then applies the configured
+                // hysteresis band and N-of-M agreement on top of that.
                 valid_count += 1;
-                Some(reading.distance_mm <= threshold_mm)
+                Some(debouncer.decide(reading.sensor_id, distance_mm))
             }
             ReadingStatus::Error { .. } => {
                 error_count += 1;
@@ -219,8 +308,14 @@ mod tests {
     #[test]
     fn obstructions_use_threshold_and_track_errors() {
         let readings = vec![ok_reading(1, 999), ok_reading(2, 1001), error_reading(3)];
+        let distances: Vec<u16> = readings.iter().map(|reading| reading.distance_mm).collect();
+        let mut debouncer = HysteresisDebouncer::new(&OccupancyConfig {
+            threshold_mm: 1000,
+            ..OccupancyConfig::default()
+        });
 
-        let (obstructions, valid_count, error_count) = obstructions_from_readings(&readings, 1000);
+        let (obstructions, valid_count, error_count) =
+            obstructions_from_readings(&readings, &distances, &[], 0.5, &mut debouncer);
 
         assert_eq!(valid_count, 2);
         assert_eq!(error_count, 1);
@@ -234,13 +329,110 @@ mod tests {
     }
 }
 
+/// A single-slot "latest wins" handoff between the sensor producer and the
+/// estimation worker: pushing always replaces whatever is waiting, so a slow
+/// or timed-out model call never leaves the worker processing a backlog of
+/// stale snapshots once it catches up.
+struct LatestSlot<T> {
+    slot: Mutex<Option<T>>,
+    condvar: Condvar,
+}
+
+impl<T> LatestSlot<T> {
+    fn new() -> Self {
+        Self {
+            slot: Mutex::new(None),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn push(&self, value: T) {
+        let mut guard = self.slot.lock().expect("latest-slot lock poisoned");
+        *guard = Some(value);
+        self.condvar.notify_one();
+    }
+
+    /// Blocks up to `timeout` for a value, returning `None` on timeout so the
+    /// caller can re-check a stop flag instead of blocking forever.
+    fn pop_wait(&self, timeout: Duration) -> Option<T> {
+        let guard = self.slot.lock().expect("latest-slot lock poisoned");
+        let (mut guard, _) = self
+            .condvar
+            .wait_timeout_while(guard, timeout, |value| value.is_none())
+            .expect("latest-slot lock poisoned");
+        guard.take()
+    }
+}
+
+/// Spawn the sensor refresh pipeline as two threads decoupled by a
+/// single-slot "latest wins" handoff: a producer that reads sensors on a
+/// steady `interval` tick and an estimation worker that runs `model` against
+/// the newest obstruction snapshot. This keeps sensor acquisition on a
+/// steady cadence even when a remote model call is slow or timing out -
+/// the producer never blocks on it. Returns `(producer, worker)` join
+/// handles.
 pub fn spawn_refresh_thread<F, X>(
+    sensor_factory: F,
+    xshut_controller: Option<X>,
+    state: Arc<RwLock<AppState>>,
+    interval: Duration,
+    stop: Arc<AtomicBool>,
+    model: Arc<dyn EstimationModel>,
+    rediscovery: Option<RediscoveryConfig>,
+) -> (std::thread::JoinHandle<()>, std::thread::JoinHandle<()>)
+where
+    F: SensorDriverFactory + Send + 'static,
+    X: XshutController + Send + 'static,
+{
+    let obstructions_slot = Arc::new(LatestSlot::<Vec<SensorObstruction>>::new());
+
+    let worker_handle = {
+        let state = Arc::clone(&state);
+        let stop = Arc::clone(&stop);
+        let model = Arc::clone(&model);
+        let obstructions_slot = Arc::clone(&obstructions_slot);
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let obstructions = match obstructions_slot.pop_wait(Duration::from_millis(100)) {
+                    Some(obstructions) => obstructions,
+                    None => continue,
+                };
+
+                if let Err(err) = apply_wait_time_estimate(
+                    &state,
+                    model.as_ref(),
+                    &obstructions,
+                    SystemTime::now(),
+                ) {
+                    warn!(error = %err, "Error running estimation worker cycle");
+                }
+            }
+        })
+    };
+
+    let producer_handle = spawn_sensor_producer(
+        sensor_factory,
+        xshut_controller,
+        state,
+        interval,
+        stop,
+        model,
+        rediscovery,
+        obstructions_slot,
+    );
+
+    (producer_handle, worker_handle)
+}
+
+fn spawn_sensor_producer<F, X>(
     mut sensor_factory: F,
     mut xshut_controller: Option<X>,
     state: Arc<RwLock<AppState>>,
     interval: Duration,
     stop: Arc<AtomicBool>,
     model: Arc<dyn EstimationModel>,
+    rediscovery: Option<RediscoveryConfig>,
+    obstructions_slot: Arc<LatestSlot<Vec<SensorObstruction>>>,
 ) -> std::thread::JoinHandle<()>
 where
     F: SensorDriverFactory + Send + 'static,
@@ -259,10 +451,84 @@ where
         // Track consecutive errors per sensor
         let mut error_counts = std::collections::HashMap::new();
         const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+        let mut validator = DataValidator::new(DataValidatorConfig::default());
+        let mut deglitcher = MedianDeglitcher::new(model.occupancy_config().distance_median_window);
+        let mut debouncer = HysteresisDebouncer::new(model.occupancy_config());
 
         while !stop.load(Ordering::Relaxed) {
             let cycle_start = Instant::now();
 
+            let rediscovery_requested = {
+                let mut guard = state.write().expect("state lock poisoned");
+                guard.take_rediscovery_request()
+            };
+            if rediscovery_requested {
+                match (xshut_controller.as_mut(), rediscovery.as_ref()) {
+                    (Some(xshut), Some(rediscovery)) => match rediscovery.resolve() {
+                        Ok((base_address, configs)) => {
+                            info!(
+                                count = configs.len(),
+                                base_address = format_args!("{base_address:#04x}"),
+                                "Re-running sensor discovery after runtime config change"
+                            );
+                            match discover_and_store_sensors(
+                                xshut,
+                                &mut sensor_factory,
+                                &configs,
+                                base_address,
+                                &state,
+                            ) {
+                                Ok(results) => {
+                                    sensors = results;
+                                    error_counts.clear();
+                                }
+                                Err(err) => {
+                                    warn!(error = %err, "Rediscovery after runtime config change failed")
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            warn!(error = %err, "Failed to resolve runtime config for rediscovery")
+                        }
+                    },
+                    _ => warn!(
+                        "Rediscovery requested but no XSHUT controller or runtime config is available"
+                    ),
+                }
+            }
+
+            let reinit_requests = {
+                let mut guard = state.write().expect("state lock poisoned");
+                guard.take_reinitialize_requests()
+            };
+            if !reinit_requests.is_empty() {
+                if let Some(ref mut xshut) = xshut_controller {
+                    for sensor_id in reinit_requests {
+                        match sensors.iter().find(|s| s.sensor_id == sensor_id) {
+                            Some(sensor_info) => {
+                                match reinitialize_sensor(xshut, &mut sensor_factory, sensor_info) {
+                                    Ok(_) => {
+                                        error_counts.insert(sensor_id, 0);
+                                        info!(
+                                            sensor_id,
+                                            "Forced sensor re-init via control channel succeeded"
+                                        );
+                                    }
+                                    Err(err) => warn!(
+                                        sensor_id,
+                                        error = %err,
+                                        "Forced sensor re-init via control channel failed"
+                                    ),
+                                }
+                            }
+                            None => warn!(sensor_id, "Re-init requested for unknown sensor id"),
+                        }
+                    }
+                } else {
+                    warn!("Sensor re-init requested but no XSHUT controller is available");
+                }
+            }
+
             let readings_result =
                 read_and_store_distances(&mut sensor_factory, &mut sensors, &state, model.as_ref());
 
@@ -325,8 +591,15 @@ where
                 }
             }
 
-            if let Err(e) = run_refresh_cycle(&state, model.as_ref()) {
-                warn!("Error running estimation cycle: {}", e);
+            match update_obstructions_from_readings(
+                &state,
+                model.as_ref(),
+                &mut validator,
+                &mut deglitcher,
+                &mut debouncer,
+            ) {
+                Ok(obstructions) => obstructions_slot.push(obstructions),
+                Err(e) => warn!("Error updating obstructions: {}", e),
             }
 
             sleep_with_stop(interval, &stop, cycle_start);
@@ -15,6 +15,21 @@ pub struct OccupancyConfig {
     pub sensor_min_mm: u16,
     /// Maximum valid sensor reading (hardware limit).
     pub sensor_max_mm: u16,
+    /// Per-sensor sliding-window size for
+    /// [`crate::bus::median_filter::MedianDeglitcher`]; `1` disables median
+    /// filtering (the raw distance is always used).
+    pub distance_median_window: usize,
+    /// Widens the exit threshold to `threshold_mm + hysteresis_mm` for
+    /// [`crate::bus::hysteresis::HysteresisDebouncer`]; `0` disables the
+    /// dead zone (enter and exit thresholds coincide).
+    pub hysteresis_mm: u16,
+    /// Consecutive agreeing readings required to flip the debounced
+    /// occupancy state in [`crate::bus::hysteresis::HysteresisDebouncer`];
+    /// `1` disables debouncing.
+    pub debounce_n: u32,
+    /// Size of the rolling window `debounce_n` is checked against; widened
+    /// to at least `debounce_n` if configured smaller.
+    pub debounce_m: u32,
 }
 
 impl Default for OccupancyConfig {
@@ -23,6 +38,10 @@ impl Default for OccupancyConfig {
             threshold_mm: 1200,
             sensor_min_mm: 10,
             sensor_max_mm: 4000,
+            distance_median_window: 1,
+            hysteresis_mm: 0,
+            debounce_n: 1,
+            debounce_m: 1,
         }
     }
 }
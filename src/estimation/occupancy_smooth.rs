@@ -0,0 +1,354 @@
+//! Median deglitch + EMA + hysteresis smoothing stage for occupancy.
+//!
+//! `LinearV2Model` feeds raw per-frame `OccupancyReading`s straight into its
+//! interpolation, so a person passing briefly in front of a sensor jitters
+//! `wait_time_minutes` just as much as a sustained occupancy change does.
+//! [`OccupancySmoother`] sits between `occupancy_from_obstructions` and a
+//! model's wait-time formula: it keeps a ring buffer of the last
+//! `window_len` occupancy samples, takes their **median** to reject
+//! single-frame spikes, low-pass filters the median with an EMA
+//! (`s_t = alpha * median + (1 - alpha) * s_{t-1}`), and finally only lets
+//! the *reported* value move once the EMA has sat more than
+//! `hysteresis_margin` away from it for `dwell_frames` consecutive samples -
+//! so the reported occupancy itself doesn't chatter back and forth near a
+//! boundary. [`OccupancySmoothModel`] wraps the smoother with the same
+//! slope/intercept formula [`crate::estimation::deglitch::DeglitchModel`]
+//! and [`crate::estimation::ewma::EwmaModel`] use, so existing calibration
+//! files only need a new `model`/`params` pair to pick it up.
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig, occupancy_from_obstructions};
+use crate::state::{
+    OccupancyReading, OccupancyStatus, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate,
+    WaitTimeStatus,
+};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for [`OccupancySmoother`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct OccupancySmootherConfig {
+    /// Number of occupancy samples kept for the median deglitch filter.
+    pub window_len: usize,
+    /// EMA coefficient applied to the window's median.
+    pub alpha: f64,
+    /// Minimum occupancy-percent deviation from the last reported value the
+    /// EMA must sustain before the reported value is allowed to move.
+    pub hysteresis_margin: f64,
+    /// Consecutive samples the EMA must stay past `hysteresis_margin` before
+    /// the reported occupancy crosses to the new value.
+    pub dwell_frames: u32,
+}
+
+impl Default for OccupancySmootherConfig {
+    fn default() -> Self {
+        Self {
+            window_len: 5,
+            alpha: 0.3,
+            hysteresis_margin: 5.0,
+            dwell_frames: 3,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SmootherState {
+    window: VecDeque<Option<f64>>,
+    ema: Option<f64>,
+    reported: Option<f64>,
+    dwell_count: u32,
+}
+
+/// Reusable median + EMA + hysteresis filter for a stream of
+/// `OccupancyReading`s, independent of any particular wait-time formula.
+#[derive(Debug)]
+pub struct OccupancySmoother {
+    config: OccupancySmootherConfig,
+    state: Mutex<SmootherState>,
+}
+
+impl OccupancySmoother {
+    pub fn new(config: OccupancySmootherConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(SmootherState::default()),
+        }
+    }
+
+    /// Push one raw `OccupancyReading` through the filter and return the
+    /// smoothed reading.
+    pub fn smooth(&self, raw: OccupancyReading) -> OccupancyReading {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        state.window.push_back(raw.occupancy_percent);
+        while state.window.len() > self.config.window_len.max(1) {
+            state.window.pop_front();
+        }
+
+        let no_data_count = state.window.iter().filter(|sample| sample.is_none()).count();
+        let degraded = no_data_count * 2 > state.window.len();
+
+        let valid: Vec<f64> = state.window.iter().filter_map(|sample| *sample).collect();
+        let Some(median) = median(&valid) else {
+            return OccupancyReading {
+                occupancy_percent: None,
+                timestamp: raw.timestamp,
+                status: OccupancyStatus::NoData,
+            };
+        };
+
+        let ema = match state.ema {
+            Some(previous) => self.config.alpha * median + (1.0 - self.config.alpha) * previous,
+            None => median,
+        };
+        state.ema = Some(ema);
+
+        let reported = match state.reported {
+            None => {
+                state.dwell_count = 0;
+                ema
+            }
+            Some(reported) if (ema - reported).abs() > self.config.hysteresis_margin => {
+                state.dwell_count += 1;
+                if state.dwell_count >= self.config.dwell_frames.max(1) {
+                    state.dwell_count = 0;
+                    ema
+                } else {
+                    reported
+                }
+            }
+            Some(reported) => {
+                state.dwell_count = 0;
+                reported
+            }
+        };
+        state.reported = Some(reported);
+
+        OccupancyReading {
+            occupancy_percent: Some(reported),
+            timestamp: raw.timestamp,
+            status: if degraded {
+                OccupancyStatus::Degraded
+            } else {
+                OccupancyStatus::Ok
+            },
+        }
+    }
+}
+
+/// Median of `values`; `None` on an empty slice. Even-length slices average
+/// the two middle elements.
+fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+/// Parameters for [`OccupancySmoothModel`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct OccupancySmoothParams {
+    pub window_len: usize,
+    pub alpha: f64,
+    pub hysteresis_margin: f64,
+    pub dwell_frames: u32,
+    pub slope: f64,
+    pub intercept: f64,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for OccupancySmoothParams {
+    fn default() -> Self {
+        let smoother = OccupancySmootherConfig::default();
+        Self {
+            window_len: smoother.window_len,
+            alpha: smoother.alpha,
+            hysteresis_margin: smoother.hysteresis_margin,
+            dwell_frames: smoother.dwell_frames,
+            slope: 0.2,
+            intercept: 0.0,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+/// Estimation model that runs occupancy through [`OccupancySmoother`] before
+/// applying the slope/intercept wait-time formula.
+#[derive(Debug)]
+pub struct OccupancySmoothModel {
+    params: OccupancySmoothParams,
+    occupancy_config: OccupancyConfig,
+    smoother: OccupancySmoother,
+}
+
+impl OccupancySmoothModel {
+    pub fn new(params: OccupancySmoothParams, occupancy_config: OccupancyConfig) -> Self {
+        let smoother = OccupancySmoother::new(OccupancySmootherConfig {
+            window_len: params.window_len,
+            alpha: params.alpha,
+            hysteresis_margin: params.hysteresis_margin,
+            dwell_frames: params.dwell_frames,
+        });
+        Self {
+            params,
+            occupancy_config,
+            smoother,
+        }
+    }
+}
+
+impl EstimationModel for OccupancySmoothModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let raw = occupancy_from_obstructions(obstructions, timestamp);
+        // This cycle's own data decides NoData/Degraded; the window's older
+        // samples only shape the smoothed value.
+        let raw_has_data = raw.occupancy_percent.is_some();
+        let raw_degraded = matches!(raw.status, OccupancyStatus::Degraded);
+        let occupancy = self.smoother.smooth(raw);
+
+        let Some(occupancy_percent) = occupancy.occupancy_percent.filter(|_| raw_has_data) else {
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let mut wait_time = self.params.intercept + self.params.slope * occupancy_percent;
+        if let Some(min) = self.params.min_wait_minutes {
+            wait_time = wait_time.max(min as f64);
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            wait_time = wait_time.min(max as f64);
+        }
+
+        let status = match occupancy.status {
+            OccupancyStatus::Degraded => WaitTimeStatus::Degraded,
+            _ if raw_degraded => WaitTimeStatus::Degraded,
+            _ => WaitTimeStatus::Ok,
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(wait_time),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn reading(occupancy_percent: Option<f64>, timestamp: SystemTime) -> OccupancyReading {
+        OccupancyReading {
+            occupancy_percent,
+            timestamp,
+            status: if occupancy_percent.is_some() {
+                OccupancyStatus::Ok
+            } else {
+                OccupancyStatus::NoData
+            },
+        }
+    }
+
+    #[test]
+    fn single_frame_spike_is_rejected_by_the_median() {
+        let smoother = OccupancySmoother::new(OccupancySmootherConfig {
+            window_len: 5,
+            alpha: 1.0,
+            hysteresis_margin: 0.0,
+            dwell_frames: 1,
+        });
+
+        for _ in 0..4 {
+            let _ = smoother.smooth(reading(Some(0.0), UNIX_EPOCH));
+        }
+        let result = smoother.smooth(reading(Some(100.0), UNIX_EPOCH));
+
+        assert_eq!(result.occupancy_percent, Some(0.0));
+    }
+
+    #[test]
+    fn reported_value_holds_until_dwell_frames_elapse() {
+        let smoother = OccupancySmoother::new(OccupancySmootherConfig {
+            window_len: 1,
+            alpha: 1.0,
+            hysteresis_margin: 5.0,
+            dwell_frames: 3,
+        });
+
+        let first = smoother.smooth(reading(Some(0.0), UNIX_EPOCH));
+        let second = smoother.smooth(reading(Some(100.0), UNIX_EPOCH));
+        let third = smoother.smooth(reading(Some(100.0), UNIX_EPOCH));
+        let fourth = smoother.smooth(reading(Some(100.0), UNIX_EPOCH));
+
+        assert_eq!(first.occupancy_percent, Some(0.0));
+        assert_eq!(second.occupancy_percent, Some(0.0));
+        assert_eq!(third.occupancy_percent, Some(0.0));
+        assert_eq!(fourth.occupancy_percent, Some(100.0));
+    }
+
+    #[test]
+    fn status_degrades_once_more_than_half_the_window_is_no_data() {
+        let smoother = OccupancySmoother::new(OccupancySmootherConfig {
+            window_len: 3,
+            alpha: 1.0,
+            hysteresis_margin: 0.0,
+            dwell_frames: 1,
+        });
+
+        let _ = smoother.smooth(reading(Some(20.0), UNIX_EPOCH));
+        let _ = smoother.smooth(reading(None, UNIX_EPOCH));
+        let result = smoother.smooth(reading(None, UNIX_EPOCH));
+
+        assert_eq!(result.status, OccupancyStatus::Degraded);
+    }
+
+    #[test]
+    fn model_degrades_wait_time_when_fully_no_data() {
+        let model = OccupancySmoothModel::new(
+            OccupancySmoothParams::default(),
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(1);
+
+        let obstruction = |obstructed: Option<bool>, timestamp: SystemTime| SensorObstruction {
+            sensor_id: 1,
+            obstructed,
+            timestamp,
+        };
+
+        let _ = model.compute_wait_time(&[obstruction(Some(true), t0)], t0);
+        let estimate = model.compute_wait_time(&[obstruction(None, t1)], t1);
+
+        assert_eq!(estimate.wait_time_minutes, None);
+        assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+        assert_eq!(estimate.error_code, Some(WaitTimeErrorCode::NoData));
+    }
+}
@@ -0,0 +1,287 @@
+//! PID-feedback obstruction-count estimation model.
+//!
+//! `SmoothedObstructionModel` blends the raw estimate with an EMA, which
+//! reacts the same way regardless of how far the output has drifted from
+//! the raw value. This model instead treats the published wait time as a
+//! controlled variable driven toward the raw obstruction-count estimate by
+//! a PID loop, so it responds quickly to a genuine load change (large
+//! error) while still damping the noise from momentary sensor flicker
+//! (small error).
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig};
+use crate::state::{SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus};
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for the PID obstruction-count model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PidObstructionParams {
+    pub base_minutes: f64,
+    pub per_obstruction_minutes: f64,
+    pub kp: f64,
+    #[serde(default)]
+    pub ki: f64,
+    #[serde(default)]
+    pub kd: f64,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for PidObstructionParams {
+    fn default() -> Self {
+        Self {
+            base_minutes: 0.0,
+            per_obstruction_minutes: 2.0,
+            kp: 1.0,
+            ki: 0.0,
+            kd: 0.0,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ControllerState {
+    output: f64,
+    integral: f64,
+    prev_error: f64,
+    last_timestamp: Option<SystemTime>,
+}
+
+/// Estimation model that drives the published wait time toward the raw
+/// obstruction-count estimate with a PID controller.
+#[derive(Debug)]
+pub struct PidObstructionModel {
+    params: PidObstructionParams,
+    occupancy_config: OccupancyConfig,
+    state: Mutex<ControllerState>,
+}
+
+impl PidObstructionModel {
+    pub fn new(params: PidObstructionParams, occupancy_config: OccupancyConfig) -> Self {
+        Self {
+            params,
+            occupancy_config,
+            state: Mutex::new(ControllerState::default()),
+        }
+    }
+
+    /// Same raw instantaneous estimate as `ObstructionCountModel`, unclamped.
+    fn raw_wait_time(&self, obstructions: &[SensorObstruction]) -> Option<(f64, u32)> {
+        let mut valid_count = 0u32;
+        let mut obstructed_count = 0u32;
+        let mut error_count = 0u32;
+
+        for obstruction in obstructions {
+            match obstruction.obstructed {
+                Some(true) => {
+                    valid_count += 1;
+                    obstructed_count += 1;
+                }
+                Some(false) => {
+                    valid_count += 1;
+                }
+                None => {
+                    error_count += 1;
+                }
+            }
+        }
+
+        if valid_count == 0 {
+            return None;
+        }
+
+        let raw = self.params.base_minutes
+            + (obstructed_count as f64 * self.params.per_obstruction_minutes);
+        Some((raw, error_count))
+    }
+}
+
+impl EstimationModel for PidObstructionModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some((raw, error_count)) = self.raw_wait_time(obstructions) else {
+            // Reset to neutral so a stale output/integral doesn't persist
+            // across the outage.
+            *state = ControllerState::default();
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let dt = state
+            .last_timestamp
+            .and_then(|previous| timestamp.duration_since(previous).ok())
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or(0.0);
+
+        let error = raw - state.output;
+        let derivative = if dt > 0.0 { (error - state.prev_error) / dt } else { 0.0 };
+        let candidate_integral = state.integral + error * dt;
+
+        let mut output = state.output
+            + self.params.kp * error
+            + self.params.ki * candidate_integral
+            + self.params.kd * derivative;
+
+        let mut integral = candidate_integral;
+
+        if let Some(min) = self.params.min_wait_minutes {
+            let min = min as f64;
+            if output < min {
+                output = min;
+                // Anti-windup: don't keep accumulating integral in the
+                // direction that's already saturating the output.
+                if error < 0.0 {
+                    integral = state.integral;
+                }
+            }
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            let max = max as f64;
+            if output > max {
+                output = max;
+                if error > 0.0 {
+                    integral = state.integral;
+                }
+            }
+        }
+
+        state.output = output;
+        state.integral = integral;
+        state.prev_error = error;
+        state.last_timestamp = Some(timestamp);
+
+        let status = if error_count > 0 {
+            WaitTimeStatus::Degraded
+        } else {
+            WaitTimeStatus::Ok
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(output),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn obstruction(sensor_id: u32, obstructed: Option<bool>, timestamp: SystemTime) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id,
+            obstructed,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn proportional_only_tracks_a_fraction_of_the_error() {
+        let model = PidObstructionModel::new(
+            PidObstructionParams {
+                kp: 0.5,
+                ..PidObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        // output starts at 0, raw is 2.0, kp=0.5 -> output moves to 1.0.
+        let estimate =
+            model.compute_wait_time(&[obstruction(1, Some(true), UNIX_EPOCH)], UNIX_EPOCH);
+
+        assert_eq!(estimate.wait_time_minutes, Some(1.0));
+    }
+
+    #[test]
+    fn integral_term_accumulates_over_time() {
+        let model = PidObstructionModel::new(
+            PidObstructionParams {
+                kp: 0.0,
+                ki: 0.1,
+                ..PidObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(10);
+
+        let _ = model.compute_wait_time(&[obstruction(1, Some(true), t0)], t0);
+        let second = model.compute_wait_time(&[obstruction(1, Some(true), t1)], t1);
+
+        // error stays 2.0 (output hasn't moved with kp=0), integral = 2.0*10=20, ki=0.1 -> +2.0
+        assert_eq!(second.wait_time_minutes, Some(2.0));
+    }
+
+    #[test]
+    fn no_data_resets_controller_to_neutral() {
+        let model = PidObstructionModel::new(
+            PidObstructionParams {
+                kp: 1.0,
+                ..PidObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(5);
+        let t2 = UNIX_EPOCH + Duration::from_secs(10);
+
+        let _ = model.compute_wait_time(&[obstruction(1, Some(true), t0)], t0);
+        let no_data = model.compute_wait_time(&[obstruction(1, None, t1)], t1);
+        let recovered = model.compute_wait_time(&[obstruction(1, Some(true), t2)], t2);
+
+        assert_eq!(no_data.wait_time_minutes, None);
+        assert_eq!(no_data.status, WaitTimeStatus::Degraded);
+        // Controller reset, so this behaves like the first-ever sample again.
+        assert_eq!(recovered.wait_time_minutes, Some(2.0));
+    }
+
+    #[test]
+    fn anti_windup_stops_integral_growing_while_pinned_at_max() {
+        let model = PidObstructionModel::new(
+            PidObstructionParams {
+                kp: 0.0,
+                ki: 1.0,
+                per_obstruction_minutes: 100.0,
+                max_wait_minutes: Some(10),
+                ..PidObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(10);
+        let t2 = UNIX_EPOCH + Duration::from_secs(20);
+
+        let first = model.compute_wait_time(&[obstruction(1, Some(true), t0)], t0);
+        let second = model.compute_wait_time(&[obstruction(1, Some(true), t1)], t1);
+        let third = model.compute_wait_time(&[obstruction(1, Some(true), t2)], t2);
+
+        // First sample has no elapsed dt yet, so nothing has accumulated.
+        assert_eq!(first.wait_time_minutes, Some(0.0));
+        // From here the integral would run away past `max_wait_minutes`
+        // without anti-windup; it should stay pinned instead.
+        assert_eq!(second.wait_time_minutes, Some(10.0));
+        assert_eq!(third.wait_time_minutes, Some(10.0));
+    }
+}
@@ -1,16 +1,125 @@
 use crate::estimation::model::{EstimationModel, OccupancyConfig};
 use crate::state::{SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::io::{Read, Write};
+use std::io::{BufRead, Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
-use std::time::{Duration, SystemTime};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use tracing::warn;
 
 const API_VERSION: &str = "1.0";
 
+/// Exponential backoff policy for transient `RemoteError`s (`Connect`, `Io`,
+/// and 5xx `Http`). Delay is `base_delay * 2^attempt`, capped at `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Consecutive-failure threshold and cooldown for the circuit breaker
+/// guarding remote model calls.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CircuitState {
+    Closed,
+    /// Skipping the network until `opened_at.elapsed() >= cooldown`.
+    Open { opened_at: Instant },
+    /// Cooldown elapsed; the next call is a probe that closes or re-opens
+    /// the circuit depending on its outcome.
+    HalfOpen,
+}
+
+/// Trips after `failure_threshold` consecutive remote-call failures,
+/// skipping the network entirely for `cooldown` before allowing a single
+/// half-open probe through.
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    consecutive_failures: AtomicU32,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            consecutive_failures: AtomicU32::new(0),
+            state: Mutex::new(CircuitState::Closed),
+        }
+    }
+
+    /// Returns `true` if a network call should be attempted: the circuit is
+    /// closed, half-open (probing), or its cooldown has just elapsed.
+    fn allow_call(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        match *state {
+            CircuitState::Closed | CircuitState::HalfOpen => true,
+            CircuitState::Open { opened_at } => {
+                if opened_at.elapsed() >= self.config.cooldown {
+                    *state = CircuitState::HalfOpen;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        *state = CircuitState::Closed;
+    }
+
+    fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        match *state {
+            CircuitState::HalfOpen => *state = CircuitState::Open {
+                opened_at: Instant::now(),
+            },
+            CircuitState::Closed if failures >= self.config.failure_threshold => {
+                *state = CircuitState::Open {
+                    opened_at: Instant::now(),
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
 pub struct RemoteModel {
     endpoint: String,
     timeout: Duration,
@@ -18,6 +127,12 @@ pub struct RemoteModel {
     params: serde_json::Value,
     occupancy_config: OccupancyConfig,
     fallback_model: Option<Box<dyn EstimationModel>>,
+    retry: RetryConfig,
+    circuit_breaker: CircuitBreaker,
+    /// Reused across calls to avoid a TCP (and TLS) handshake every refresh
+    /// tick; cleared and transparently reconnected whenever a reuse attempt
+    /// fails (stale keep-alive socket, server-initiated close, ...).
+    connection: Mutex<Option<std::io::BufReader<Transport>>>,
 }
 
 impl RemoteModel {
@@ -28,6 +143,29 @@ impl RemoteModel {
         params: serde_json::Value,
         occupancy_config: OccupancyConfig,
         fallback_model: Option<Box<dyn EstimationModel>>,
+    ) -> Self {
+        Self::with_resilience(
+            endpoint,
+            timeout,
+            model_id,
+            params,
+            occupancy_config,
+            fallback_model,
+            RetryConfig::default(),
+            CircuitBreakerConfig::default(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_resilience(
+        endpoint: String,
+        timeout: Duration,
+        model_id: String,
+        params: serde_json::Value,
+        occupancy_config: OccupancyConfig,
+        fallback_model: Option<Box<dyn EstimationModel>>,
+        retry: RetryConfig,
+        circuit_breaker: CircuitBreakerConfig,
     ) -> Self {
         Self {
             endpoint,
@@ -36,6 +174,9 @@ impl RemoteModel {
             params,
             occupancy_config,
             fallback_model,
+            retry,
+            circuit_breaker: CircuitBreaker::new(circuit_breaker),
+            connection: Mutex::new(None),
         }
     }
 
@@ -44,6 +185,10 @@ impl RemoteModel {
         obstructions: &[SensorObstruction],
         timestamp: SystemTime,
     ) -> Result<PredictResponse, RemoteError> {
+        if !self.circuit_breaker.allow_call() {
+            return Err(RemoteError::CircuitOpen);
+        }
+
         let request = PredictRequest::new(
             &self.model_id,
             &self.params,
@@ -51,10 +196,79 @@ impl RemoteModel {
             timestamp,
         )?;
         let payload = serde_json::to_string(&request).map_err(RemoteError::Json)?;
-        let response_body = send_http_json(&self.endpoint, &payload, self.timeout)?;
-        let response: PredictResponse = serde_json::from_str(&response_body).map_err(RemoteError::Json)?;
+
+        let mut attempt = 0;
+        let response_body = loop {
+            match self.send_request(&payload) {
+                Ok(body) => {
+                    self.circuit_breaker.record_success();
+                    break body;
+                }
+                Err(err) if is_retryable(&err) && attempt < self.retry.max_retries => {
+                    let delay = backoff_delay(&self.retry, attempt);
+                    warn!(
+                        attempt,
+                        error = %err,
+                        delay_ms = delay.as_millis(),
+                        "Retrying remote model call after transient failure"
+                    );
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                Err(err) => {
+                    self.circuit_breaker.record_failure();
+                    return Err(err);
+                }
+            }
+        };
+
+        let response: PredictResponse =
+            serde_json::from_str(&response_body).map_err(RemoteError::Json)?;
         Ok(response)
     }
+
+    /// Sends `body` over the persistent keep-alive connection, transparently
+    /// reconnecting if the cached socket was closed or errored since the
+    /// last request. Returns the response body on a 2xx status, or
+    /// `RemoteError::Http` otherwise.
+    fn send_request(&self, body: &str) -> Result<String, RemoteError> {
+        let parsed = parse_http_url(&self.endpoint)?;
+        let request = build_request(&parsed, body);
+
+        let mut guard = self.connection.lock().unwrap_or_else(|p| p.into_inner());
+
+        let response = match guard
+            .as_mut()
+            .and_then(|reader| exchange_http(reader, &request).ok())
+        {
+            Some(response) => response,
+            None => {
+                *guard = None;
+                let (reader, response) = fresh_connection(&parsed, self.timeout, &request)?;
+                *guard = Some(reader);
+                response
+            }
+        };
+
+        if response.status_code >= 400 {
+            return Err(RemoteError::Http(response.status_code, response.body));
+        }
+
+        Ok(response.body)
+    }
+}
+
+/// Retryable failures are ones where the request may not have reached (or
+/// been handled by) the server: connection/IO errors and 5xx responses.
+/// JSON/URL errors are deterministic and retrying them cannot help.
+fn is_retryable(err: &RemoteError) -> bool {
+    matches!(err, RemoteError::Connect(_) | RemoteError::Io(_))
+        || matches!(err, RemoteError::Http(code, _) if *code >= 500)
+}
+
+fn backoff_delay(retry: &RetryConfig, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt);
+    retry.base_delay.saturating_mul(factor).min(retry.max_delay)
 }
 
 impl fmt::Debug for RemoteModel {
@@ -176,9 +390,11 @@ enum RemoteError {
     Dns(String),
     Connect(std::io::Error),
     Io(std::io::Error),
+    Tls(String),
     Http(u16, String),
     Json(serde_json::Error),
     Timestamp(time::error::Format),
+    CircuitOpen,
 }
 
 impl fmt::Display for RemoteError {
@@ -188,11 +404,13 @@ impl fmt::Display for RemoteError {
             RemoteError::Dns(msg) => write!(f, "dns error: {msg}"),
             RemoteError::Connect(err) => write!(f, "connect error: {err}"),
             RemoteError::Io(err) => write!(f, "io error: {err}"),
+            RemoteError::Tls(msg) => write!(f, "tls error: {msg}"),
             RemoteError::Http(code, body) => {
                 write!(f, "http status {code} ({})", body.trim())
             }
             RemoteError::Json(err) => write!(f, "json error: {err}"),
             RemoteError::Timestamp(err) => write!(f, "timestamp error: {err}"),
+            RemoteError::CircuitOpen => write!(f, "circuit breaker open, skipping remote call"),
         }
     }
 }
@@ -204,16 +422,29 @@ fn format_timestamp(timestamp: SystemTime) -> Result<String, RemoteError> {
         .map_err(RemoteError::Timestamp)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scheme {
+    Http,
+    Https,
+}
+
 struct ParsedUrl {
+    scheme: Scheme,
     host: String,
     port: u16,
     path: String,
 }
 
 fn parse_http_url(endpoint: &str) -> Result<ParsedUrl, RemoteError> {
-    let trimmed = endpoint
-        .strip_prefix("http://")
-        .ok_or_else(|| RemoteError::InvalidUrl("only http:// supported".to_string()))?;
+    let (scheme, default_port, trimmed) = if let Some(rest) = endpoint.strip_prefix("https://") {
+        (Scheme::Https, 443, rest)
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        (Scheme::Http, 80, rest)
+    } else {
+        return Err(RemoteError::InvalidUrl(
+            "only http:// or https:// supported".to_string(),
+        ));
+    };
 
     let mut parts = trimmed.splitn(2, '/');
     let host_port = parts
@@ -234,25 +465,73 @@ fn parse_http_url(endpoint: &str) -> Result<ParsedUrl, RemoteError> {
         Some(port_str) if !port_str.is_empty() => port_str
             .parse::<u16>()
             .map_err(|_| RemoteError::InvalidUrl("invalid port".to_string()))?,
-        _ => 80,
+        _ => default_port,
     };
 
     Ok(ParsedUrl {
+        scheme,
         host: host.to_string(),
         port,
         path,
     })
 }
 
-fn send_http_json(endpoint: &str, body: &str, timeout: Duration) -> Result<String, RemoteError> {
-    let parsed = parse_http_url(endpoint)?;
+/// A byte transport to the remote model endpoint: either a plaintext socket
+/// or one wrapped in a TLS session. `Transport` itself stays persistent and
+/// reusable across requests - only `connect_transport` opens a new one.
+enum Transport {
+    Plain(TcpStream),
+    Tls(StreamOwned<ClientConnection, TcpStream>),
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Transport::Plain(stream) => stream.read(buf),
+            Transport::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Transport::Plain(stream) => stream.write(buf),
+            Transport::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Transport::Plain(stream) => stream.flush(),
+            Transport::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+struct HttpResponse {
+    status_code: u16,
+    body: String,
+}
+
+fn build_request(parsed: &ParsedUrl, body: &str) -> String {
+    format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: keep-alive\r\n\r\n{}",
+        parsed.path,
+        parsed.host,
+        body.as_bytes().len(),
+        body
+    )
+}
+
+fn connect_transport(parsed: &ParsedUrl, timeout: Duration) -> Result<Transport, RemoteError> {
     let addr = (parsed.host.as_str(), parsed.port)
         .to_socket_addrs()
         .map_err(|err| RemoteError::Dns(err.to_string()))?
         .next()
         .ok_or_else(|| RemoteError::Dns("no addresses resolved".to_string()))?;
 
-    let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(RemoteError::Connect)?;
+    let stream = TcpStream::connect_timeout(&addr, timeout).map_err(RemoteError::Connect)?;
     stream
         .set_read_timeout(Some(timeout))
         .map_err(RemoteError::Io)?;
@@ -260,28 +539,57 @@ fn send_http_json(endpoint: &str, body: &str, timeout: Duration) -> Result<Strin
         .set_write_timeout(Some(timeout))
         .map_err(RemoteError::Io)?;
 
-    let request = format!(
-        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-        parsed.path,
-        parsed.host,
-        body.as_bytes().len(),
-        body
-    );
+    match parsed.scheme {
+        Scheme::Http => Ok(Transport::Plain(stream)),
+        Scheme::Https => Ok(Transport::Tls(connect_tls(stream, &parsed.host)?)),
+    }
+}
 
-    stream
+/// Opens a fresh transport and exchanges `request` over it, handing back the
+/// buffered reader so the caller can cache it for the next request.
+fn fresh_connection(
+    parsed: &ParsedUrl,
+    timeout: Duration,
+    request: &str,
+) -> Result<(std::io::BufReader<Transport>, HttpResponse), RemoteError> {
+    let transport = connect_transport(parsed, timeout)?;
+    let mut reader = std::io::BufReader::new(transport);
+    let response = exchange_http(&mut reader, request)?;
+    Ok((reader, response))
+}
+
+/// Writes `request` and reads exactly one HTTP/1.1 response off `reader`:
+/// the status line and headers, then the body per `Content-Length` or
+/// `Transfer-Encoding: chunked`. Leaves the connection positioned to read
+/// the next response, so `reader` can be reused for a subsequent request.
+fn exchange_http(
+    reader: &mut std::io::BufReader<Transport>,
+    request: &str,
+) -> Result<HttpResponse, RemoteError> {
+    reader
+        .get_mut()
         .write_all(request.as_bytes())
         .map_err(RemoteError::Io)?;
 
-    let mut response = String::new();
-    stream.read_to_string(&mut response).map_err(RemoteError::Io)?;
-
-    let (headers, body) = response
-        .split_once("\r\n\r\n")
-        .ok_or_else(|| RemoteError::Http(0, "invalid http response".to_string()))?;
+    let mut header_lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line).map_err(RemoteError::Io)?;
+        if read == 0 {
+            return Err(RemoteError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "connection closed before response headers completed",
+            )));
+        }
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed.is_empty() {
+            break;
+        }
+        header_lines.push(trimmed.to_string());
+    }
 
-    let status_line = headers
-        .lines()
-        .next()
+    let status_line = header_lines
+        .first()
         .ok_or_else(|| RemoteError::Http(0, "missing status line".to_string()))?;
     let status_code = status_line
         .split_whitespace()
@@ -290,9 +598,90 @@ fn send_http_json(endpoint: &str, body: &str, timeout: Duration) -> Result<Strin
         .parse::<u16>()
         .map_err(|_| RemoteError::Http(0, "invalid status code".to_string()))?;
 
-    if status_code >= 400 {
-        return Err(RemoteError::Http(status_code, body.to_string()));
+    let chunked = header_lines.iter().any(|line| {
+        line.split_once(':')
+            .map(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("transfer-encoding")
+                    && value.to_ascii_lowercase().contains("chunked")
+            })
+            .unwrap_or(false)
+    });
+    let content_length = header_lines.iter().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            value.trim().parse::<usize>().ok()
+        } else {
+            None
+        }
+    });
+
+    let body = if chunked {
+        read_chunked_body(reader)?
+    } else if let Some(len) = content_length {
+        let mut buf = vec![0u8; len];
+        reader.read_exact(&mut buf).map_err(RemoteError::Io)?;
+        String::from_utf8_lossy(&buf).into_owned()
+    } else {
+        String::new()
+    };
+
+    Ok(HttpResponse { status_code, body })
+}
+
+/// Decodes a `Transfer-Encoding: chunked` body: each chunk is a hex size
+/// line, that many bytes, a trailing CRLF, repeating until a zero-size
+/// chunk terminates the stream (optionally followed by trailer headers).
+fn read_chunked_body(reader: &mut std::io::BufReader<Transport>) -> Result<String, RemoteError> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line).map_err(RemoteError::Io)?;
+        let size_str = size_line.trim().split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_str, 16)
+            .map_err(|_| RemoteError::Http(0, format!("invalid chunk size: {size_str}")))?;
+
+        if size == 0 {
+            loop {
+                let mut trailer = String::new();
+                let read = reader.read_line(&mut trailer).map_err(RemoteError::Io)?;
+                if read == 0 || trailer.trim().is_empty() {
+                    break;
+                }
+            }
+            break;
+        }
+
+        let mut chunk = vec![0u8; size];
+        reader.read_exact(&mut chunk).map_err(RemoteError::Io)?;
+        body.extend_from_slice(&chunk);
+
+        let mut crlf = [0u8; 2];
+        reader.read_exact(&mut crlf).map_err(RemoteError::Io)?;
     }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Wraps `stream` in a rustls `ClientConnection` validated against the
+/// webpki-roots trust store, mirroring the plaintext path's request framing
+/// once the handshake completes.
+fn connect_tls(
+    stream: TcpStream,
+    host: &str,
+) -> Result<StreamOwned<ClientConnection, TcpStream>, RemoteError> {
+    let config = tls_client_config();
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|err| RemoteError::Tls(format!("invalid server name {host}: {err}")))?;
+    let connection = ClientConnection::new(config, server_name)
+        .map_err(|err| RemoteError::Tls(err.to_string()))?;
+    Ok(StreamOwned::new(connection, stream))
+}
 
-    Ok(body.to_string())
+fn tls_client_config() -> Arc<ClientConfig> {
+    let mut root_store = RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    Arc::new(
+        ClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth(),
+    )
 }
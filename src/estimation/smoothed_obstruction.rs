@@ -0,0 +1,274 @@
+//! Exponentially-smoothed obstruction-count estimation model.
+//!
+//! `ObstructionCountModel` recomputes wait time from scratch every cycle,
+//! so a single flickering sensor makes the published estimate jump around.
+//! This model computes the same raw instantaneous estimate, then blends it
+//! with the previous smoothed value: `smoothed = alpha * raw + (1 - alpha)
+//! * previous_smoothed`. Smaller `alpha` means heavier smoothing.
+
+use crate::estimation::model::{EstimationModel, OccupancyConfig};
+use crate::state::{SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus};
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Parameters for the smoothed obstruction-count model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmoothedObstructionParams {
+    pub base_minutes: f64,
+    pub per_obstruction_minutes: f64,
+    /// Smoothing factor in `(0, 1]`; smaller values weight the previous
+    /// smoothed estimate more heavily.
+    pub alpha: f64,
+    /// If the gap since the last sample exceeds this many seconds, skip the
+    /// blend and snap straight to the raw value instead of smoothing across
+    /// a stale reading.
+    pub max_gap_secs: Option<f64>,
+    pub min_wait_minutes: Option<u32>,
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl Default for SmoothedObstructionParams {
+    fn default() -> Self {
+        Self {
+            base_minutes: 0.0,
+            per_obstruction_minutes: 2.0,
+            alpha: 0.3,
+            max_gap_secs: None,
+            min_wait_minutes: None,
+            max_wait_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FilterState {
+    smoothed: Option<f64>,
+    last_timestamp: Option<SystemTime>,
+}
+
+/// Estimation model that exponentially smooths `ObstructionCountModel`'s raw
+/// estimate between calls.
+#[derive(Debug)]
+pub struct SmoothedObstructionModel {
+    params: SmoothedObstructionParams,
+    occupancy_config: OccupancyConfig,
+    state: Mutex<FilterState>,
+}
+
+impl SmoothedObstructionModel {
+    pub fn new(params: SmoothedObstructionParams, occupancy_config: OccupancyConfig) -> Self {
+        Self {
+            params,
+            occupancy_config,
+            state: Mutex::new(FilterState::default()),
+        }
+    }
+
+    /// Same raw instantaneous estimate as `ObstructionCountModel`, unclamped.
+    fn raw_wait_time(&self, obstructions: &[SensorObstruction]) -> Option<(f64, u32)> {
+        let mut valid_count = 0u32;
+        let mut obstructed_count = 0u32;
+        let mut error_count = 0u32;
+
+        for obstruction in obstructions {
+            match obstruction.obstructed {
+                Some(true) => {
+                    valid_count += 1;
+                    obstructed_count += 1;
+                }
+                Some(false) => {
+                    valid_count += 1;
+                }
+                None => {
+                    error_count += 1;
+                }
+            }
+        }
+
+        if valid_count == 0 {
+            return None;
+        }
+
+        let raw = self.params.base_minutes
+            + (obstructed_count as f64 * self.params.per_obstruction_minutes);
+        Some((raw, error_count))
+    }
+}
+
+impl EstimationModel for SmoothedObstructionModel {
+    fn compute_wait_time(
+        &self,
+        obstructions: &[SensorObstruction],
+        timestamp: SystemTime,
+    ) -> WaitTimeEstimate {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some((raw, error_count)) = self.raw_wait_time(obstructions) else {
+            // Reset so a stale smoothed value doesn't persist across the outage.
+            state.smoothed = None;
+            state.last_timestamp = None;
+            return WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: WaitTimeStatus::Degraded,
+                error_code: Some(WaitTimeErrorCode::NoData),
+            };
+        };
+
+        let dt = state
+            .last_timestamp
+            .and_then(|previous| timestamp.duration_since(previous).ok())
+            .map(|duration| duration.as_secs_f64());
+        let gap_too_large = match (dt, self.params.max_gap_secs) {
+            (Some(dt), Some(max_gap_secs)) => dt > max_gap_secs,
+            _ => false,
+        };
+
+        let mut wait_time = match state.smoothed {
+            Some(previous) if !gap_too_large => {
+                self.params.alpha * raw + (1.0 - self.params.alpha) * previous
+            }
+            _ => raw,
+        };
+
+        state.smoothed = Some(wait_time);
+        state.last_timestamp = Some(timestamp);
+
+        if let Some(min) = self.params.min_wait_minutes {
+            wait_time = wait_time.max(min as f64);
+        }
+        if let Some(max) = self.params.max_wait_minutes {
+            wait_time = wait_time.min(max as f64);
+        }
+
+        let status = if error_count > 0 {
+            WaitTimeStatus::Degraded
+        } else {
+            WaitTimeStatus::Ok
+        };
+
+        WaitTimeEstimate {
+            wait_time_minutes: Some(wait_time),
+            timestamp,
+            status,
+            error_code: None,
+        }
+    }
+
+    fn occupancy_config(&self) -> &OccupancyConfig {
+        &self.occupancy_config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn obstruction(sensor_id: u32, obstructed: Option<bool>, timestamp: SystemTime) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id,
+            obstructed,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn first_sample_passes_through_unsmoothed() {
+        let model = SmoothedObstructionModel::new(
+            SmoothedObstructionParams::default(),
+            OccupancyConfig::default(),
+        );
+
+        let estimate = model.compute_wait_time(
+            &[obstruction(1, Some(true), UNIX_EPOCH)],
+            UNIX_EPOCH,
+        );
+
+        assert_eq!(estimate.wait_time_minutes, Some(2.0));
+    }
+
+    #[test]
+    fn subsequent_samples_blend_toward_raw() {
+        let model = SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                alpha: 0.5,
+                ..SmoothedObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(5);
+
+        let first = model.compute_wait_time(&[obstruction(1, Some(false), t0)], t0);
+        let second = model.compute_wait_time(&[obstruction(1, Some(true), t1)], t1);
+
+        assert_eq!(first.wait_time_minutes, Some(0.0));
+        // raw jumps to 2.0, smoothed halfway between 0.0 (previous) and 2.0 (raw).
+        assert_eq!(second.wait_time_minutes, Some(1.0));
+    }
+
+    #[test]
+    fn no_data_resets_the_filter_instead_of_holding() {
+        let model = SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                alpha: 0.5,
+                ..SmoothedObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(5);
+        let t2 = UNIX_EPOCH + Duration::from_secs(10);
+
+        let _ = model.compute_wait_time(&[obstruction(1, Some(true), t0)], t0);
+        let no_data = model.compute_wait_time(&[obstruction(1, None, t1)], t1);
+        let recovered = model.compute_wait_time(&[obstruction(1, Some(true), t2)], t2);
+
+        assert_eq!(no_data.wait_time_minutes, None);
+        assert_eq!(no_data.status, WaitTimeStatus::Degraded);
+        // Filter reset after NoData, so the first valid sample back passes
+        // straight through instead of blending with the stale pre-outage value.
+        assert_eq!(recovered.wait_time_minutes, Some(2.0));
+    }
+
+    #[test]
+    fn large_gap_snaps_to_raw_instead_of_blending() {
+        let model = SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                alpha: 0.1,
+                max_gap_secs: Some(10.0),
+                ..SmoothedObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(60);
+
+        let _ = model.compute_wait_time(&[obstruction(1, Some(false), t0)], t0);
+        let after_gap = model.compute_wait_time(&[obstruction(1, Some(true), t1)], t1);
+
+        assert_eq!(after_gap.wait_time_minutes, Some(2.0));
+    }
+
+    #[test]
+    fn smoothed_value_is_clamped_to_bounds() {
+        let model = SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                per_obstruction_minutes: 100.0,
+                alpha: 1.0,
+                max_wait_minutes: Some(10),
+                ..SmoothedObstructionParams::default()
+            },
+            OccupancyConfig::default(),
+        );
+
+        let estimate = model.compute_wait_time(&[obstruction(1, Some(true), UNIX_EPOCH)], UNIX_EPOCH);
+
+        assert_eq!(estimate.wait_time_minutes, Some(10.0));
+    }
+}
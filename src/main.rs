@@ -2,28 +2,104 @@ mod admin;
 mod api;
 mod bus;
 mod config;
+mod config_watcher;
 mod display;
 mod error;
 mod estimation;
+mod metric;
+mod runtime_config;
+mod scpi;
 mod sensor;
 mod state;
+mod storage;
+mod telemetry;
+mod watchdog;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
+use tracing_subscriber::prelude::*;
 
-fn init_tracing() {
-    let subscriber = tracing_subscriber::fmt().with_target(false).finish();
+type LogReloadHandle = tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>;
+
+/// Installs a `tracing` subscriber whose filter can be swapped at runtime
+/// (see [`apply_log_level`]), starting from `RUST_LOG` or `"info"` until the
+/// config file's `[logging].level` is known.
+fn init_tracing() -> LogReloadHandle {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    let (filter, reload_handle) = tracing_subscriber::reload::Layer::new(filter);
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_target(false));
     let _ = tracing::subscriber::set_global_default(subscriber);
+    reload_handle
+}
+
+/// Swaps the live log filter to `level`, logging and keeping the previous
+/// filter in place if `level` doesn't parse as an `EnvFilter` directive.
+fn apply_log_level(handle: &LogReloadHandle, level: &str) {
+    match tracing_subscriber::EnvFilter::try_new(level) {
+        Ok(filter) => {
+            if handle.reload(filter).is_err() {
+                tracing::warn!("Failed to apply log level - subscriber already gone");
+            }
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, level, "Invalid [logging].level, keeping current log filter");
+        }
+    }
+}
+
+/// Watches `config_rx` for reloads and applies the new log level. The rest
+/// of the reloaded `Config` (refresh interval, sensors, ...) is only logged;
+/// running subsystems keep the values they were started with.
+fn spawn_config_reload_listener(
+    mut config_rx: tokio::sync::watch::Receiver<config::Config>,
+    log_reload_handle: LogReloadHandle,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            if config_rx.changed().await.is_err() {
+                return;
+            }
+            let new_config = config_rx.borrow_and_update().clone();
+            apply_log_level(&log_reload_handle, &new_config.logging.level);
+            tracing::info!(
+                refresh_interval_ms = new_config.refresh_interval().as_millis(),
+                sensors = ?new_config.sensor_names(),
+                "Config file reloaded"
+            );
+        }
+    })
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    init_tracing();
+    let log_reload_handle = init_tracing();
     let config_path = config::resolve_config_path();
     tracing::info!(config_path = %config_path.display(), "mariam-flow starting");
     let config = config::load_from_path(&config_path)?;
+    apply_log_level(&log_reload_handle, &config.logging.level);
+    let (config_rx, _config_watcher_handle) =
+        config_watcher::spawn_config_watcher(config_path.clone(), config.clone());
+    let _config_reload_handle = spawn_config_reload_listener(config_rx, log_reload_handle);
     let state = Arc::new(RwLock::new(state::AppState::new()));
+    if let Ok(mut guard) = state.write() {
+        guard.set_history_capacity(config.history_capacity());
+    } else {
+        tracing::warn!("State lock poisoned while configuring history capacity");
+    }
+
+    let runtime_config_path = config.runtime_config_path();
+    let runtime_config = match runtime_config::RuntimeConfigStore::load(&runtime_config_path) {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load runtime config, starting unprovisioned");
+            runtime_config::RuntimeConfigStore::empty(&runtime_config_path)
+        }
+    };
+    let runtime_config = Arc::new(RwLock::new(runtime_config));
 
     // Load calibration file (required)
     let calibration_path = config
@@ -64,18 +140,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::warn!("State lock poisoned while applying model");
     }
 
-    // Discover sensors at startup
-    let sensor_configs = config.sensor_configs();
+    // Discover sensors at startup, preferring the runtime config's overrides
+    // over the boot-time TOML config for the base address and sensor map.
+    let (base_address, sensor_configs) = {
+        let guard = runtime_config.read().expect("runtime config lock poisoned");
+        (
+            guard.i2c_base_address_or(sensor::ADDRESS_BASE_7BIT),
+            guard.sensor_configs_or(&config.sensor_configs()),
+        )
+    };
     let xshut_controller = if sensor_configs.is_empty() {
         tracing::warn!("No sensors configured in [sensors].xshut_pins");
         None
     } else {
         tracing::info!(
             count = sensor_configs.len(),
-            pins = ?config.xshut_pins(),
+            base_address = format_args!("{base_address:#04x}"),
             "Starting sensor discovery"
         );
-        run_sensor_discovery(&config, &state)
+        run_sensor_discovery(&sensor_configs, base_address, &state)
     };
 
     let has_sensors = xshut_controller.is_some();
@@ -83,19 +166,75 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start periodic refresh thread (readings → obstructions → wait time)
     let stop_flag = Arc::new(AtomicBool::new(false));
     let refresh_interval = config.refresh_interval();
+    let rediscovery = Some(bus::xshut::RediscoveryConfig {
+        runtime_config: Arc::clone(&runtime_config),
+        fallback_sensors: config.sensor_configs(),
+        fallback_base_address: sensor::ADDRESS_BASE_7BIT,
+    });
     let _refresh_handle = if has_sensors {
         Some(spawn_refresh_thread(
             xshut_controller,
             &state,
             Arc::clone(&stop_flag),
             refresh_interval,
+            rediscovery,
         ))
     } else {
         tracing::warn!("Refresh thread not started - no sensors available");
         None
     };
 
-    let app = api::router(Arc::clone(&state));
+    #[cfg(feature = "mqtt")]
+    if let Some(mqtt_config) = config.mqtt_config() {
+        tracing::info!(host = %mqtt_config.host, site = %mqtt_config.site, "Starting MQTT telemetry publisher");
+        let _mqtt_handle = telemetry::mqtt::spawn_mqtt_publisher(Arc::clone(&state), mqtt_config);
+    }
+
+    let (prometheus_metrics, _prometheus_handle) = telemetry::prometheus::PrometheusMetrics::spawn(&state);
+
+    let _watchdog_handle = watchdog::spawn_watchdog(Arc::clone(&state), config.watchdog_config());
+
+    let history_store: Arc<dyn storage::HistoryStore> =
+        Arc::new(storage::EmbeddedHistoryStore::new(config.storage_retention()));
+    let _history_handle = storage::spawn_recorder(Arc::clone(&state), Arc::clone(&history_store));
+
+    #[cfg(feature = "otel_metrics")]
+    if let Some(otlp_endpoint) = config.otlp_endpoint() {
+        tracing::info!(%otlp_endpoint, "Starting OpenTelemetry metrics bridge");
+        let _otel_handle = telemetry::otel::spawn_otel_metrics(&state, &otlp_endpoint);
+    }
+
+    if let Some(admin_addr) = config.admin_addr() {
+        tracing::info!(%admin_addr, "Starting admin command server");
+        let _admin_handle = admin::spawn_command_server(
+            admin_addr,
+            Arc::clone(&state),
+            calibration.clone(),
+            calibration_path.to_path_buf(),
+        );
+    }
+
+    if let Some(scpi_addr) = config.scpi_addr() {
+        tracing::info!(%scpi_addr, "Starting SCPI command server");
+        let _scpi_handle = scpi::spawn_command_server(scpi_addr, Arc::clone(&state));
+    }
+
+    let app = api::router(
+        Arc::clone(&state),
+        Arc::clone(&runtime_config),
+        Arc::clone(&prometheus_metrics),
+        Arc::clone(&history_store),
+    );
+
+    if let Some(relay_config) = config.relay_config() {
+        tracing::info!(
+            relay_addr = %relay_config.relay_addr,
+            device_id = %relay_config.device_id,
+            "Starting relay client for reverse-tunnel API access"
+        );
+        let _relay_handle = bus::relay::spawn_relay_client(relay_config, app.clone());
+    }
+
     let port = config.server_port();
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -110,18 +249,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Run sensor discovery and return controller if successful
 fn run_sensor_discovery(
-    config: &config::Config,
+    sensor_configs: &[sensor::SensorConfig],
+    base_address: u8,
     state: &Arc<RwLock<state::AppState>>,
 ) -> Option<Box<dyn bus::xshut::XshutController + Send>> {
     #[cfg(target_os = "linux")]
     {
         use bus::xshut::{RppalXshutController, discover_and_store_sensors};
-        use sensor::vl53l1x::Vl53l1xFactory;
+        use sensor::vl53l1x::{Vl53l1xConfig, Vl53l1xFactory};
+        use vl53l1x_uld::IOVoltage;
 
-        let xshut_pins = config.xshut_pins();
-        let sensor_configs = config.sensor_configs();
+        let xshut_pins: Vec<u8> = sensor_configs.iter().map(|s| s.xshut_pin).collect();
 
-        let mut xshut = match RppalXshutController::new(xshut_pins) {
+        let mut xshut = match RppalXshutController::new(&xshut_pins) {
             Ok(xshut) => xshut,
             Err(err) => {
                 tracing::error!(error = %err, "Failed to initialize GPIO for XSHUT");
@@ -129,9 +269,15 @@ fn run_sensor_discovery(
             }
         };
 
-        let mut factory = Vl53l1xFactory::default();
+        let mut factory = match Vl53l1xFactory::new_rppal(IOVoltage::Volt2_8, Vl53l1xConfig::default()) {
+            Ok(factory) => factory,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to initialize I2C bus for VL53L1X");
+                return None;
+            }
+        };
 
-        match discover_and_store_sensors(&mut xshut, &mut factory, &sensor_configs, state) {
+        match discover_and_store_sensors(&mut xshut, &mut factory, sensor_configs, base_address, state) {
             Ok(results) => {
                 let ready = results
                     .iter()
@@ -160,19 +306,21 @@ fn run_sensor_discovery(
 
     #[cfg(not(target_os = "linux"))]
     {
-        let _ = (config, state);
+        let _ = (sensor_configs, base_address, state);
         tracing::warn!("Sensor discovery requires Linux/Raspberry Pi - skipping");
         None
     }
 }
 
-/// Spawn the periodic refresh thread for the estimation pipeline
+/// Spawn the periodic refresh pipeline (sensor producer + estimation
+/// worker) for the estimation pipeline
 fn spawn_refresh_thread(
     xshut_controller: Option<Box<dyn bus::xshut::XshutController + Send>>,
     state: &Arc<RwLock<state::AppState>>,
     stop: Arc<AtomicBool>,
     interval: Duration,
-) -> std::thread::JoinHandle<()> {
+    rediscovery: Option<bus::xshut::RediscoveryConfig>,
+) -> (std::thread::JoinHandle<()>, std::thread::JoinHandle<()>) {
     // Get model from state to pass to thread
     let model = {
         let guard = state.read().expect("state lock poisoned");
@@ -181,9 +329,16 @@ fn spawn_refresh_thread(
     #[cfg(target_os = "linux")]
     {
         use estimation::spawn_refresh_thread as spawn_thread;
-        use sensor::vl53l1x::Vl53l1xFactory;
+        use sensor::vl53l1x::{Vl53l1xConfig, Vl53l1xFactory};
+        use vl53l1x_uld::IOVoltage;
 
-        let factory = Vl53l1xFactory::default();
+        let factory = match Vl53l1xFactory::new_rppal(IOVoltage::Volt2_8, Vl53l1xConfig::default()) {
+            Ok(factory) => factory,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to initialize I2C bus for VL53L1X");
+                return (std::thread::spawn(|| {}), std::thread::spawn(|| {}));
+            }
+        };
         tracing::info!(
             interval_ms = interval.as_millis(),
             "Starting estimation refresh thread"
@@ -195,14 +350,15 @@ fn spawn_refresh_thread(
             interval,
             stop,
             model,
+            rediscovery,
         )
     }
 
     #[cfg(not(target_os = "linux"))]
     {
-        let _ = (state, stop, interval, model, xshut_controller);
-        tracing::warn!("Refresh thread requires Linux/Raspberry Pi - starting dummy thread");
-        std::thread::spawn(|| {})
+        let _ = (state, stop, interval, model, xshut_controller, rediscovery);
+        tracing::warn!("Refresh thread requires Linux/Raspberry Pi - starting dummy threads");
+        (std::thread::spawn(|| {}), std::thread::spawn(|| {}))
     }
 }
 
@@ -0,0 +1,270 @@
+//! Windowed metrics over reading updates.
+//!
+//! `AppState::subscribe_readings` broadcasts a fresh snapshot every cycle,
+//! but nothing accumulates it over time: each cycle overwrites the last.
+//! `Metric` subscribes to that channel and keeps, per sensor, a sliding
+//! time window of `SensorReading`s, exposing summary statistics so the
+//! estimation layer can consume smoothed values instead of a single cycle's
+//! raw distance. Modeled on the openbmc telemetry `Metric`, which registers
+//! for sensor updates and accumulates timestamped readings per sensor.
+
+use crate::sensor::SensorId;
+use crate::state::{AppState, ReadingStatus, SensorReading};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+/// Summary statistics over a sensor's current window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats {
+    pub min_mm: u16,
+    pub max_mm: u16,
+    pub mean_mm: f64,
+    /// Fraction (0.0..=1.0) of samples in the window with `ReadingStatus::Ok`.
+    pub valid_fraction: f64,
+}
+
+/// An aggregated snapshot for one sensor's window, taken at `timestamp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricValue {
+    pub sensor_id: SensorId,
+    pub timestamp: SystemTime,
+    pub stats: WindowStats,
+    pub count: usize,
+}
+
+struct SensorWindow {
+    window: Duration,
+    samples: VecDeque<SensorReading>,
+}
+
+impl SensorWindow {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, reading: SensorReading) {
+        let now = reading.timestamp;
+        self.samples.push_back(reading);
+        while let Some(front) = self.samples.front() {
+            match now.duration_since(front.timestamp) {
+                Ok(age) if age > self.window => {
+                    self.samples.pop_front();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn stats(&self) -> Option<WindowStats> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let valid: Vec<u16> = self
+            .samples
+            .iter()
+            .filter(|reading| matches!(reading.status, ReadingStatus::Ok { .. }))
+            .map(|reading| reading.distance_mm)
+            .collect();
+
+        if valid.is_empty() {
+            return None;
+        }
+
+        let min_mm = valid.iter().copied().min().unwrap_or(0);
+        let max_mm = valid.iter().copied().max().unwrap_or(0);
+        let mean_mm = valid.iter().map(|&mm| mm as f64).sum::<f64>() / valid.len() as f64;
+        let valid_fraction = valid.len() as f64 / self.samples.len() as f64;
+
+        Some(WindowStats {
+            min_mm,
+            max_mm,
+            mean_mm,
+            valid_fraction,
+        })
+    }
+}
+
+/// Maintains a sliding window of `SensorReading`s per sensor and republishes
+/// aggregated `MetricValue` snapshots on every ingest.
+pub struct Metric {
+    window: Duration,
+    windows: Mutex<HashMap<SensorId, SensorWindow>>,
+    values_tx: watch::Sender<HashMap<SensorId, MetricValue>>,
+}
+
+impl Metric {
+    pub fn new(window: Duration) -> Self {
+        let (values_tx, _values_rx) = watch::channel(HashMap::new());
+        Self {
+            window,
+            windows: Mutex::new(HashMap::new()),
+            values_tx,
+        }
+    }
+
+    pub fn subscribe_values(&self) -> watch::Receiver<HashMap<SensorId, MetricValue>> {
+        self.values_tx.subscribe()
+    }
+
+    pub fn values(&self) -> HashMap<SensorId, MetricValue> {
+        self.values_tx.borrow().clone()
+    }
+
+    /// Folds a readings snapshot into each sensor's window and republishes
+    /// the resulting `MetricValue`s.
+    pub fn ingest(&self, readings: &[SensorReading]) {
+        let mut windows = self.windows.lock().unwrap_or_else(|p| p.into_inner());
+        let mut values = self.values_tx.borrow().clone();
+
+        for reading in readings {
+            let window = windows
+                .entry(reading.sensor_id)
+                .or_insert_with(|| SensorWindow::new(self.window));
+            window.push(reading.clone());
+
+            match window.stats() {
+                Some(stats) => {
+                    values.insert(
+                        reading.sensor_id,
+                        MetricValue {
+                            sensor_id: reading.sensor_id,
+                            timestamp: reading.timestamp,
+                            stats,
+                            count: window.samples.len(),
+                        },
+                    );
+                }
+                None => {
+                    values.remove(&reading.sensor_id);
+                }
+            }
+        }
+
+        let _ = self.values_tx.send(values);
+    }
+
+    /// Registers against `state`'s readings broadcast channel and spawns a
+    /// task that ingests every subsequent snapshot as it arrives.
+    pub fn spawn(
+        window: Duration,
+        state: &Arc<RwLock<AppState>>,
+    ) -> (Arc<Metric>, tokio::task::JoinHandle<()>) {
+        let metric = Arc::new(Metric::new(window));
+        let mut readings_rx = {
+            let guard = state.read().expect("state lock poisoned");
+            guard.subscribe_readings()
+        };
+
+        let task_metric = Arc::clone(&metric);
+        let handle = tokio::spawn(async move {
+            loop {
+                if readings_rx.changed().await.is_err() {
+                    break;
+                }
+                let readings = readings_rx.borrow().clone();
+                task_metric.ingest(&readings);
+            }
+        });
+
+        (metric, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::SensorRangeStatus;
+    use std::time::UNIX_EPOCH;
+
+    fn ok_reading(sensor_id: SensorId, distance_mm: u16, timestamp: SystemTime) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm,
+            timestamp,
+            status: ReadingStatus::Ok {
+                range_status: SensorRangeStatus::Valid,
+            },
+        }
+    }
+
+    fn error_reading(sensor_id: SensorId, timestamp: SystemTime) -> SensorReading {
+        SensorReading {
+            sensor_id,
+            distance_mm: 0,
+            timestamp,
+            status: ReadingStatus::Error {
+                reason: "read failed".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn ingest_computes_min_max_mean_over_window() {
+        let metric = Metric::new(Duration::from_secs(60));
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(1);
+        let t2 = UNIX_EPOCH + Duration::from_secs(2);
+
+        metric.ingest(&[ok_reading(1, 1000, t0)]);
+        metric.ingest(&[ok_reading(1, 800, t1)]);
+        metric.ingest(&[ok_reading(1, 1200, t2)]);
+
+        let values = metric.values();
+        let value = values.get(&1).expect("sensor 1 has a metric value");
+
+        assert_eq!(value.stats.min_mm, 800);
+        assert_eq!(value.stats.max_mm, 1200);
+        assert_eq!(value.stats.mean_mm, 1000.0);
+        assert_eq!(value.count, 3);
+        assert_eq!(value.stats.valid_fraction, 1.0);
+    }
+
+    #[test]
+    fn ingest_evicts_samples_older_than_the_window() {
+        let metric = Metric::new(Duration::from_secs(5));
+        let t0 = UNIX_EPOCH;
+        let t_late = UNIX_EPOCH + Duration::from_secs(10);
+
+        metric.ingest(&[ok_reading(1, 500, t0)]);
+        metric.ingest(&[ok_reading(1, 900, t_late)]);
+
+        let values = metric.values();
+        let value = values.get(&1).expect("sensor 1 has a metric value");
+
+        // The t0 sample should have been evicted: only 900 remains.
+        assert_eq!(value.stats.min_mm, 900);
+        assert_eq!(value.stats.max_mm, 900);
+        assert_eq!(value.count, 1);
+    }
+
+    #[test]
+    fn ingest_tracks_valid_fraction_with_errors() {
+        let metric = Metric::new(Duration::from_secs(60));
+        let t0 = UNIX_EPOCH;
+        let t1 = UNIX_EPOCH + Duration::from_secs(1);
+
+        metric.ingest(&[ok_reading(1, 1000, t0)]);
+        metric.ingest(&[error_reading(1, t1)]);
+
+        let values = metric.values();
+        let value = values.get(&1).expect("sensor 1 has a metric value");
+
+        assert_eq!(value.count, 2);
+        assert_eq!(value.stats.valid_fraction, 0.5);
+    }
+
+    #[test]
+    fn sensor_with_only_errors_has_no_metric_value() {
+        let metric = Metric::new(Duration::from_secs(60));
+
+        metric.ingest(&[error_reading(1, UNIX_EPOCH)]);
+
+        assert!(metric.values().get(&1).is_none());
+    }
+}
@@ -0,0 +1,292 @@
+//! Persistent `key=value`-per-line runtime configuration.
+//!
+//! Modeled on the SD-card `config.txt` format used by field-programmable
+//! embedded systems (e.g. artiq-zynq's `ip`/`mac` keys): a flat text file,
+//! one `key=value` pair per line, that an operator can read, write, and
+//! remove entries from without a rebuild. Recognizes `i2c_base_address` and
+//! repeated `sensor.<id>.xshut_pin` entries so the I2C address plan or XSHUT
+//! wiring can be re-planned in the field; every mutation is persisted back
+//! to disk immediately so the change survives a restart.
+
+use crate::sensor::{SensorConfig, SensorId};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub const DEFAULT_RUNTIME_CONFIG_PATH: &str = "config/runtime.txt";
+
+const I2C_BASE_ADDRESS_KEY: &str = "i2c_base_address";
+const SENSOR_XSHUT_PIN_PREFIX: &str = "sensor.";
+const SENSOR_XSHUT_PIN_SUFFIX: &str = ".xshut_pin";
+
+#[derive(Debug, Error)]
+pub enum RuntimeConfigError {
+    #[error("failed to read runtime config: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("malformed line {line_no} in runtime config (expected key=value): {line:?}")]
+    Malformed { line_no: usize, line: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeConfigStore {
+    path: PathBuf,
+    entries: BTreeMap<String, String>,
+}
+
+impl RuntimeConfigStore {
+    pub fn load_default() -> Result<Self, RuntimeConfigError> {
+        Self::load(DEFAULT_RUNTIME_CONFIG_PATH)
+    }
+
+    /// Loads `path`, tolerating a missing file: an un-provisioned unit simply
+    /// starts with an empty store and falls back to compiled-in defaults
+    /// until an operator writes an entry.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RuntimeConfigError> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// Starts an empty, unpersisted store bound to `path` - used when
+    /// loading fails but writes should still land somewhere sensible.
+    pub fn empty(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Sets `key` to `value` and persists the whole store to disk.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), RuntimeConfigError> {
+        self.entries.insert(key.to_string(), value.to_string());
+        self.persist()
+    }
+
+    /// Removes `key`, if present, and persists the whole store to disk.
+    pub fn remove(&mut self, key: &str) -> Result<(), RuntimeConfigError> {
+        self.entries.remove(key);
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<(), RuntimeConfigError> {
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut contents = String::new();
+        for (key, value) in &self.entries {
+            contents.push_str(key);
+            contents.push('=');
+            contents.push_str(value);
+            contents.push('\n');
+        }
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    /// Returns the configured I2C base address, or `fallback` if `
+    /// i2c_base_address` is unset or unparseable.
+    pub fn i2c_base_address_or(&self, fallback: u8) -> u8 {
+        self.get(I2C_BASE_ADDRESS_KEY)
+            .and_then(parse_u8)
+            .unwrap_or(fallback)
+    }
+
+    /// Returns sensor configs built from `sensor.<id>.xshut_pin` entries,
+    /// ordered by sensor id, or `fallback.to_vec()` if none are set.
+    pub fn sensor_configs_or(&self, fallback: &[SensorConfig]) -> Vec<SensorConfig> {
+        let mut configs: Vec<SensorConfig> = self
+            .entries
+            .iter()
+            .filter_map(|(key, value)| {
+                let id_str = key
+                    .strip_prefix(SENSOR_XSHUT_PIN_PREFIX)?
+                    .strip_suffix(SENSOR_XSHUT_PIN_SUFFIX)?;
+                let sensor_id: SensorId = id_str.parse().ok()?;
+                let xshut_pin: u8 = value.parse().ok()?;
+                Some(SensorConfig {
+                    sensor_id,
+                    xshut_pin,
+                })
+            })
+            .collect();
+
+        if configs.is_empty() {
+            return fallback.to_vec();
+        }
+        configs.sort_by_key(|config| config.sensor_id);
+        configs
+    }
+}
+
+fn parse(contents: &str) -> Result<BTreeMap<String, String>, RuntimeConfigError> {
+    let mut entries = BTreeMap::new();
+    for (line_no, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| RuntimeConfigError::Malformed {
+                line_no: line_no + 1,
+                line: raw_line.to_string(),
+            })?;
+        entries.insert(key.trim().to_string(), value.trim().to_string());
+    }
+    Ok(entries)
+}
+
+fn parse_u8(value: &str) -> Option<u8> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_path(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos();
+        std::env::temp_dir().join(format!("mariam-runtime-config-{label}-{unique}.txt"))
+    }
+
+    #[test]
+    fn missing_file_loads_as_empty_store() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("missing");
+
+        let store = RuntimeConfigStore::load(&path)?;
+
+        assert_eq!(store.entries().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn set_persists_and_is_readable_after_reload() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("set-reload");
+        let mut store = RuntimeConfigStore::load(&path)?;
+
+        store.set("i2c_base_address", "0x40")?;
+        store.set("sensor.1.xshut_pin", "17")?;
+
+        let reloaded = RuntimeConfigStore::load(&path)?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(reloaded.get("i2c_base_address"), Some("0x40"));
+        assert_eq!(reloaded.get("sensor.1.xshut_pin"), Some("17"));
+        Ok(())
+    }
+
+    #[test]
+    fn remove_persists_deletion() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("remove");
+        let mut store = RuntimeConfigStore::load(&path)?;
+        store.set("i2c_base_address", "0x40")?;
+
+        store.remove("i2c_base_address")?;
+
+        let reloaded = RuntimeConfigStore::load(&path)?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(reloaded.get("i2c_base_address"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_line_is_rejected() {
+        let path = temp_path("malformed");
+        std::fs::write(&path, "not a key value line\n").expect("write fixture");
+
+        let result = RuntimeConfigStore::load(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(result, Err(RuntimeConfigError::Malformed { .. })));
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("comments");
+        std::fs::write(&path, "# comment\n\ni2c_base_address=0x40\n")
+            .expect("write fixture");
+
+        let store = RuntimeConfigStore::load(&path)?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(store.get("i2c_base_address"), Some("0x40"));
+        Ok(())
+    }
+
+    #[test]
+    fn i2c_base_address_or_falls_back_when_unset() -> Result<(), RuntimeConfigError> {
+        let store = RuntimeConfigStore::load(temp_path("base-fallback"))?;
+
+        assert_eq!(store.i2c_base_address_or(0x30), 0x30);
+        Ok(())
+    }
+
+    #[test]
+    fn i2c_base_address_or_parses_hex_and_decimal() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("base-parse");
+        let mut store = RuntimeConfigStore::load(&path)?;
+        store.set("i2c_base_address", "0x42")?;
+        assert_eq!(store.i2c_base_address_or(0x30), 0x42);
+
+        store.set("i2c_base_address", "66")?;
+        assert_eq!(store.i2c_base_address_or(0x30), 66);
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[test]
+    fn sensor_configs_or_falls_back_when_unset() -> Result<(), RuntimeConfigError> {
+        let store = RuntimeConfigStore::load(temp_path("sensors-fallback"))?;
+        let fallback = vec![SensorConfig {
+            sensor_id: 1,
+            xshut_pin: 17,
+        }];
+
+        let configs = store.sensor_configs_or(&fallback);
+
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].xshut_pin, 17);
+        Ok(())
+    }
+
+    #[test]
+    fn sensor_configs_or_builds_sorted_configs_from_entries() -> Result<(), RuntimeConfigError> {
+        let path = temp_path("sensors-sorted");
+        let mut store = RuntimeConfigStore::load(&path)?;
+        store.set("sensor.2.xshut_pin", "27")?;
+        store.set("sensor.1.xshut_pin", "17")?;
+
+        let configs = store.sensor_configs_or(&[]);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].sensor_id, 1);
+        assert_eq!(configs[0].xshut_pin, 17);
+        assert_eq!(configs[1].sensor_id, 2);
+        assert_eq!(configs[1].xshut_pin, 27);
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+//! SCPI-style line command console for sensor control and queue queries.
+//!
+//! Complements the `admin` module's `GET`/`SET` calibration console with a
+//! colon-separated, query-capable protocol modeled on instrument SCPI
+//! syntax: `SENS:STAT?` returns all sensor statuses, `SENS:REINIT 2` queues
+//! a forced re-init for sensor id 2, `SENS:DISCover` re-runs discovery, and
+//! `QUEUE:WAIT?` returns the current wait-time estimate. Keywords follow the
+//! usual SCPI short/long form convention - the capitalized prefix (e.g.
+//! `DISC`) is the minimum abbreviation accepted, with the full word (e.g.
+//! `DISCOVER`) also valid. Hardware access is exclusively owned by the
+//! estimation refresh thread, so `REINIT`/`DISCover` just flag `AppState`
+//! (via `request_reinitialize`/`request_rediscovery`) and let that thread
+//! perform the actual re-init or rediscovery on its next cycle.
+
+use crate::sensor::{SensorId, SensorStatus};
+use crate::state::{AppState, WaitTimeStatus};
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+#[derive(Debug, Error)]
+pub enum ScpiError {
+    #[error("empty command")]
+    Empty,
+    #[error("unknown command: {0}")]
+    UnknownCommand(String),
+    #[error("missing argument for {0}")]
+    MissingArgument(&'static str),
+    #[error("invalid value for {0}: {1}")]
+    InvalidValue(&'static str, String),
+}
+
+/// Shared state the SCPI console reads and flags mutations against.
+struct ScpiState {
+    state: Arc<RwLock<AppState>>,
+}
+
+/// Spawn the SCPI command server on `addr`, serving each accepted
+/// connection as its own concurrent command session.
+pub fn spawn_command_server(addr: SocketAddr, state: Arc<RwLock<AppState>>) -> JoinHandle<()> {
+    let scpi_state = Arc::new(ScpiState { state });
+
+    tokio::spawn(async move {
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                warn!(%addr, error = %err, "Failed to bind SCPI command server");
+                return;
+            }
+        };
+
+        loop {
+            let (socket, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    warn!(error = %err, "SCPI command server accept failed");
+                    continue;
+                }
+            };
+
+            let scpi_state = Arc::clone(&scpi_state);
+            tokio::spawn(async move {
+                if let Err(err) = handle_connection(socket, scpi_state).await {
+                    warn!(%peer, error = %err, "SCPI connection ended with error");
+                }
+            });
+        }
+    })
+}
+
+async fn handle_connection(
+    socket: tokio::net::TcpStream,
+    scpi_state: Arc<ScpiState>,
+) -> Result<(), std::io::Error> {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let reply = match handle_command(trimmed, &scpi_state) {
+            Ok(reply) => reply,
+            Err(err) => format!("ERR {err}"),
+        };
+
+        writer.write_all(reply.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+
+    Ok(())
+}
+
+/// A single node in the static SCPI command tree, matched by its SCPI
+/// short/long form (e.g. short `DISC`, long `DISCOVER`).
+struct Keyword {
+    short: &'static str,
+    long: &'static str,
+}
+
+const SENS: Keyword = Keyword {
+    short: "SENS",
+    long: "SENSOR",
+};
+const STAT: Keyword = Keyword {
+    short: "STAT",
+    long: "STATUS",
+};
+const REINIT: Keyword = Keyword {
+    short: "REINIT",
+    long: "REINITIALIZE",
+};
+const DISC: Keyword = Keyword {
+    short: "DISC",
+    long: "DISCOVER",
+};
+const QUEUE: Keyword = Keyword {
+    short: "QUEUE",
+    long: "QUEUE",
+};
+const WAIT: Keyword = Keyword {
+    short: "WAIT",
+    long: "WAIT",
+};
+
+/// Matches `token` against a keyword's short/long form: any abbreviation
+/// from the short form up to the full long form is accepted, as long as it
+/// is itself a prefix of the long form - the standard SCPI convention.
+fn keyword_matches(keyword: &Keyword, token: &str) -> bool {
+    let upper = token.to_ascii_uppercase();
+    upper.len() >= keyword.short.len()
+        && upper.len() <= keyword.long.len()
+        && keyword.long.starts_with(upper.as_str())
+        && upper.starts_with(keyword.short)
+}
+
+fn handle_command(line: &str, scpi_state: &ScpiState) -> Result<String, ScpiError> {
+    let mut tokens = line.split_whitespace();
+    let head = tokens.next().ok_or(ScpiError::Empty)?;
+    let args: Vec<&str> = tokens.collect();
+
+    let is_query = head.ends_with('?');
+    let head = head.trim_end_matches('?');
+    let path: Vec<&str> = head.split(':').filter(|segment| !segment.is_empty()).collect();
+
+    match path.as_slice() {
+        [root, leaf] if is_query && keyword_matches(&SENS, root) && keyword_matches(&STAT, leaf) => {
+            Ok(handle_sens_stat(scpi_state))
+        }
+        [root, leaf]
+            if !is_query && keyword_matches(&SENS, root) && keyword_matches(&REINIT, leaf) =>
+        {
+            handle_sens_reinit(&args, scpi_state)
+        }
+        [root, leaf] if !is_query && keyword_matches(&SENS, root) && keyword_matches(&DISC, leaf) => {
+            Ok(handle_sens_disc(scpi_state))
+        }
+        [root, leaf]
+            if is_query && keyword_matches(&QUEUE, root) && keyword_matches(&WAIT, leaf) =>
+        {
+            Ok(handle_queue_wait(scpi_state))
+        }
+        _ => Err(ScpiError::UnknownCommand(line.to_string())),
+    }
+}
+
+fn handle_sens_stat(scpi_state: &ScpiState) -> String {
+    let guard = scpi_state.state.read().unwrap_or_else(|p| p.into_inner());
+    if guard.sensors().is_empty() {
+        return "OK".to_string();
+    }
+    let statuses: Vec<String> = guard
+        .sensors()
+        .iter()
+        .map(|sensor| format!("sensor-{}={}", sensor.sensor_id, format_sensor_status(&sensor.status)))
+        .collect();
+    format!("OK {}", statuses.join(";"))
+}
+
+fn format_sensor_status(status: &SensorStatus) -> String {
+    match status {
+        SensorStatus::Ready => "ready".to_string(),
+        SensorStatus::Provisional => "provisional".to_string(),
+        SensorStatus::Error { error } => format!("error:{error}"),
+    }
+}
+
+fn handle_sens_reinit(args: &[&str], scpi_state: &ScpiState) -> Result<String, ScpiError> {
+    let sensor_id_str = args
+        .first()
+        .ok_or(ScpiError::MissingArgument("SENS:REINIT"))?;
+    let sensor_id: SensorId = sensor_id_str
+        .parse()
+        .map_err(|_| ScpiError::InvalidValue("SENS:REINIT", sensor_id_str.to_string()))?;
+
+    let mut guard = scpi_state.state.write().unwrap_or_else(|p| p.into_inner());
+    guard.request_reinitialize(sensor_id);
+    Ok(format!("OK queued sensor_id={sensor_id}"))
+}
+
+fn handle_sens_disc(scpi_state: &ScpiState) -> String {
+    let mut guard = scpi_state.state.write().unwrap_or_else(|p| p.into_inner());
+    guard.request_rediscovery();
+    "OK queued".to_string()
+}
+
+fn handle_queue_wait(scpi_state: &ScpiState) -> String {
+    let guard = scpi_state.state.read().unwrap_or_else(|p| p.into_inner());
+    match guard.wait_time() {
+        Some(estimate) => format!(
+            "OK wait_time_minutes={} status={}",
+            estimate
+                .wait_time_minutes
+                .map(|minutes| minutes.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+            match estimate.status {
+                WaitTimeStatus::Ok => "ok",
+                WaitTimeStatus::Degraded => "degraded",
+            }
+        ),
+        None => "OK wait_time_minutes=none status=unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::{DeviceSensorError, SensorAddress, SensorInfo};
+    use crate::state::WaitTimeEstimate;
+    use std::time::UNIX_EPOCH;
+
+    fn scpi_state_with(state: AppState) -> ScpiState {
+        ScpiState {
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    #[test]
+    fn sens_stat_query_reports_all_sensors() {
+        let mut app_state = AppState::new();
+        app_state
+            .set_sensors(vec![
+                SensorInfo {
+                    sensor_id: 1,
+                    xshut_pin: 17,
+                    address: SensorAddress::I2c(0x30),
+                    status: SensorStatus::Ready,
+                },
+                SensorInfo {
+                    sensor_id: 2,
+                    xshut_pin: 27,
+                    address: SensorAddress::I2c(0x31),
+                    status: SensorStatus::Error {
+                        error: DeviceSensorError::Other("i2c timeout".to_string()),
+                    },
+                },
+            ])
+            .expect("set sensors");
+        let scpi_state = scpi_state_with(app_state);
+
+        let reply = handle_command("SENS:STAT?", &scpi_state).expect("command ok");
+
+        assert_eq!(reply, "OK sensor-1=ready;sensor-2=error:i2c timeout");
+    }
+
+    #[test]
+    fn sens_stat_accepts_long_form_and_lowercase() {
+        let scpi_state = scpi_state_with(AppState::new());
+
+        let reply = handle_command("sensor:status?", &scpi_state).expect("command ok");
+
+        assert_eq!(reply, "OK");
+    }
+
+    #[test]
+    fn sens_reinit_queues_request() {
+        let scpi_state = scpi_state_with(AppState::new());
+
+        let reply = handle_command("SENS:REINIT 2", &scpi_state).expect("command ok");
+
+        assert_eq!(reply, "OK queued sensor_id=2");
+        let mut guard = scpi_state.state.write().expect("state lock");
+        assert!(guard.take_reinitialize_requests().contains(&2));
+    }
+
+    #[test]
+    fn sens_reinit_missing_argument_is_rejected() {
+        let scpi_state = scpi_state_with(AppState::new());
+
+        let err = handle_command("SENS:REINIT", &scpi_state).unwrap_err();
+
+        assert!(matches!(err, ScpiError::MissingArgument("SENS:REINIT")));
+    }
+
+    #[test]
+    fn sens_disc_queues_rediscovery() {
+        let scpi_state = scpi_state_with(AppState::new());
+
+        let reply = handle_command("SENS:DISCover", &scpi_state).expect("command ok");
+
+        assert_eq!(reply, "OK queued");
+        let mut guard = scpi_state.state.write().expect("state lock");
+        assert!(guard.take_rediscovery_request());
+    }
+
+    #[test]
+    fn queue_wait_query_reports_estimate() {
+        let mut app_state = AppState::new();
+        app_state
+            .set_wait_time(WaitTimeEstimate {
+                wait_time_minutes: Some(7.5),
+                timestamp: UNIX_EPOCH,
+                status: WaitTimeStatus::Ok,
+                error_code: None,
+            })
+            .expect("set wait time");
+        let scpi_state = scpi_state_with(app_state);
+
+        let reply = handle_command("QUEUE:WAIT?", &scpi_state).expect("command ok");
+
+        assert_eq!(reply, "OK wait_time_minutes=7.5 status=ok");
+    }
+
+    #[test]
+    fn unknown_command_is_rejected() {
+        let scpi_state = scpi_state_with(AppState::new());
+
+        let err = handle_command("FROB:WHATEVER", &scpi_state).unwrap_err();
+
+        assert!(matches!(err, ScpiError::UnknownCommand(_)));
+    }
+}
@@ -0,0 +1,136 @@
+//! Async counterpart to [`SensorDriver`] for concurrent ranging.
+//!
+//! A bank of sensors driven through the blocking [`SensorDriver`] trait must
+//! be ranged sequentially, paying each sensor's inter-measurement timing
+//! budget back to back. `AsyncSensorDriver` lets callers `await` every sensor
+//! concurrently (`futures::future::join_all` / `tokio::join!`) so one tick
+//! costs ~1x budget instead of N x budget. [`BlockingSensorDriver`] adapts
+//! any existing blocking driver (hardware or mock) onto this trait by
+//! running each call on [`tokio::task::spawn_blocking`].
+
+use crate::error::AppError;
+use crate::sensor::{DistanceMeasurement, SensorDriver};
+use std::future::Future;
+
+pub trait AsyncSensorDriver {
+    fn init_default(&mut self) -> impl Future<Output = Result<(), AppError>> + Send;
+    fn set_address(&mut self, new_address: u8) -> impl Future<Output = Result<(), AppError>> + Send;
+    fn verify(&mut self) -> impl Future<Output = Result<(), AppError>> + Send;
+    /// Start continuous ranging mode. Must be called after init before reading distances.
+    fn start_ranging(&mut self) -> impl Future<Output = Result<(), AppError>> + Send;
+    fn read_distance(&mut self) -> impl Future<Output = Result<DistanceMeasurement, AppError>> + Send;
+}
+
+/// Drives a blocking [`SensorDriver`] on the blocking thread pool so it can
+/// satisfy [`AsyncSensorDriver`] without the driver itself needing to change.
+pub struct BlockingSensorDriver<D> {
+    driver: Option<D>,
+}
+
+impl<D> BlockingSensorDriver<D> {
+    pub fn new(driver: D) -> Self {
+        Self {
+            driver: Some(driver),
+        }
+    }
+
+    /// Runs `f` against the inner driver on `spawn_blocking`, returning the
+    /// driver to `self` once the blocking call completes.
+    async fn with_driver<F, T>(&mut self, f: F) -> Result<T, AppError>
+    where
+        D: SensorDriver + Send + 'static,
+        F: FnOnce(&mut D) -> Result<T, AppError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut driver = self
+            .driver
+            .take()
+            .expect("BlockingSensorDriver used after a prior panic");
+
+        let (result, driver) = tokio::task::spawn_blocking(move || {
+            let result = f(&mut driver);
+            (result, driver)
+        })
+        .await
+        .unwrap_or_else(|_| panic!("blocking sensor task panicked"));
+
+        self.driver = Some(driver);
+        result
+    }
+}
+
+impl<D> AsyncSensorDriver for BlockingSensorDriver<D>
+where
+    D: SensorDriver + Send + 'static,
+{
+    async fn init_default(&mut self) -> Result<(), AppError> {
+        self.with_driver(|driver| driver.init_default()).await
+    }
+
+    async fn set_address(&mut self, new_address: u8) -> Result<(), AppError> {
+        self.with_driver(move |driver| driver.set_address(new_address))
+            .await
+    }
+
+    async fn verify(&mut self) -> Result<(), AppError> {
+        self.with_driver(|driver| driver.verify()).await
+    }
+
+    async fn start_ranging(&mut self) -> Result<(), AppError> {
+        self.with_driver(|driver| driver.start_ranging()).await
+    }
+
+    async fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
+        self.with_driver(|driver| driver.read_distance()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensor::mock::{MockSensorBehavior, MockSensorDriver};
+
+    #[tokio::test]
+    async fn blocking_adapter_forwards_successful_reads() {
+        let behavior = MockSensorBehavior::with_reading(321, crate::sensor::SensorRangeStatus::Valid);
+        let mut driver = BlockingSensorDriver::new(MockSensorDriver::new(behavior));
+
+        let measurement = driver.read_distance().await.expect("read distance ok");
+
+        assert_eq!(measurement.distance_mm, 321);
+    }
+
+    #[tokio::test]
+    async fn blocking_adapter_forwards_errors() {
+        let behavior = MockSensorBehavior::fail_read_distance();
+        let mut driver = BlockingSensorDriver::new(MockSensorDriver::new(behavior));
+
+        let err = driver.read_distance().await.unwrap_err();
+
+        assert_eq!(err.to_string(), "sensor error: mock read distance failed");
+    }
+
+    #[tokio::test]
+    async fn concurrent_reads_complete_for_every_driver() {
+        let mut drivers = vec![
+            BlockingSensorDriver::new(MockSensorDriver::new(MockSensorBehavior::with_reading(
+                100,
+                crate::sensor::SensorRangeStatus::Valid,
+            ))),
+            BlockingSensorDriver::new(MockSensorDriver::new(MockSensorBehavior::with_reading(
+                200,
+                crate::sensor::SensorRangeStatus::Valid,
+            ))),
+        ];
+
+        let results =
+            futures::future::join_all(drivers.iter_mut().map(|driver| driver.read_distance()))
+                .await;
+
+        let distances: Vec<u16> = results
+            .into_iter()
+            .map(|result| result.expect("read ok").distance_mm)
+            .collect();
+        assert_eq!(distances, vec![100, 200]);
+    }
+}
@@ -0,0 +1,214 @@
+//! Generic `embedded-hal` VL53L0X driver.
+//!
+//! Unlike [`crate::sensor::vl53l1x`], which wraps the `vl53l1x_uld` crate's
+//! own protocol implementation, `I2cSensorDriver<I2C, D>` implements the
+//! same `SensorDriver` methods by talking the VL53L0X register protocol
+//! directly over any
+//! `embedded_hal::i2c::I2c` bus and `embedded_hal::delay::DelayNs` source,
+//! so the exact same driver runs on a Raspberry Pi (via
+//! `linux-embedded-hal`) or a bare-metal MCU (via embassy). Keeping the
+//! `SensorDriver` surface identical means `read_and_store_distances` works
+//! unchanged against mock, rppal, or bare-metal backends.
+
+use crate::error::AppError;
+use crate::sensor::{DistanceMeasurement, SensorDriver, SensorDriverFactory, SensorRangeStatus};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::i2c::I2c;
+use std::sync::{Arc, Mutex};
+
+const REG_IDENTIFICATION_MODEL_ID: u8 = 0xC0;
+const EXPECTED_MODEL_ID: u8 = 0xEE;
+const REG_I2C_SLAVE_DEVICE_ADDRESS: u8 = 0x8A;
+const REG_SYSRANGE_START: u8 = 0x00;
+const REG_RESULT_RANGE_STATUS: u8 = 0x14;
+const REG_RESULT_RANGE_MM_HIGH: u8 = 0x1E;
+const RANGE_STATUS_COMPLETE_BIT: u8 = 0x01;
+
+/// Generic VL53L0X driver over any `embedded-hal` I2C bus, sharing that bus
+/// (behind a mutex) with the other sensors the same factory created.
+pub struct I2cSensorDriver<I2C, D> {
+    i2c: Arc<Mutex<I2C>>,
+    delay: D,
+    address: u8,
+}
+
+impl<I2C, D> I2cSensorDriver<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    fn write_register(&mut self, register: u8, value: u8) -> Result<(), AppError> {
+        let mut i2c = self
+            .i2c
+            .lock()
+            .map_err(|_| AppError::I2c("i2c bus lock poisoned".to_string()))?;
+        i2c.write(self.address, &[register, value])
+            .map_err(|_| AppError::I2c(format!("write to register {register:#04x} failed")))
+    }
+
+    fn read_register(&mut self, register: u8) -> Result<u8, AppError> {
+        let mut buf = [0u8; 1];
+        let mut i2c = self
+            .i2c
+            .lock()
+            .map_err(|_| AppError::I2c("i2c bus lock poisoned".to_string()))?;
+        i2c.write_read(self.address, &[register], &mut buf)
+            .map_err(|_| AppError::I2c(format!("read from register {register:#04x} failed")))?;
+        Ok(buf[0])
+    }
+
+    fn read_register_u16(&mut self, register: u8) -> Result<u16, AppError> {
+        let mut buf = [0u8; 2];
+        let mut i2c = self
+            .i2c
+            .lock()
+            .map_err(|_| AppError::I2c("i2c bus lock poisoned".to_string()))?;
+        i2c.write_read(self.address, &[register], &mut buf)
+            .map_err(|_| AppError::I2c(format!("read from register {register:#04x} failed")))?;
+        Ok(u16::from_be_bytes(buf))
+    }
+}
+
+impl<I2C, D> SensorDriver for I2cSensorDriver<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    fn init_default(&mut self) -> Result<(), AppError> {
+        let model_id = self.read_register(REG_IDENTIFICATION_MODEL_ID)?;
+        if model_id != EXPECTED_MODEL_ID {
+            return Err(AppError::Sensor(format!(
+                "unexpected VL53L0X model id: {model_id:#04x}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn set_address(&mut self, new_address: u8) -> Result<(), AppError> {
+        self.write_register(REG_I2C_SLAVE_DEVICE_ADDRESS, new_address & 0x7F)?;
+        self.address = new_address;
+        Ok(())
+    }
+
+    fn verify(&mut self) -> Result<(), AppError> {
+        self.read_register(REG_IDENTIFICATION_MODEL_ID).map(|_| ())
+    }
+
+    fn start_ranging(&mut self) -> Result<(), AppError> {
+        self.write_register(REG_SYSRANGE_START, 0x01)
+    }
+
+    fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
+        loop {
+            let status = self.read_register(REG_RESULT_RANGE_STATUS)?;
+            if status & RANGE_STATUS_COMPLETE_BIT != 0 {
+                break;
+            }
+            self.delay.delay_ms(1);
+        }
+
+        let distance_mm = self.read_register_u16(REG_RESULT_RANGE_MM_HIGH)?;
+        let device_status = self.read_register(REG_RESULT_RANGE_STATUS)?;
+
+        // Re-arm for the next measurement.
+        self.write_register(REG_SYSRANGE_START, 0x01)?;
+
+        Ok(DistanceMeasurement {
+            distance_mm,
+            range_status: range_status_from_device_status(device_status),
+        })
+    }
+}
+
+fn range_status_from_device_status(status: u8) -> SensorRangeStatus {
+    match (status >> 3) & 0x0F {
+        0x0B => SensorRangeStatus::Valid,
+        0x01..=0x03 => SensorRangeStatus::SignalFailure,
+        0x04 => SensorRangeStatus::OutOfBounds,
+        0x05 => SensorRangeStatus::HardwareFailure,
+        0x06 => SensorRangeStatus::SigmaFailure,
+        0x07 => SensorRangeStatus::WrapCheckFail,
+        _ => SensorRangeStatus::None,
+    }
+}
+
+/// Factory that owns the shared I2C bus, the XSHUT `OutputPin` used to
+/// reset the sensor before reassigning its address, and the delay source
+/// every driver it creates borrows.
+pub struct I2cSensorFactory<I2C, D, X> {
+    i2c: Arc<Mutex<I2C>>,
+    delay: D,
+    xshut_pin: X,
+}
+
+impl<I2C, D, X> I2cSensorFactory<I2C, D, X>
+where
+    I2C: I2c,
+    D: DelayNs + Clone,
+    X: OutputPin,
+{
+    pub fn new(i2c: I2C, delay: D, xshut_pin: X) -> Self {
+        Self {
+            i2c: Arc::new(Mutex::new(i2c)),
+            delay,
+            xshut_pin,
+        }
+    }
+}
+
+impl<I2C, D, X> SensorDriverFactory for I2cSensorFactory<I2C, D, X>
+where
+    I2C: I2c,
+    D: DelayNs + Clone,
+    X: OutputPin,
+{
+    type Driver = I2cSensorDriver<I2C, D>;
+
+    fn create_default(&mut self) -> Result<Self::Driver, AppError> {
+        Ok(I2cSensorDriver {
+            i2c: Arc::clone(&self.i2c),
+            delay: self.delay.clone(),
+            address: crate::sensor::DEFAULT_I2C_ADDRESS_7BIT,
+        })
+    }
+
+    fn create_for_address(&mut self, address: u8) -> Result<Self::Driver, AppError> {
+        // Hold the sensor in reset, then release it so it boots back at the
+        // default address before we reassign it - the same XSHUT dance
+        // `bus::xshut::allocate_addresses` does for the rppal-backed driver.
+        self.xshut_pin
+            .set_low()
+            .map_err(|_| AppError::Gpio("failed to drive XSHUT low".to_string()))?;
+        self.delay.delay_ms(5);
+        self.xshut_pin
+            .set_high()
+            .map_err(|_| AppError::Gpio("failed to drive XSHUT high".to_string()))?;
+        self.delay.delay_ms(5);
+
+        let mut driver = self.create_default()?;
+        driver.set_address(address)?;
+        Ok(driver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_status_decodes_common_device_codes() {
+        assert_eq!(
+            range_status_from_device_status(0x0B << 3),
+            SensorRangeStatus::Valid
+        );
+        assert_eq!(
+            range_status_from_device_status(0x05 << 3),
+            SensorRangeStatus::HardwareFailure
+        );
+        assert_eq!(
+            range_status_from_device_status(0xFF),
+            SensorRangeStatus::None
+        );
+    }
+}
@@ -1,5 +1,11 @@
 use crate::error::AppError;
 use crate::sensor::{DistanceMeasurement, SensorDriver, SensorDriverFactory, SensorRangeStatus};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read as _;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
 
 #[derive(Debug, Clone, Copy)]
 pub struct MockSensorBehavior {
@@ -10,6 +16,9 @@ pub struct MockSensorBehavior {
     pub read_distance_ok: bool,
     pub distance_mm: u16,
     pub range_status: SensorRangeStatus,
+    /// Number of `read_distance_nb` calls that return `WouldBlock` before a
+    /// reading latches.
+    pub would_block_cycles: u32,
 }
 
 impl MockSensorBehavior {
@@ -22,6 +31,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
         }
     }
 
@@ -34,6 +44,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
         }
     }
 
@@ -46,6 +57,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
         }
     }
 
@@ -58,6 +70,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
         }
     }
 
@@ -70,6 +83,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm,
             range_status,
+            would_block_cycles: 0,
         }
     }
 
@@ -82,6 +96,7 @@ impl MockSensorBehavior {
             read_distance_ok: true,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
         }
     }
 
@@ -94,6 +109,80 @@ impl MockSensorBehavior {
             read_distance_ok: false,
             distance_mm: 0,
             range_status: SensorRangeStatus::Valid,
+            would_block_cycles: 0,
+        }
+    }
+
+    /// Like `with_reading`, but `read_distance_nb` returns `WouldBlock` for
+    /// `would_block_cycles` polls before the reading latches.
+    pub fn with_would_block(
+        distance_mm: u16,
+        range_status: SensorRangeStatus,
+        would_block_cycles: u32,
+    ) -> Self {
+        Self {
+            would_block_cycles,
+            ..Self::with_reading(distance_mm, range_status)
+        }
+    }
+}
+
+/// One recorded frame in a fixture's timeline, played back on successive
+/// `read_distance` calls.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockSensorFrame {
+    pub distance_mm: u16,
+    pub range_status: SensorRangeStatus,
+    #[serde(default = "default_true")]
+    pub create_ok: bool,
+    #[serde(default = "default_true")]
+    pub read_ok: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One sensor's recorded timeline in a fixture file, keyed by I2C address so
+/// replay lines up with real `SensorInfo`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockSensorFixture {
+    pub i2c_address: u8,
+    pub frames: Vec<MockSensorFrame>,
+}
+
+#[derive(Debug, Error)]
+pub enum MockFixtureError {
+    #[error("failed to read fixture file: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("fixture file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("failed to parse fixture file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("fixture has no frames for address {0:#04x}")]
+    EmptyTimeline(u8),
+}
+
+/// Per-sensor cursor into a fixture's timeline. Advances on every
+/// `read_distance`, holding the last frame once the timeline is exhausted.
+#[derive(Debug)]
+struct MockSensorTimeline {
+    frames: Vec<MockSensorFrame>,
+    cursor: usize,
+}
+
+impl MockSensorTimeline {
+    fn new(frames: Vec<MockSensorFrame>) -> Self {
+        Self { frames, cursor: 0 }
+    }
+
+    fn current(&self) -> &MockSensorFrame {
+        &self.frames[self.cursor]
+    }
+
+    fn advance(&mut self) {
+        if self.cursor + 1 < self.frames.len() {
+            self.cursor += 1;
         }
     }
 }
@@ -101,6 +190,7 @@ impl MockSensorBehavior {
 pub struct MockSensorFactory {
     behaviors: Vec<MockSensorBehavior>,
     next_index: usize,
+    timelines: HashMap<u8, Arc<Mutex<MockSensorTimeline>>>,
 }
 
 impl MockSensorFactory {
@@ -108,9 +198,44 @@ impl MockSensorFactory {
         Self {
             behaviors,
             next_index: 0,
+            timelines: HashMap::new(),
         }
     }
 
+    /// Loads a replay fixture: a JSON array of `MockSensorFixture`, each
+    /// sensor keyed by `i2c_address` with an ordered frame timeline.
+    /// `.gz`-suffixed paths are transparently decompressed.
+    pub fn from_fixture(path: impl AsRef<Path>) -> Result<Self, MockFixtureError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let contents = if path.extension().and_then(|ext| ext.to_str()) == Some("gz") {
+            let mut decoder = flate2::read::GzDecoder::new(&bytes[..]);
+            let mut decompressed = String::new();
+            decoder.read_to_string(&mut decompressed)?;
+            decompressed
+        } else {
+            String::from_utf8(bytes)?
+        };
+
+        let fixtures: Vec<MockSensorFixture> = serde_json::from_str(&contents)?;
+        let mut timelines = HashMap::with_capacity(fixtures.len());
+        for fixture in fixtures {
+            if fixture.frames.is_empty() {
+                return Err(MockFixtureError::EmptyTimeline(fixture.i2c_address));
+            }
+            timelines.insert(
+                fixture.i2c_address,
+                Arc::new(Mutex::new(MockSensorTimeline::new(fixture.frames))),
+            );
+        }
+
+        Ok(Self {
+            behaviors: Vec::new(),
+            next_index: 0,
+            timelines,
+        })
+    }
+
     fn next_behavior(&mut self) -> MockSensorBehavior {
         let behavior = self
             .behaviors
@@ -122,32 +247,57 @@ impl MockSensorFactory {
     }
 }
 
+enum MockSensorSource {
+    Static(MockSensorBehavior),
+    Timeline(Arc<Mutex<MockSensorTimeline>>),
+}
+
 pub struct MockSensorDriver {
-    behavior: MockSensorBehavior,
+    source: MockSensorSource,
+    would_block_remaining: u32,
+}
+
+impl MockSensorDriver {
+    pub fn new(behavior: MockSensorBehavior) -> Self {
+        Self {
+            would_block_remaining: behavior.would_block_cycles,
+            source: MockSensorSource::Static(behavior),
+        }
+    }
+
+    fn from_timeline(timeline: Arc<Mutex<MockSensorTimeline>>) -> Self {
+        Self {
+            source: MockSensorSource::Timeline(timeline),
+            would_block_remaining: 0,
+        }
+    }
 }
 
 impl SensorDriver for MockSensorDriver {
     fn init_default(&mut self) -> Result<(), AppError> {
-        if self.behavior.init_ok {
-            Ok(())
-        } else {
-            Err(AppError::Sensor("mock init failed".to_string()))
+        match &self.source {
+            MockSensorSource::Static(behavior) if !behavior.init_ok => {
+                Err(AppError::Sensor("mock init failed".to_string()))
+            }
+            _ => Ok(()),
         }
     }
 
     fn set_address(&mut self, _new_address: u8) -> Result<(), AppError> {
-        if self.behavior.set_address_ok {
-            Ok(())
-        } else {
-            Err(AppError::Sensor("mock set address failed".to_string()))
+        match &self.source {
+            MockSensorSource::Static(behavior) if !behavior.set_address_ok => {
+                Err(AppError::Sensor("mock set address failed".to_string()))
+            }
+            _ => Ok(()),
         }
     }
 
     fn verify(&mut self) -> Result<(), AppError> {
-        if self.behavior.verify_ok {
-            Ok(())
-        } else {
-            Err(AppError::Sensor("mock verify failed".to_string()))
+        match &self.source {
+            MockSensorSource::Static(behavior) if !behavior.verify_ok => {
+                Err(AppError::Sensor("mock verify failed".to_string()))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -157,15 +307,40 @@ impl SensorDriver for MockSensorDriver {
     }
 
     fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
-        if self.behavior.read_distance_ok {
-            Ok(DistanceMeasurement {
-                distance_mm: self.behavior.distance_mm,
-                range_status: self.behavior.range_status,
-            })
-        } else {
-            Err(AppError::Sensor("mock read distance failed".to_string()))
+        match &self.source {
+            MockSensorSource::Static(behavior) => {
+                if behavior.read_distance_ok {
+                    Ok(DistanceMeasurement {
+                        distance_mm: behavior.distance_mm,
+                        range_status: behavior.range_status,
+                    })
+                } else {
+                    Err(AppError::Sensor("mock read distance failed".to_string()))
+                }
+            }
+            MockSensorSource::Timeline(timeline) => {
+                let mut timeline = timeline.lock().unwrap_or_else(|p| p.into_inner());
+                let frame = timeline.current().clone();
+                timeline.advance();
+                if frame.read_ok {
+                    Ok(DistanceMeasurement {
+                        distance_mm: frame.distance_mm,
+                        range_status: frame.range_status,
+                    })
+                } else {
+                    Err(AppError::Sensor("mock read distance failed".to_string()))
+                }
+            }
         }
     }
+
+    fn read_distance_nb(&mut self) -> nb::Result<DistanceMeasurement, AppError> {
+        if self.would_block_remaining > 0 {
+            self.would_block_remaining -= 1;
+            return Err(nb::Error::WouldBlock);
+        }
+        self.read_distance().map_err(nb::Error::Other)
+    }
 }
 
 impl SensorDriverFactory for MockSensorFactory {
@@ -174,14 +349,28 @@ impl SensorDriverFactory for MockSensorFactory {
     fn create_default(&mut self) -> Result<Self::Driver, AppError> {
         let behavior = self.next_behavior();
         if behavior.create_ok {
-            Ok(MockSensorDriver { behavior })
+            Ok(MockSensorDriver::new(behavior))
         } else {
             Err(AppError::Sensor("mock create failed".to_string()))
         }
     }
 
-    fn create_for_address(&mut self, _address: u8) -> Result<Self::Driver, AppError> {
-        self.create_default()
+    fn create_for_address(&mut self, address: u8) -> Result<Self::Driver, AppError> {
+        let Some(timeline) = self.timelines.get(&address) else {
+            return self.create_default();
+        };
+        let timeline = Arc::clone(timeline);
+        let create_ok = timeline
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .current()
+            .create_ok;
+
+        if create_ok {
+            Ok(MockSensorDriver::from_timeline(timeline))
+        } else {
+            Err(AppError::Sensor("mock create failed".to_string()))
+        }
     }
 }
 
@@ -189,10 +378,21 @@ impl SensorDriverFactory for MockSensorFactory {
 mod tests {
     use super::*;
 
+    #[test]
+    fn read_distance_nb_would_block_before_latching() {
+        let behavior = MockSensorBehavior::with_would_block(321, SensorRangeStatus::Valid, 2);
+        let mut driver = MockSensorDriver::new(behavior);
+
+        assert!(matches!(driver.read_distance_nb(), Err(nb::Error::WouldBlock)));
+        assert!(matches!(driver.read_distance_nb(), Err(nb::Error::WouldBlock)));
+        let measurement = driver.read_distance_nb().expect("reading latches");
+        assert_eq!(measurement.distance_mm, 321);
+    }
+
     #[test]
     fn read_distance_returns_measurement() {
         let behavior = MockSensorBehavior::with_reading(123, SensorRangeStatus::Valid);
-        let mut driver = MockSensorDriver { behavior };
+        let mut driver = MockSensorDriver::new(behavior);
 
         let measurement = driver.read_distance().expect("read distance ok");
 
@@ -203,10 +403,81 @@ mod tests {
     #[test]
     fn read_distance_can_fail() {
         let behavior = MockSensorBehavior::fail_read_distance();
-        let mut driver = MockSensorDriver { behavior };
+        let mut driver = MockSensorDriver::new(behavior);
 
         let err = driver.read_distance().unwrap_err();
 
         assert_eq!(err.to_string(), "sensor error: mock read distance failed");
     }
+
+    fn fixture_json() -> &'static str {
+        r#"[
+            {
+                "i2c_address": 48,
+                "frames": [
+                    { "distance_mm": 1500, "range_status": "Valid" },
+                    { "distance_mm": 900, "range_status": "Valid" },
+                    { "distance_mm": 900, "range_status": "Valid" }
+                ]
+            }
+        ]"#
+    }
+
+    fn write_fixture(contents: &str, suffix: &str) -> std::path::PathBuf {
+        let unique = std::process::id();
+        let path = std::env::temp_dir().join(format!("mariam-mock-fixture-{unique}{suffix}"));
+        std::fs::write(&path, contents).expect("write fixture");
+        path
+    }
+
+    #[test]
+    fn from_fixture_replays_timeline_per_address() {
+        let path = write_fixture(fixture_json(), ".json");
+        let mut factory = MockSensorFactory::from_fixture(&path).expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let mut driver = factory.create_for_address(0x30).expect("create ok");
+
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 1500);
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 900);
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 900);
+    }
+
+    #[test]
+    fn from_fixture_holds_last_frame_past_the_end() {
+        let path = write_fixture(fixture_json(), "-hold.json");
+        let mut factory = MockSensorFactory::from_fixture(&path).expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let mut driver = factory.create_for_address(0x30).expect("create ok");
+        for _ in 0..10 {
+            let _ = driver.read_distance();
+        }
+
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 900);
+    }
+
+    #[test]
+    fn from_fixture_falls_back_to_default_behavior_for_unknown_address() {
+        let path = write_fixture(fixture_json(), "-fallback.json");
+        let mut factory = MockSensorFactory::from_fixture(&path).expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let mut driver = factory.create_for_address(0x31).expect("create ok");
+
+        let measurement = driver.read_distance().expect("default behavior reads ok");
+        assert_eq!(measurement.range_status, SensorRangeStatus::Valid);
+    }
+
+    #[test]
+    fn from_fixture_rejects_empty_timelines() {
+        let path = write_fixture(
+            r#"[{ "i2c_address": 48, "frames": [] }]"#,
+            "-empty.json",
+        );
+        let result = MockSensorFactory::from_fixture(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(result, Err(MockFixtureError::EmptyTimeline(0x30))));
+    }
 }
@@ -1,7 +1,14 @@
 use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
+pub mod async_driver;
+pub mod i2c_hal;
 pub mod mock;
+pub mod one_wire;
+pub mod replay;
 pub mod vl53l1x;
+pub mod vl53l1x_async;
 
 pub type SensorId = u32;
 
@@ -18,19 +25,111 @@ pub struct SensorConfig {
 
 #[derive(Debug, Clone)]
 pub enum SensorStatus {
+    /// Addressed and ranging, but not yet confirmed by a post-address
+    /// self-test - distinct from `Ready` so subscribers can tell "ACKed its
+    /// address" from "confirmed returning good data".
+    Provisional,
     Ready,
-    Error { message: String },
+    Error { error: DeviceSensorError },
+}
+
+/// Why a sensor ended up in [`SensorStatus::Error`], classified at the
+/// point the driver layer gives up on it so subscribers (the `/api/sensors`
+/// handler, SCPI, the watchdog) don't have to re-derive it by pattern
+/// matching free-form error text. `Other` is the fallback for failures
+/// [`From<AppError>`] can't confidently classify from the error variant
+/// alone - callers that need a [`crate::api::responses::SensorErrorCode`]
+/// still substring-match its message, same as before this type existed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceSensorError {
+    Timeout,
+    OutOfRange,
+    InvalidReading,
+    NoResponse,
+    Other(String),
+}
+
+impl fmt::Display for DeviceSensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceSensorError::Timeout => write!(f, "timeout"),
+            DeviceSensorError::OutOfRange => write!(f, "reading out of range"),
+            DeviceSensorError::InvalidReading => write!(f, "invalid reading"),
+            DeviceSensorError::NoResponse => write!(f, "no response"),
+            DeviceSensorError::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<AppError> for DeviceSensorError {
+    /// XSHUT/GPIO failures mean the sensor never ACKed at all, so those
+    /// classify as `NoResponse` directly. I2C/sensor-driver failures carry
+    /// a free-form message from `rppal`/`vl53l1x_uld`, so we look for the
+    /// same keywords the old message-matching heuristic used before
+    /// falling back to `Other` with the full error text preserved.
+    fn from(err: AppError) -> Self {
+        match &err {
+            AppError::Gpio(_) | AppError::Xshut(_) => DeviceSensorError::NoResponse,
+            AppError::I2c(message) | AppError::Sensor(message) => {
+                let message_lower = message.to_lowercase();
+                if message_lower.contains("timeout") {
+                    DeviceSensorError::Timeout
+                } else if message_lower.contains("range") {
+                    DeviceSensorError::OutOfRange
+                } else if message_lower.contains("invalid") {
+                    DeviceSensorError::InvalidReading
+                } else {
+                    DeviceSensorError::Other(err.to_string())
+                }
+            }
+            AppError::InvalidAddress(_) | AppError::AddressAllocationOverflow | AppError::StateLock => {
+                DeviceSensorError::Other(err.to_string())
+            }
+        }
+    }
+}
+
+/// A sensor's physical bus identity. Ranging sensors ([`vl53l1x`]) are
+/// addressed over I2C; 1-Wire temperature sensors ([`one_wire`]) have no
+/// I2C address at all and are instead identified by their ROM id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SensorAddress {
+    I2c(u8),
+    OneWire(String),
+}
+
+impl SensorAddress {
+    /// Hex (`0x30`) for I2C, or the bare ROM id for 1-Wire - the same
+    /// string the `/api/sensors` response already shows for I2C addresses.
+    pub fn display(&self) -> String {
+        match self {
+            SensorAddress::I2c(address) => format!("0x{address:02x}"),
+            SensorAddress::OneWire(rom_id) => rom_id.clone(),
+        }
+    }
+
+    /// The 7-bit I2C address, if this sensor is I2C-addressed - `None` for
+    /// 1-Wire sensors, which have no such address to feed an I2C
+    /// `SensorDriverFactory`.
+    pub fn as_i2c(&self) -> Option<u8> {
+        match self {
+            SensorAddress::I2c(address) => Some(*address),
+            SensorAddress::OneWire(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SensorInfo {
     pub sensor_id: SensorId,
+    /// XSHUT pin used during I2C discovery; unused (`0`) for 1-Wire sensors,
+    /// which aren't XSHUT-sequenced.
     pub xshut_pin: u8,
-    pub i2c_address: u8,
+    pub address: SensorAddress,
     pub status: SensorStatus,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SensorRangeStatus {
     Valid,
     SigmaFailure,
@@ -69,6 +168,14 @@ pub trait SensorDriver {
     /// Start continuous ranging mode. Must be called after init before reading distances.
     fn start_ranging(&mut self) -> Result<(), AppError>;
     fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError>;
+
+    /// Non-blocking variant of `read_distance`: returns `nb::Error::WouldBlock`
+    /// until a ranging result has latched. The default implementation treats
+    /// the driver as always-ready and defers straight to `read_distance`;
+    /// drivers with a real integration-time budget should override this.
+    fn read_distance_nb(&mut self) -> nb::Result<DistanceMeasurement, AppError> {
+        self.read_distance().map_err(nb::Error::Other)
+    }
 }
 
 pub trait SensorDriverFactory {
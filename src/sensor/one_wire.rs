@@ -0,0 +1,186 @@
+//! 1-Wire temperature sensor backend, modeled on templog's `OneWireSensor`.
+//!
+//! Unlike the VL53L1X ranging sensors in [`crate::sensor::vl53l1x`], a
+//! 1-Wire device (e.g. a DS18B20) isn't addressed over I2C or sequenced
+//! through XSHUT - the kernel's `w1` subsystem enumerates it under
+//! `/sys/bus/w1/devices/<rom-id>/w1_slave` and a read is just "cat that
+//! file and parse the two-line format it prints". [`OneWireSensor`] reads
+//! that file directly rather than going through a crate, since the format
+//! is small and stable and this avoids pulling in a GPIO-bitbanged 1-Wire
+//! master for a sysfs-backed device.
+//!
+//! The read runs on a helper thread so a wedged sysfs mount (a real failure
+//! mode on some overlay/network filesystems) can be bounded by `timeout`
+//! instead of hanging the caller indefinitely.
+
+use crate::error::AppError;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default base directory the Linux `w1` subsystem exposes slave devices
+/// under.
+pub const DEFAULT_W1_BASE_DIR: &str = "/sys/bus/w1/devices";
+
+/// Reads and parses one DS18B20-style 1-Wire temperature sensor.
+#[derive(Debug, Clone)]
+pub struct OneWireSensor {
+    base_dir: PathBuf,
+    rom_id: String,
+    timeout: Duration,
+}
+
+impl OneWireSensor {
+    pub fn new(base_dir: impl Into<PathBuf>, rom_id: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            rom_id: rom_id.into(),
+            timeout,
+        }
+    }
+
+    /// The 1-Wire ROM id this sensor reads under `base_dir`, e.g.
+    /// `"28-0316027f64ff"`.
+    pub fn rom_id(&self) -> &str {
+        &self.rom_id
+    }
+
+    fn slave_path(&self) -> PathBuf {
+        self.base_dir.join(&self.rom_id).join("w1_slave")
+    }
+
+    /// Reads the current temperature in degrees Celsius.
+    ///
+    /// Error messages deliberately mirror the substrings
+    /// [`crate::sensor::DeviceSensorError`]'s `From<AppError>` impl already
+    /// looks for - `"timeout"` for a stalled read, `"invalid"` for a bad CRC
+    /// or unparsable payload, and anything else (notably a missing slave
+    /// file) falling through to `NoResponse`.
+    pub fn read_celsius(&self) -> Result<f64, AppError> {
+        let millidegrees = self.read_millidegrees()?;
+        Ok(millidegrees as f64 / 1000.0)
+    }
+
+    fn read_millidegrees(&self) -> Result<i32, AppError> {
+        let contents = self.read_slave_file()?;
+        parse_w1_slave(&contents)
+            .map_err(|reason| AppError::Sensor(format!("1-Wire {}: {reason}", self.rom_id)))
+    }
+
+    fn read_slave_file(&self) -> Result<String, AppError> {
+        let path = self.slave_path();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let read_path = path.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(std::fs::read_to_string(read_path));
+        });
+
+        match rx.recv_timeout(self.timeout) {
+            Ok(Ok(contents)) => Ok(contents),
+            Ok(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => Err(AppError::Sensor(
+                format!("1-Wire slave file not found: {}", path.display()),
+            )),
+            Ok(Err(err)) => Err(AppError::Sensor(format!(
+                "1-Wire read error on {}: {err}",
+                path.display()
+            ))),
+            Err(_) => Err(AppError::Sensor(format!(
+                "1-Wire read timeout after {:?} on {}",
+                self.timeout,
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Parses the kernel `w1_slave` two-line format:
+///
+/// ```text
+/// 4e 01 4b 46 7f ff 0c 10 5c : crc=5c YES
+/// 4e 01 4b 46 7f ff 0c 10 5c t=20500
+/// ```
+///
+/// Returns the temperature in millidegrees Celsius, or an error string
+/// containing `"invalid"` if the CRC line reads `NO` or either line is
+/// malformed.
+fn parse_w1_slave(contents: &str) -> Result<i32, String> {
+    let mut lines = contents.lines();
+    let crc_line = lines
+        .next()
+        .ok_or_else(|| "invalid reading: empty w1_slave file".to_string())?;
+    if !crc_line.trim_end().ends_with("YES") {
+        return Err("invalid reading: CRC check failed (NO)".to_string());
+    }
+
+    let data_line = lines
+        .next()
+        .ok_or_else(|| "invalid reading: missing temperature line".to_string())?;
+    let raw = data_line
+        .rsplit("t=")
+        .next()
+        .ok_or_else(|| "invalid reading: missing t= field".to_string())?;
+    raw.trim()
+        .parse::<i32>()
+        .map_err(|_| "invalid reading: non-numeric temperature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_slave_file(dir: &Path, rom_id: &str, contents: &str) {
+        let device_dir = dir.join(rom_id);
+        fs::create_dir_all(&device_dir).expect("create device dir");
+        fs::write(device_dir.join("w1_slave"), contents).expect("write fixture");
+    }
+
+    #[test]
+    fn parses_valid_reading_into_celsius() {
+        let dir = std::env::temp_dir().join(format!(
+            "mariam-flow-w1-valid-{}",
+            std::process::id()
+        ));
+        write_slave_file(
+            &dir,
+            "28-0316027f64ff",
+            "4e 01 4b 46 7f ff 0c 10 5c : crc=5c YES\n4e 01 4b 46 7f ff 0c 10 5c t=20500\n",
+        );
+
+        let sensor = OneWireSensor::new(&dir, "28-0316027f64ff", Duration::from_secs(1));
+        assert_eq!(sensor.read_celsius().expect("read"), 20.5);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_slave_file_reports_no_response() {
+        let dir = std::env::temp_dir().join(format!(
+            "mariam-flow-w1-missing-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+
+        let sensor = OneWireSensor::new(&dir, "28-does-not-exist", Duration::from_secs(1));
+        let err = sensor.read_celsius().expect_err("should not find file");
+        let message = err.to_string();
+        assert!(!message.contains("timeout"));
+        assert!(!message.contains("invalid"));
+        assert!(message.contains("not found"));
+    }
+
+    #[test]
+    fn bad_crc_line_reports_invalid_reading() {
+        let dir = std::env::temp_dir().join(format!("mariam-flow-w1-crc-{}", std::process::id()));
+        write_slave_file(
+            &dir,
+            "28-0316027f64ff",
+            "4e 01 4b 46 7f ff 0c 10 5c : crc=5c NO\n4e 01 4b 46 7f ff 0c 10 5c t=20500\n",
+        );
+
+        let sensor = OneWireSensor::new(&dir, "28-0316027f64ff", Duration::from_secs(1));
+        let err = sensor.read_celsius().expect_err("bad crc should fail");
+        assert!(err.to_string().contains("invalid"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
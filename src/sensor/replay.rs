@@ -0,0 +1,480 @@
+//! Record-and-replay driver for offline model comparison.
+//!
+//! [`MockSensorFactory::from_fixture`](crate::sensor::mock::MockSensorFactory)
+//! replays a hand-authored, `i2c_address`-keyed fixture; it's built for
+//! writing test cases by hand, not for capturing a real queue. This module
+//! is the other half: [`SensorReadingRecorder`] appends every cycle's
+//! [`SensorReading`]s to newline-delimited JSON (one reading per line,
+//! optionally gzip-compressed), and [`ReplaySensorFactory`] streams a
+//! recorded fixture back through [`SensorDriverFactory`] - including the
+//! original `ReadingStatus::Error`s - so `linear_v1`, `linear_v2`, and
+//! `obstruction_count_v1` can be driven against byte-identical data.
+
+use crate::error::AppError;
+use crate::sensor::{
+    DistanceMeasurement, SensorDriver, SensorDriverFactory, SensorId, SensorRangeStatus,
+};
+use crate::state::{ReadingStatus, SensorReading};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read as _, Write as _};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("failed to read fixture file: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("fixture file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("failed to parse fixture line: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("fixture has no frames for sensor {0}")]
+    EmptyTimeline(SensorId),
+}
+
+/// Appends recorded cycles to newline-delimited JSON, one [`SensorReading`]
+/// per line. `.gz`-suffixed paths are transparently gzip-compressed.
+pub struct SensorReadingRecorder {
+    writer: Box<dyn std::io::Write + Send>,
+}
+
+impl SensorReadingRecorder {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let file = std::fs::File::create(path.as_ref())?;
+        let writer: Box<dyn std::io::Write + Send> =
+            if path.as_ref().extension().and_then(|ext| ext.to_str()) == Some("gz") {
+                Box::new(flate2::write::GzEncoder::new(
+                    file,
+                    flate2::Compression::default(),
+                ))
+            } else {
+                Box::new(file)
+            };
+        Ok(Self { writer })
+    }
+
+    /// Appends one cycle's readings, each as its own line.
+    pub fn record_cycle(&mut self, readings: &[SensorReading]) -> Result<(), std::io::Error> {
+        for reading in readings {
+            let line = serde_json::to_string(reading)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            writeln!(self.writer, "{line}")?;
+        }
+        self.writer.flush()
+    }
+}
+
+/// How a [`ReplaySensorFactory`] paces successive reads of a recorded
+/// timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayPacing {
+    /// Return the next frame immediately - the default, and what offline
+    /// model comparison wants.
+    #[default]
+    AsFastAsPossible,
+    /// Sleep for the recorded gap between a sensor's consecutive frames
+    /// before returning the next one, reproducing the original cadence.
+    RealTime,
+}
+
+/// Per-sensor cursor into a recorded timeline. Advances on every
+/// `read_distance`, holding the last frame once the timeline is exhausted.
+#[derive(Debug)]
+struct ReplayTimeline {
+    frames: Vec<SensorReading>,
+    cursor: usize,
+}
+
+impl ReplayTimeline {
+    fn current(&self) -> &SensorReading {
+        &self.frames[self.cursor]
+    }
+
+    fn advance(&mut self) -> Option<Duration> {
+        if self.cursor + 1 >= self.frames.len() {
+            return None;
+        }
+        let gap = self.frames[self.cursor + 1]
+            .timestamp
+            .duration_since(self.frames[self.cursor].timestamp)
+            .ok();
+        self.cursor += 1;
+        gap
+    }
+}
+
+/// Replays a recorded fixture back through [`SensorDriverFactory`], keyed by
+/// `sensor_id` (not `i2c_address` - recordings don't assume addresses are
+/// reassigned the same way on replay, so the caller supplies the mapping).
+pub struct ReplaySensorFactory {
+    pacing: ReplayPacing,
+    address_to_sensor: HashMap<u8, SensorId>,
+    timelines: HashMap<SensorId, Arc<Mutex<ReplayTimeline>>>,
+    next_index: usize,
+    ordered_sensor_ids: Vec<SensorId>,
+}
+
+impl ReplaySensorFactory {
+    /// Loads a fixture recorded by [`SensorReadingRecorder`]: newline-
+    /// delimited `SensorReading`s, grouped into a per-`sensor_id` timeline in
+    /// the order they appear. `.gz`-suffixed paths are transparently
+    /// decompressed. `address_to_sensor` maps the `i2c_address` a caller will
+    /// request through [`SensorDriverFactory::create_for_address`] back to
+    /// the recorded `sensor_id`.
+    pub fn from_fixture(
+        path: impl AsRef<Path>,
+        address_to_sensor: HashMap<u8, SensorId>,
+        pacing: ReplayPacing,
+    ) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let contents = if path.extension().and_then(|ext| ext.to_str()) == Some("gz") {
+            let mut decoder = flate2::read::GzDecoder::new(&bytes[..]);
+            let mut decompressed = String::new();
+            decoder.read_to_string(&mut decompressed)?;
+            decompressed
+        } else {
+            String::from_utf8(bytes)?
+        };
+
+        let mut frames_by_sensor: HashMap<SensorId, Vec<SensorReading>> = HashMap::new();
+        let mut ordered_sensor_ids = Vec::new();
+        for line in BufReader::new(contents.as_bytes()).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reading: SensorReading = serde_json::from_str(&line)?;
+            frames_by_sensor
+                .entry(reading.sensor_id)
+                .or_insert_with(|| {
+                    ordered_sensor_ids.push(reading.sensor_id);
+                    Vec::new()
+                })
+                .push(reading);
+        }
+
+        let mut timelines = HashMap::with_capacity(frames_by_sensor.len());
+        for (sensor_id, frames) in frames_by_sensor {
+            if frames.is_empty() {
+                return Err(ReplayError::EmptyTimeline(sensor_id));
+            }
+            timelines.insert(
+                sensor_id,
+                Arc::new(Mutex::new(ReplayTimeline { frames, cursor: 0 })),
+            );
+        }
+
+        Ok(Self {
+            pacing,
+            address_to_sensor,
+            timelines,
+            next_index: 0,
+            ordered_sensor_ids,
+        })
+    }
+
+    fn driver_for(&self, sensor_id: SensorId) -> Result<ReplaySensorDriver, AppError> {
+        let timeline = self
+            .timelines
+            .get(&sensor_id)
+            .ok_or(AppError::Sensor(format!(
+                "no recorded timeline for sensor {sensor_id}"
+            )))?;
+        Ok(ReplaySensorDriver {
+            timeline: Arc::clone(timeline),
+            pacing: self.pacing,
+        })
+    }
+}
+
+impl SensorDriverFactory for ReplaySensorFactory {
+    type Driver = ReplaySensorDriver;
+
+    fn create_default(&mut self) -> Result<Self::Driver, AppError> {
+        let sensor_id = *self
+            .ordered_sensor_ids
+            .get(self.next_index)
+            .ok_or_else(|| AppError::Sensor("replay fixture exhausted".to_string()))?;
+        self.next_index += 1;
+        self.driver_for(sensor_id)
+    }
+
+    fn create_for_address(&mut self, address: u8) -> Result<Self::Driver, AppError> {
+        let sensor_id = *self
+            .address_to_sensor
+            .get(&address)
+            .ok_or(AppError::InvalidAddress(address))?;
+        self.driver_for(sensor_id)
+    }
+}
+
+pub struct ReplaySensorDriver {
+    timeline: Arc<Mutex<ReplayTimeline>>,
+    pacing: ReplayPacing,
+}
+
+impl SensorDriver for ReplaySensorDriver {
+    fn init_default(&mut self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    fn set_address(&mut self, _new_address: u8) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    fn verify(&mut self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    fn start_ranging(&mut self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
+        let mut timeline = self.timeline.lock().unwrap_or_else(|p| p.into_inner());
+        let frame = timeline.current().clone();
+        let gap = timeline.advance();
+        drop(timeline);
+
+        if self.pacing == ReplayPacing::RealTime {
+            if let Some(gap) = gap {
+                std::thread::sleep(gap);
+            }
+        }
+
+        match frame.status {
+            ReadingStatus::Ok { range_status } => Ok(DistanceMeasurement {
+                distance_mm: frame.distance_mm,
+                range_status,
+            }),
+            ReadingStatus::Error { reason } => Err(AppError::Sensor(reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::readings::read_and_store_distances;
+    use crate::estimation::model::{EstimationModel, OccupancyConfig};
+    use crate::sensor::{SensorAddress, SensorInfo, SensorStatus};
+    use crate::state::AppState;
+    use std::sync::RwLock;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[derive(Debug)]
+    struct TestModel {
+        occupancy_config: OccupancyConfig,
+    }
+
+    impl EstimationModel for TestModel {
+        fn compute_wait_time(
+            &self,
+            _obstructions: &[crate::state::SensorObstruction],
+            timestamp: std::time::SystemTime,
+        ) -> crate::state::WaitTimeEstimate {
+            crate::state::WaitTimeEstimate {
+                wait_time_minutes: None,
+                timestamp,
+                status: crate::state::WaitTimeStatus::Degraded,
+                error_code: Some(crate::state::WaitTimeErrorCode::NoData),
+            }
+        }
+
+        fn occupancy_config(&self) -> &OccupancyConfig {
+            &self.occupancy_config
+        }
+    }
+
+    fn fixture_path(suffix: &str) -> std::path::PathBuf {
+        let unique = std::process::id();
+        std::env::temp_dir().join(format!("mariam-replay-fixture-{unique}{suffix}"))
+    }
+
+    fn recorded_cycles() -> Vec<Vec<SensorReading>> {
+        vec![
+            vec![
+                SensorReading {
+                    sensor_id: 1,
+                    distance_mm: 500,
+                    timestamp: UNIX_EPOCH,
+                    status: ReadingStatus::Ok {
+                        range_status: SensorRangeStatus::Valid,
+                    },
+                },
+                SensorReading {
+                    sensor_id: 2,
+                    distance_mm: 0,
+                    timestamp: UNIX_EPOCH,
+                    status: ReadingStatus::Error {
+                        reason: "read failed".to_string(),
+                    },
+                },
+            ],
+            vec![
+                SensorReading {
+                    sensor_id: 1,
+                    distance_mm: 450,
+                    timestamp: UNIX_EPOCH + Duration::from_secs(1),
+                    status: ReadingStatus::Ok {
+                        range_status: SensorRangeStatus::Valid,
+                    },
+                },
+                SensorReading {
+                    sensor_id: 2,
+                    distance_mm: 600,
+                    timestamp: UNIX_EPOCH + Duration::from_secs(1),
+                    status: ReadingStatus::Ok {
+                        range_status: SensorRangeStatus::Valid,
+                    },
+                },
+            ],
+        ]
+    }
+
+    #[test]
+    fn round_trip_through_recorder_and_replay_matches_original_readings() -> Result<(), AppError> {
+        let path = fixture_path(".jsonl");
+        let original_cycles = recorded_cycles();
+
+        {
+            let mut recorder = SensorReadingRecorder::create(&path).expect("create recorder");
+            for cycle in &original_cycles {
+                recorder.record_cycle(cycle).expect("record cycle");
+            }
+        }
+
+        let mut address_to_sensor = HashMap::new();
+        address_to_sensor.insert(0x30, 1);
+        address_to_sensor.insert(0x31, 2);
+        let mut factory = ReplaySensorFactory::from_fixture(
+            &path,
+            address_to_sensor,
+            ReplayPacing::AsFastAsPossible,
+        )
+        .expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let model = TestModel {
+            occupancy_config: OccupancyConfig::default(),
+        };
+        let state = Arc::new(RwLock::new(AppState::new()));
+        let mut sensors = vec![
+            SensorInfo {
+                sensor_id: 1,
+                xshut_pin: 17,
+                address: SensorAddress::I2c(0x30),
+                status: SensorStatus::Ready,
+            },
+            SensorInfo {
+                sensor_id: 2,
+                xshut_pin: 27,
+                address: SensorAddress::I2c(0x31),
+                status: SensorStatus::Ready,
+            },
+        ];
+
+        for cycle in &original_cycles {
+            let replayed = read_and_store_distances(&mut factory, &mut sensors, &state, &model)?;
+
+            assert_eq!(replayed.len(), cycle.len());
+            for (replayed, original) in replayed.iter().zip(cycle) {
+                assert_eq!(replayed.sensor_id, original.sensor_id);
+                assert_eq!(replayed.distance_mm, original.distance_mm);
+                // Recorded errors come back through the driver as
+                // `AppError::Sensor`, which `read_and_store_distances` wraps.
+                let expected_status = match &original.status {
+                    ReadingStatus::Error { reason } => ReadingStatus::Error {
+                        reason: format!("read failed: sensor error: {reason}"),
+                    },
+                    status => status.clone(),
+                };
+                assert_eq!(replayed.status, expected_status);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_compressed_fixture_round_trips() -> Result<(), AppError> {
+        let path = fixture_path("-gz.jsonl.gz");
+        let original_cycles = recorded_cycles();
+
+        {
+            let mut recorder = SensorReadingRecorder::create(&path).expect("create recorder");
+            for cycle in &original_cycles {
+                recorder.record_cycle(cycle).expect("record cycle");
+            }
+        }
+
+        let mut address_to_sensor = HashMap::new();
+        address_to_sensor.insert(0x30, 1);
+        let mut factory = ReplaySensorFactory::from_fixture(
+            &path,
+            address_to_sensor,
+            ReplayPacing::AsFastAsPossible,
+        )
+        .expect("gzip fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let mut driver = factory.create_for_address(0x30).expect("create ok");
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 500);
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 450);
+
+        Ok(())
+    }
+
+    #[test]
+    fn timeline_holds_last_frame_past_the_end() {
+        let path = fixture_path("-hold.jsonl");
+        {
+            let mut recorder = SensorReadingRecorder::create(&path).expect("create recorder");
+            recorder
+                .record_cycle(&recorded_cycles().into_iter().flatten().collect::<Vec<_>>())
+                .expect("record cycle");
+        }
+
+        let mut address_to_sensor = HashMap::new();
+        address_to_sensor.insert(0x30, 1);
+        let mut factory = ReplaySensorFactory::from_fixture(
+            &path,
+            address_to_sensor,
+            ReplayPacing::AsFastAsPossible,
+        )
+        .expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let mut driver = factory.create_for_address(0x30).expect("create ok");
+        for _ in 0..10 {
+            let _ = driver.read_distance();
+        }
+
+        assert_eq!(driver.read_distance().unwrap().distance_mm, 450);
+    }
+
+    #[test]
+    fn unknown_address_returns_invalid_address_error() {
+        let path = fixture_path("-unknown.jsonl");
+        {
+            let mut recorder = SensorReadingRecorder::create(&path).expect("create recorder");
+            recorder
+                .record_cycle(&recorded_cycles().into_iter().flatten().collect::<Vec<_>>())
+                .expect("record cycle");
+        }
+
+        let mut factory = ReplaySensorFactory::from_fixture(
+            &path,
+            HashMap::new(),
+            ReplayPacing::AsFastAsPossible,
+        )
+        .expect("fixture loads");
+        let _ = std::fs::remove_file(&path);
+
+        let err = factory.create_for_address(0x30).unwrap_err();
+        assert!(matches!(err, AppError::InvalidAddress(0x30)));
+    }
+}
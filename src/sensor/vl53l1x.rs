@@ -1,141 +1,214 @@
-use crate::error::AppError;
-use crate::sensor::{DistanceMeasurement, SensorDriver, SensorDriverFactory};
+//! VL53L1X driver generic over any `embedded-hal` 1.0 `I2c` bus.
+//!
+//! `Vl53l1xDriver<I2C>`/`Vl53l1xFactory<I2C>` wrap the `vl53l1x_uld` crate's
+//! `VL53L1X<I2C>` the same way [`crate::sensor::i2c_hal`] wraps the raw
+//! VL53L0X register protocol: the bus type is a generic parameter bounded by
+//! `embedded_hal::i2c::I2c`, so the same driver runs on a Raspberry Pi (via
+//! `rppal`, wired up as the default adapter below) or a bare-metal MCU HAL
+//! (e.g. `embassy-nrf`/`embassy-stm32`) without cfg-gating this module.
+//!
+//! Every sensor on a bus shares one physical I2C peripheral, so the factory
+//! hands each `VL53L1X` a [`SharedI2cBus`] handle onto the same
+//! `Arc<Mutex<I2C>>` rather than a bus of its own. That shared handle is
+//! also what lets `Vl53l1xDriver` hold a single `Arc<Mutex<VL53L1X<_>>>`
+//! field instead of an `Owned`/`Shared` enum: a freshly created driver and a
+//! cached one are both just a clone of that `Arc`, so every `SensorDriver`
+//! method has one body instead of duplicated match arms.
 
-#[cfg(target_os = "linux")]
-use crate::sensor::DEFAULT_I2C_ADDRESS_7BIT;
-#[cfg(target_os = "linux")]
-use crate::sensor::SensorRangeStatus;
-#[cfg(target_os = "linux")]
-use rppal::i2c::I2c;
-#[cfg(target_os = "linux")]
+use crate::error::AppError;
+use crate::sensor::{
+    DEFAULT_I2C_ADDRESS_7BIT, DistanceMeasurement, SensorDriver, SensorDriverFactory,
+    SensorRangeStatus,
+};
+use embedded_hal::digital::OutputPin;
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
 use std::collections::HashMap;
-#[cfg(target_os = "linux")]
 use std::sync::{Arc, Mutex};
-#[cfg(target_os = "linux")]
-use vl53l1x_uld::{IOVoltage, RangeStatus as Vl53l1xRangeStatus, VL53L1X};
+use std::time::Duration;
+use vl53l1x_uld::{DistanceMode, IOVoltage, RangeStatus as Vl53l1xRangeStatus, VL53L1X};
 
-#[cfg(target_os = "linux")]
-pub struct Vl53l1xFactory {
+/// Cloneable handle onto a shared `embedded-hal` I2C bus: every clone locks
+/// the same underlying `Mutex<I2C>`, so multiple `VL53L1X` instances - one
+/// per sensor address - can be driven off one physical bus without each one
+/// claiming exclusive ownership of it.
+pub struct SharedI2cBus<I2C>(Arc<Mutex<I2C>>);
+
+impl<I2C> Clone for SharedI2cBus<I2C> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<I2C: I2c> ErrorType for SharedI2cBus<I2C> {
+    type Error = I2C::Error;
+}
+
+impl<I2C: I2c> I2c for SharedI2cBus<I2C> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .transaction(address, operations)
+    }
+}
+
+/// A sensor's field of view, as the grid coordinates of its top-left and
+/// bottom-right SPADs, passed straight through to the ULD's `set_roi`.
+/// Narrows the sensor's view to one lane instead of the full field.
+#[derive(Debug, Clone, Copy)]
+pub struct RoiWindow {
+    pub top_left: (u8, u8),
+    pub bottom_right: (u8, u8),
+}
+
+/// Ranging configuration applied right after `init` by
+/// [`Vl53l1xDriver::init_default`], trading range for speed/accuracy - e.g.
+/// `Short` mode with a 20ms budget for a fast-updating queue counter versus
+/// `Long` mode for a wide corridor - with an optional `roi` to narrow one
+/// sensor's view to a single lane.
+#[derive(Debug, Clone, Copy)]
+pub struct Vl53l1xConfig {
+    pub distance_mode: DistanceMode,
+    pub timing_budget_ms: u16,
+    pub inter_measurement_ms: u16,
+    pub roi: Option<RoiWindow>,
+}
+
+impl Default for Vl53l1xConfig {
+    fn default() -> Self {
+        Self {
+            distance_mode: DistanceMode::Long,
+            timing_budget_ms: 100,
+            inter_measurement_ms: 100,
+            roi: None,
+        }
+    }
+}
+
+/// Factory that owns the shared I2C bus and caches one
+/// `Arc<Mutex<VL53L1X<_>>>` per address, so repeated `create_for_address`
+/// calls for the same sensor reuse the same handle instead of racing two
+/// owners over the same device.
+pub struct Vl53l1xFactory<I2C> {
     io_voltage: IOVoltage,
-    cache: HashMap<u8, Arc<Mutex<VL53L1X<I2c>>>>,
+    config: Vl53l1xConfig,
+    bus: SharedI2cBus<I2C>,
+    cache: HashMap<u8, Arc<Mutex<VL53L1X<SharedI2cBus<I2C>>>>>,
 }
 
-#[cfg(target_os = "linux")]
-impl Vl53l1xFactory {
-    pub fn new(io_voltage: IOVoltage) -> Self {
+impl<I2C> Vl53l1xFactory<I2C>
+where
+    I2C: I2c,
+{
+    pub fn new(io_voltage: IOVoltage, config: Vl53l1xConfig, i2c: I2C) -> Self {
         Self {
             io_voltage,
+            config,
+            bus: SharedI2cBus(Arc::new(Mutex::new(i2c))),
             cache: HashMap::new(),
         }
     }
 }
 
 #[cfg(target_os = "linux")]
-enum Vl53l1xInner {
-    Owned(VL53L1X<I2c>),
-    Shared(Arc<Mutex<VL53L1X<I2c>>>),
+impl Vl53l1xFactory<rppal::i2c::I2c> {
+    /// Default adapter wiring the generic driver to the Linux `rppal` I2C
+    /// bus, used by the binary's Raspberry Pi startup path.
+    pub fn new_rppal(io_voltage: IOVoltage, config: Vl53l1xConfig) -> Result<Self, AppError> {
+        let i2c = rppal::i2c::I2c::new().map_err(|err| AppError::I2c(err.to_string()))?;
+        Ok(Self::new(io_voltage, config, i2c))
+    }
 }
 
-#[cfg(target_os = "linux")]
-pub struct Vl53l1xDriver {
-    inner: Vl53l1xInner,
+pub struct Vl53l1xDriver<I2C> {
+    driver: Arc<Mutex<VL53L1X<SharedI2cBus<I2C>>>>,
     io_voltage: IOVoltage,
+    config: Vl53l1xConfig,
 }
 
-#[cfg(target_os = "linux")]
-impl SensorDriver for Vl53l1xDriver {
+impl<I2C> SensorDriver for Vl53l1xDriver<I2C>
+where
+    I2C: I2c,
+{
     fn init_default(&mut self) -> Result<(), AppError> {
-        match &mut self.inner {
-            Vl53l1xInner::Owned(driver) => driver
-                .init(self.io_voltage)
-                .map_err(|err| AppError::Sensor(format!("{err:?}"))),
-            Vl53l1xInner::Shared(driver) => {
-                let mut guard = driver
-                    .lock()
-                    .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
-                guard
-                    .init(self.io_voltage)
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))
-            }
+        if self.config.timing_budget_ms > self.config.inter_measurement_ms {
+            return Err(AppError::Sensor(format!(
+                "timing budget {}ms exceeds inter-measurement period {}ms",
+                self.config.timing_budget_ms, self.config.inter_measurement_ms
+            )));
+        }
+
+        let mut driver = self
+            .driver
+            .lock()
+            .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
+        driver
+            .init(self.io_voltage)
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
+        driver
+            .set_distance_mode(self.config.distance_mode)
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
+        driver
+            .set_timing_budget_ms(self.config.timing_budget_ms)
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
+        driver
+            .set_inter_measurement_period_ms(self.config.inter_measurement_ms)
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
+        if let Some(roi) = self.config.roi {
+            driver
+                .set_roi(roi.top_left, roi.bottom_right)
+                .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
         }
+        Ok(())
     }
 
     fn set_address(&mut self, new_address: u8) -> Result<(), AppError> {
-        match &mut self.inner {
-            Vl53l1xInner::Owned(driver) => driver
-                .set_address(new_address)
-                .map_err(|err| AppError::Sensor(format!("{err:?}"))),
-            Vl53l1xInner::Shared(driver) => {
-                let mut guard = driver
-                    .lock()
-                    .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
-                guard
-                    .set_address(new_address)
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))
-            }
-        }
+        let mut driver = self
+            .driver
+            .lock()
+            .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
+        driver
+            .set_address(new_address)
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))
     }
 
     fn verify(&mut self) -> Result<(), AppError> {
-        match &mut self.inner {
-            Vl53l1xInner::Owned(driver) => driver
-                .get_sensor_id()
-                .map(|_| ())
-                .map_err(|err| AppError::Sensor(format!("{err:?}"))),
-            Vl53l1xInner::Shared(driver) => {
-                let mut guard = driver
-                    .lock()
-                    .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
-                guard
-                    .get_sensor_id()
-                    .map(|_| ())
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))
-            }
-        }
+        let mut driver = self
+            .driver
+            .lock()
+            .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
+        driver
+            .get_sensor_id()
+            .map(|_| ())
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))
     }
 
     fn start_ranging(&mut self) -> Result<(), AppError> {
-        match &mut self.inner {
-            Vl53l1xInner::Owned(driver) => driver
-                .start_ranging()
-                .map_err(|err| AppError::Sensor(format!("{err:?}"))),
-            Vl53l1xInner::Shared(driver) => {
-                let mut guard = driver
-                    .lock()
-                    .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
-                guard
-                    .start_ranging()
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))
-            }
-        }
+        let mut driver = self
+            .driver
+            .lock()
+            .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
+        driver
+            .start_ranging()
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))
     }
 
     fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
-        let result = match &mut self.inner {
-            Vl53l1xInner::Owned(driver) => {
-                let result = driver
-                    .get_result()
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
-                // Clear interrupt to trigger next measurement
-                driver
-                    .clear_interrupt()
-                    .map_err(|err| AppError::Sensor(format!("clear_interrupt: {err:?}")))?;
-                result
-            }
-            Vl53l1xInner::Shared(driver) => {
-                let mut guard = driver
-                    .lock()
-                    .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
-                let result = guard
-                    .get_result()
-                    .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
-                // Clear interrupt to trigger next measurement
-                guard
-                    .clear_interrupt()
-                    .map_err(|err| AppError::Sensor(format!("clear_interrupt: {err:?}")))?;
-                result
-            }
-        };
+        let mut driver = self
+            .driver
+            .lock()
+            .map_err(|_| AppError::Sensor("sensor driver lock poisoned".to_string()))?;
+        let result = driver
+            .get_result()
+            .map_err(|err| AppError::Sensor(format!("{err:?}")))?;
+        // Clear interrupt to trigger next measurement
+        driver
+            .clear_interrupt()
+            .map_err(|err| AppError::Sensor(format!("clear_interrupt: {err:?}")))?;
         Ok(DistanceMeasurement {
             distance_mm: result.distance_mm,
             range_status: SensorRangeStatus::from(result.status),
@@ -143,114 +216,38 @@ impl SensorDriver for Vl53l1xDriver {
     }
 }
 
-#[cfg(target_os = "linux")]
-impl SensorDriverFactory for Vl53l1xFactory {
-    type Driver = Vl53l1xDriver;
+impl<I2C> SensorDriverFactory for Vl53l1xFactory<I2C>
+where
+    I2C: I2c,
+{
+    type Driver = Vl53l1xDriver<I2C>;
 
     fn create_default(&mut self) -> Result<Self::Driver, AppError> {
-        let i2c = I2c::new().map_err(|err| AppError::I2c(err.to_string()))?;
-        let driver = VL53L1X::new(i2c, DEFAULT_I2C_ADDRESS_7BIT);
+        let driver = VL53L1X::new(self.bus.clone(), DEFAULT_I2C_ADDRESS_7BIT);
         Ok(Vl53l1xDriver {
-            inner: Vl53l1xInner::Owned(driver),
+            driver: Arc::new(Mutex::new(driver)),
             io_voltage: self.io_voltage,
+            config: self.config,
         })
     }
 
     fn create_for_address(&mut self, address: u8) -> Result<Self::Driver, AppError> {
-        let shared = if let Some(shared) = self.cache.get(&address) {
-            shared.clone()
+        let driver = if let Some(driver) = self.cache.get(&address) {
+            driver.clone()
         } else {
-            let i2c = I2c::new().map_err(|err| AppError::I2c(err.to_string()))?;
-            let driver = VL53L1X::new(i2c, address);
-            let shared = Arc::new(Mutex::new(driver));
-            self.cache.insert(address, shared.clone());
-            shared
+            let driver = VL53L1X::new(self.bus.clone(), address);
+            let driver = Arc::new(Mutex::new(driver));
+            self.cache.insert(address, driver.clone());
+            driver
         };
         Ok(Vl53l1xDriver {
-            inner: Vl53l1xInner::Shared(shared),
+            driver,
             io_voltage: self.io_voltage,
+            config: self.config,
         })
     }
 }
 
-#[cfg(not(target_os = "linux"))]
-pub struct Vl53l1xFactory;
-
-#[cfg(not(target_os = "linux"))]
-impl Vl53l1xFactory {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
-#[cfg(target_os = "linux")]
-impl Default for Vl53l1xFactory {
-    fn default() -> Self {
-        Self::new(IOVoltage::Volt2_8) // Default to 2.8V IO
-    }
-}
-
-#[cfg(not(target_os = "linux"))]
-impl Default for Vl53l1xFactory {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-#[cfg(not(target_os = "linux"))]
-pub struct Vl53l1xDriver;
-
-#[cfg(not(target_os = "linux"))]
-impl SensorDriver for Vl53l1xDriver {
-    fn init_default(&mut self) -> Result<(), AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-
-    fn set_address(&mut self, _new_address: u8) -> Result<(), AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-
-    fn verify(&mut self) -> Result<(), AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-
-    fn start_ranging(&mut self) -> Result<(), AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-
-    fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-}
-
-#[cfg(not(target_os = "linux"))]
-impl SensorDriverFactory for Vl53l1xFactory {
-    type Driver = Vl53l1xDriver;
-
-    fn create_default(&mut self) -> Result<Self::Driver, AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-
-    fn create_for_address(&mut self, _address: u8) -> Result<Self::Driver, AppError> {
-        Err(AppError::Sensor(
-            "VL53L1X driver requires Linux/Raspberry Pi".to_string(),
-        ))
-    }
-}
-
-#[cfg(target_os = "linux")]
 impl From<Vl53l1xRangeStatus> for SensorRangeStatus {
     fn from(status: Vl53l1xRangeStatus) -> Self {
         match status {
@@ -273,3 +270,80 @@ impl From<Vl53l1xRangeStatus> for SensorRangeStatus {
         }
     }
 }
+
+/// One sensor's bring-up target: the XSHUT pin it's wired to and the 7-bit
+/// I2C address it should be assigned once addressed.
+#[derive(Debug, Clone, Copy)]
+pub struct SensorArrayEntry {
+    pub xshut_pin: u8,
+    pub address: u8,
+}
+
+/// Brings up several VL53L1X chips that all boot at `DEFAULT_I2C_ADDRESS_7BIT`
+/// on one shared bus. Generic over any `embedded-hal` `OutputPin`, so the
+/// same sequencing works against `rppal`'s Linux GPIO (via its `embedded-hal`
+/// adapter) or an embassy HAL - the same reasoning that made
+/// [`crate::bus::xshut::HalXshutController`] generic over `OutputPin` rather
+/// than hard-wired to `rppal`.
+pub struct SensorArrayBuilder<I2C, P> {
+    factory: Vl53l1xFactory<I2C>,
+    xshut_pins: HashMap<u8, P>,
+}
+
+impl<I2C, P> SensorArrayBuilder<I2C, P>
+where
+    I2C: I2c,
+    P: OutputPin,
+{
+    pub fn new(factory: Vl53l1xFactory<I2C>, xshut_pins: HashMap<u8, P>) -> Self {
+        Self {
+            factory,
+            xshut_pins,
+        }
+    }
+
+    /// Drive every XSHUT pin low so every sensor boots back at
+    /// `DEFAULT_I2C_ADDRESS_7BIT`, then bring `entries` up one at a time:
+    /// raise its XSHUT, wait the sensor's boot time, `init_default`,
+    /// `set_address` to the requested target, and `verify` - only one
+    /// sensor is ever listening at the default address at a time. Each
+    /// addressed sensor's handle is cached on the returned factory so later
+    /// `create_for_address` calls (config reload, the SCPI console) reuse
+    /// it instead of racing a fresh handle over the bus.
+    pub fn assign(mut self, entries: &[SensorArrayEntry]) -> Result<Vl53l1xFactory<I2C>, AppError> {
+        for pin in self.xshut_pins.values_mut() {
+            pin.set_low()
+                .map_err(|err| AppError::Gpio(format!("{err:?}")))?;
+        }
+
+        for entry in entries {
+            let pin = self
+                .xshut_pins
+                .get_mut(&entry.xshut_pin)
+                .ok_or_else(|| AppError::Xshut(format!("missing XSHUT pin {}", entry.xshut_pin)))?;
+            pin.set_high()
+                .map_err(|err| AppError::Gpio(format!("{err:?}")))?;
+            // Allow sensor boot time after XSHUT release (2ms per VL53L1X datasheet)
+            std::thread::sleep(Duration::from_millis(2));
+
+            let mut driver = self.factory.create_default()?;
+            driver.init_default()?;
+            driver.set_address(entry.address)?;
+            driver.verify()?;
+
+            if self
+                .factory
+                .cache
+                .insert(entry.address, driver.driver)
+                .is_some()
+            {
+                return Err(AppError::Sensor(format!(
+                    "address {:#04x} claimed by more than one sensor in this array",
+                    entry.address
+                )));
+            }
+        }
+
+        Ok(self.factory)
+    }
+}
@@ -0,0 +1,165 @@
+//! Hardware-backed async VL53L1X driver built on `embedded-hal-async`.
+//!
+//! [`crate::sensor::async_driver::BlockingSensorDriver`] lets a blocking
+//! [`crate::sensor::SensorDriver`] satisfy [`AsyncSensorDriver`], but the
+//! I2C transaction still blocks whichever thread-pool thread runs it, and
+//! [`crate::sensor::vl53l1x::Vl53l1xDriver`] reads the result registers via
+//! `get_result` without any data-ready check. This module talks the VL53L1X register
+//! protocol directly over an
+//! `embedded_hal_async::i2c::I2c` bus - the same way [`crate::sensor::i2c_hal`]
+//! hand-rolls the VL53L0X protocol, since `vl53l1x_uld` only supports
+//! blocking I2C - and awaits the sensor's GPIO1 interrupt line via
+//! `embedded_hal_async::digital::Wait` instead of polling
+//! `RESULT__RANGE_STATUS`, so a single task can range many sensors
+//! concurrently without busy-waiting any of them.
+
+use crate::error::AppError;
+use crate::sensor::async_driver::AsyncSensorDriver;
+use crate::sensor::{DistanceMeasurement, SensorRangeStatus};
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+const REG_IDENTIFICATION_MODEL_ID: u16 = 0x010F;
+const EXPECTED_MODEL_ID: u8 = 0xEA;
+const REG_I2C_SLAVE_DEVICE_ADDRESS: u16 = 0x0001;
+const REG_SYSTEM_MODE_START: u16 = 0x0087;
+const MODE_START_RANGING: u8 = 0x40;
+const REG_SYSTEM_INTERRUPT_CLEAR: u16 = 0x0086;
+const REG_RESULT_RANGE_STATUS: u16 = 0x0089;
+const REG_RESULT_FINAL_RANGE_MM: u16 = 0x0096;
+
+/// Async VL53L1X driver over any `embedded-hal-async` I2C bus and the
+/// sensor's GPIO1 interrupt line. `W` is that line - the sensor drives it
+/// low once a ranging result has latched, and `read_distance` awaits that
+/// edge rather than polling.
+pub struct Vl53l1xAsyncDriver<I2C, W> {
+    i2c: I2C,
+    interrupt: W,
+    address: u8,
+}
+
+impl<I2C, W> Vl53l1xAsyncDriver<I2C, W>
+where
+    I2C: I2c,
+    W: Wait,
+{
+    pub fn new(i2c: I2C, interrupt: W, address: u8) -> Self {
+        Self {
+            i2c,
+            interrupt,
+            address,
+        }
+    }
+
+    async fn write_register(&mut self, register: u16, value: u8) -> Result<(), AppError> {
+        let [hi, lo] = register.to_be_bytes();
+        self.i2c
+            .write(self.address, &[hi, lo, value])
+            .await
+            .map_err(|_| AppError::I2c(format!("write to register {register:#06x} failed")))
+    }
+
+    async fn read_register(&mut self, register: u16) -> Result<u8, AppError> {
+        let [hi, lo] = register.to_be_bytes();
+        let mut buf = [0u8; 1];
+        self.i2c
+            .write_read(self.address, &[hi, lo], &mut buf)
+            .await
+            .map_err(|_| AppError::I2c(format!("read from register {register:#06x} failed")))?;
+        Ok(buf[0])
+    }
+
+    async fn read_register_u16(&mut self, register: u16) -> Result<u16, AppError> {
+        let [hi, lo] = register.to_be_bytes();
+        let mut buf = [0u8; 2];
+        self.i2c
+            .write_read(self.address, &[hi, lo], &mut buf)
+            .await
+            .map_err(|_| AppError::I2c(format!("read from register {register:#06x} failed")))?;
+        Ok(u16::from_be_bytes(buf))
+    }
+}
+
+impl<I2C, W> AsyncSensorDriver for Vl53l1xAsyncDriver<I2C, W>
+where
+    I2C: I2c + Send,
+    W: Wait + Send,
+{
+    async fn init_default(&mut self) -> Result<(), AppError> {
+        let model_id = self.read_register(REG_IDENTIFICATION_MODEL_ID).await?;
+        if model_id != EXPECTED_MODEL_ID {
+            return Err(AppError::Sensor(format!(
+                "unexpected VL53L1X model id: {model_id:#04x}"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn set_address(&mut self, new_address: u8) -> Result<(), AppError> {
+        self.write_register(REG_I2C_SLAVE_DEVICE_ADDRESS, new_address & 0x7F)
+            .await?;
+        self.address = new_address;
+        Ok(())
+    }
+
+    async fn verify(&mut self) -> Result<(), AppError> {
+        self.read_register(REG_IDENTIFICATION_MODEL_ID)
+            .await
+            .map(|_| ())
+    }
+
+    async fn start_ranging(&mut self) -> Result<(), AppError> {
+        self.write_register(REG_SYSTEM_MODE_START, MODE_START_RANGING)
+            .await
+    }
+
+    async fn read_distance(&mut self) -> Result<DistanceMeasurement, AppError> {
+        self.interrupt.wait_for_low().await.map_err(|_| {
+            AppError::Gpio("failed to await VL53L1X data-ready interrupt".to_string())
+        })?;
+
+        let distance_mm = self.read_register_u16(REG_RESULT_FINAL_RANGE_MM).await?;
+        let device_status = self.read_register(REG_RESULT_RANGE_STATUS).await?;
+
+        // Clear interrupt to trigger next measurement.
+        self.write_register(REG_SYSTEM_INTERRUPT_CLEAR, 0x01)
+            .await?;
+
+        Ok(DistanceMeasurement {
+            distance_mm,
+            range_status: range_status_from_device_status(device_status),
+        })
+    }
+}
+
+fn range_status_from_device_status(status: u8) -> SensorRangeStatus {
+    match status & 0x1F {
+        0x09 => SensorRangeStatus::Valid,
+        0x04 => SensorRangeStatus::SignalFailure,
+        0x05 => SensorRangeStatus::OutOfBounds,
+        0x06 => SensorRangeStatus::SigmaFailure,
+        0x07 => SensorRangeStatus::Wraparound,
+        _ => SensorRangeStatus::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_status_decodes_common_device_codes() {
+        assert_eq!(
+            range_status_from_device_status(0x09),
+            SensorRangeStatus::Valid
+        );
+        assert_eq!(
+            range_status_from_device_status(0x06),
+            SensorRangeStatus::SigmaFailure
+        );
+        assert_eq!(
+            range_status_from_device_status(0x1F),
+            SensorRangeStatus::None
+        );
+    }
+}
@@ -3,17 +3,23 @@ use crate::estimation::linear_v1::LinearV1Model;
 use crate::estimation::model::EstimationModel;
 use crate::sensor::{SensorId, SensorInfo, SensorRangeStatus};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::watch;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Default capacity of the wait-time history ring buffer: ~4 hours of
+/// samples at the default 5s refresh interval. Overridden by
+/// `Config::history_capacity`.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 2880;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReadingStatus {
     Ok { range_status: SensorRangeStatus },
     Error { reason: String },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SensorReading {
     pub sensor_id: SensorId,
     pub distance_mm: u16,
@@ -21,13 +27,31 @@ pub struct SensorReading {
     pub status: ReadingStatus,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SensorObstruction {
     pub sensor_id: SensorId,
     pub obstructed: Option<bool>,
     pub timestamp: SystemTime,
 }
 
+/// Per-sensor cross-validation result from [`crate::bus::validator::DataValidator`],
+/// kept alongside the readings/obstructions it was derived from so a
+/// consumer can see *why* a sensor was dropped instead of just that it was.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorValidation {
+    pub sensor_id: SensorId,
+    /// Confidence in `[0, 1]`; the obstruction pipeline treats readings
+    /// below the validator's configured threshold the same as a dropped
+    /// sensor.
+    pub confidence: f64,
+    /// Raw distance repeated for more cycles than the validator allows.
+    pub stale: bool,
+    /// Exponentially-weighted rate of `ReadingStatus::Error` cycles.
+    pub error_density: f64,
+    /// Distance fell outside `[sensor_min_mm, sensor_max_mm]`.
+    pub out_of_bounds: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OccupancyStatus {
     Ok,
@@ -79,10 +103,16 @@ pub struct AppState {
     readings_tx: watch::Sender<Vec<SensorReading>>,
     obstructions: Vec<SensorObstruction>,
     obstructions_tx: watch::Sender<Vec<SensorObstruction>>,
+    validations: Vec<SensorValidation>,
+    validations_tx: watch::Sender<Vec<SensorValidation>>,
     wait_time: Option<WaitTimeEstimate>,
     wait_time_tx: watch::Sender<Option<WaitTimeEstimate>>,
+    history: VecDeque<WaitTimeEstimate>,
+    history_capacity: usize,
     calibration: Option<CalibrationParams>,
     model: Arc<dyn EstimationModel>,
+    rediscovery_requested: bool,
+    reinitialize_requested: BTreeSet<SensorId>,
 }
 
 impl AppState {
@@ -90,6 +120,7 @@ impl AppState {
         let (sensors_tx, _sensors_rx) = watch::channel(Vec::new());
         let (readings_tx, _readings_rx) = watch::channel(Vec::new());
         let (obstructions_tx, _obstructions_rx) = watch::channel(Vec::new());
+        let (validations_tx, _validations_rx) = watch::channel(Vec::new());
         let (wait_time_tx, _wait_time_rx) = watch::channel(None);
         let model = Arc::new(LinearV1Model::with_defaults());
         Self {
@@ -99,10 +130,16 @@ impl AppState {
             readings_tx,
             obstructions: Vec::new(),
             obstructions_tx,
+            validations: Vec::new(),
+            validations_tx,
             wait_time: None,
             wait_time_tx,
+            history: VecDeque::new(),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
             calibration: None,
             model,
+            rediscovery_requested: false,
+            reinitialize_requested: BTreeSet::new(),
         }
     }
 
@@ -154,6 +191,21 @@ impl AppState {
         Ok(())
     }
 
+    pub fn validations(&self) -> &[SensorValidation] {
+        &self.validations
+    }
+
+    pub fn subscribe_validations(&self) -> watch::Receiver<Vec<SensorValidation>> {
+        self.validations_tx.subscribe()
+    }
+
+    pub fn set_validations(&mut self, validations: Vec<SensorValidation>) -> Result<(), AppError> {
+        self.validations = validations.clone();
+        // Send is best-effort - no subscribers is OK, local state is still updated
+        let _ = self.validations_tx.send(validations);
+        Ok(())
+    }
+
     pub fn wait_time(&self) -> Option<&WaitTimeEstimate> {
         self.wait_time.as_ref()
     }
@@ -164,11 +216,60 @@ impl AppState {
 
     pub fn set_wait_time(&mut self, wait_time: WaitTimeEstimate) -> Result<(), AppError> {
         self.wait_time = Some(wait_time.clone());
+        self.history.push_back(wait_time.clone());
+        while self.history.len() > self.history_capacity {
+            self.history.pop_front();
+        }
         // Send is best-effort - no subscribers is OK, local state is still updated
         let _ = self.wait_time_tx.send(Some(wait_time));
         Ok(())
     }
 
+    /// Returns the wait-time history ring buffer, oldest first. Every
+    /// `EstimationModel` feeds the same buffer through `set_wait_time`, so
+    /// swapping models doesn't lose continuity.
+    pub fn history(&self) -> impl Iterator<Item = &WaitTimeEstimate> {
+        self.history.iter()
+    }
+
+    /// Sets the history ring buffer capacity, evicting the oldest samples
+    /// immediately if it shrinks below the current length.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        while self.history.len() > self.history_capacity {
+            self.history.pop_front();
+        }
+    }
+
+    /// Returns history samples since `since` (inclusive, if given),
+    /// downsampled to at most `max_samples` by taking an even stride
+    /// through the filtered range so the result still spans the full
+    /// window instead of just its tail.
+    pub fn history_query(
+        &self,
+        since: Option<SystemTime>,
+        max_samples: Option<usize>,
+    ) -> Vec<WaitTimeEstimate> {
+        let filtered: Vec<&WaitTimeEstimate> = self
+            .history
+            .iter()
+            .filter(|estimate| match since {
+                Some(since) => estimate.timestamp >= since,
+                None => true,
+            })
+            .collect();
+
+        let max_samples = max_samples.unwrap_or(filtered.len()).max(1);
+        if filtered.len() <= max_samples {
+            return filtered.into_iter().cloned().collect();
+        }
+
+        let stride = filtered.len() as f64 / max_samples as f64;
+        (0..max_samples)
+            .map(|i| filtered[((i as f64 * stride) as usize).min(filtered.len() - 1)].clone())
+            .collect()
+    }
+
     pub fn calibration(&self) -> Option<&CalibrationParams> {
         self.calibration.as_ref()
     }
@@ -184,6 +285,29 @@ impl AppState {
     pub fn model(&self) -> &Arc<dyn EstimationModel> {
         &self.model
     }
+
+    /// Flags that the sensor map or I2C address plan changed and discovery
+    /// should re-run on the next refresh cycle.
+    pub fn request_rediscovery(&mut self) {
+        self.rediscovery_requested = true;
+    }
+
+    /// Clears and returns the pending rediscovery flag.
+    pub fn take_rediscovery_request(&mut self) -> bool {
+        std::mem::replace(&mut self.rediscovery_requested, false)
+    }
+
+    /// Flags `sensor_id` for a forced re-init on the next refresh cycle -
+    /// used by the SCPI console, which doesn't own the XSHUT hardware
+    /// handle itself.
+    pub fn request_reinitialize(&mut self, sensor_id: SensorId) {
+        self.reinitialize_requested.insert(sensor_id);
+    }
+
+    /// Clears and returns the set of sensor ids pending a forced re-init.
+    pub fn take_reinitialize_requests(&mut self) -> BTreeSet<SensorId> {
+        std::mem::take(&mut self.reinitialize_requested)
+    }
 }
 
 impl Default for AppState {
@@ -250,4 +374,105 @@ mod tests {
         assert_eq!(state.wait_time(), Some(&estimate));
         assert_eq!(*receiver.borrow(), Some(estimate));
     }
+
+    fn estimate_at(secs: u64) -> WaitTimeEstimate {
+        WaitTimeEstimate {
+            wait_time_minutes: Some(secs as f64),
+            timestamp: UNIX_EPOCH + Duration::from_secs(secs),
+            status: WaitTimeStatus::Ok,
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn set_wait_time_appends_to_history() {
+        let mut state = AppState::new();
+
+        state.set_wait_time(estimate_at(1)).expect("set wait time");
+        state.set_wait_time(estimate_at(2)).expect("set wait time");
+
+        let history: Vec<_> = state.history().cloned().collect();
+        assert_eq!(history, vec![estimate_at(1), estimate_at(2)]);
+    }
+
+    #[test]
+    fn history_capacity_evicts_oldest_samples() {
+        let mut state = AppState::new();
+        state.set_history_capacity(2);
+
+        state.set_wait_time(estimate_at(1)).expect("set wait time");
+        state.set_wait_time(estimate_at(2)).expect("set wait time");
+        state.set_wait_time(estimate_at(3)).expect("set wait time");
+
+        let history: Vec<_> = state.history().cloned().collect();
+        assert_eq!(history, vec![estimate_at(2), estimate_at(3)]);
+    }
+
+    #[test]
+    fn shrinking_history_capacity_evicts_immediately() {
+        let mut state = AppState::new();
+        state.set_wait_time(estimate_at(1)).expect("set wait time");
+        state.set_wait_time(estimate_at(2)).expect("set wait time");
+        state.set_wait_time(estimate_at(3)).expect("set wait time");
+
+        state.set_history_capacity(1);
+
+        let history: Vec<_> = state.history().cloned().collect();
+        assert_eq!(history, vec![estimate_at(3)]);
+    }
+
+    #[test]
+    fn history_query_filters_by_since() {
+        let mut state = AppState::new();
+        for secs in 1..=5 {
+            state.set_wait_time(estimate_at(secs)).expect("set wait time");
+        }
+
+        let results = state.history_query(Some(UNIX_EPOCH + Duration::from_secs(3)), None);
+
+        assert_eq!(results, vec![estimate_at(3), estimate_at(4), estimate_at(5)]);
+    }
+
+    #[test]
+    fn history_query_downsamples_to_max_samples() {
+        let mut state = AppState::new();
+        for secs in 1..=10 {
+            state.set_wait_time(estimate_at(secs)).expect("set wait time");
+        }
+
+        let results = state.history_query(None, Some(5));
+
+        assert_eq!(results.len(), 5);
+        // Downsampling should span the full range, not just the tail.
+        assert_eq!(results.first(), Some(&estimate_at(1)));
+        assert_eq!(results.last(), Some(&estimate_at(9)));
+    }
+
+    #[test]
+    fn rediscovery_request_is_cleared_on_take() {
+        let mut state = AppState::new();
+
+        assert!(!state.take_rediscovery_request());
+
+        state.request_rediscovery();
+        assert!(state.take_rediscovery_request());
+        assert!(!state.take_rediscovery_request());
+    }
+
+    #[test]
+    fn reinitialize_requests_are_cleared_on_take() {
+        let mut state = AppState::new();
+
+        assert!(state.take_reinitialize_requests().is_empty());
+
+        state.request_reinitialize(1);
+        state.request_reinitialize(2);
+        state.request_reinitialize(1);
+
+        let requested = state.take_reinitialize_requests();
+        assert_eq!(requested.len(), 2);
+        assert!(requested.contains(&1));
+        assert!(requested.contains(&2));
+        assert!(state.take_reinitialize_requests().is_empty());
+    }
 }
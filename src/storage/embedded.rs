@@ -0,0 +1,115 @@
+//! Default [`HistoryStore`] backend: an in-process ring buffer per sensor.
+
+use super::{HistorySample, HistoryStore, RetentionConfig};
+use crate::sensor::SensorId;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+#[derive(Debug)]
+pub struct EmbeddedHistoryStore {
+    retention: RetentionConfig,
+    series: RwLock<HashMap<SensorId, VecDeque<HistorySample>>>,
+}
+
+impl EmbeddedHistoryStore {
+    pub fn new(retention: RetentionConfig) -> Self {
+        Self {
+            retention,
+            series: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl HistoryStore for EmbeddedHistoryStore {
+    fn record(&self, sample: HistorySample) {
+        let mut guard = self
+            .series
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let series = guard.entry(sample.sensor_id).or_default();
+        series.push_back(sample);
+        while series.len() > self.retention.capacity_per_sensor {
+            series.pop_front();
+        }
+    }
+
+    fn query(
+        &self,
+        sensor_id: SensorId,
+        from: Option<SystemTime>,
+        to: Option<SystemTime>,
+    ) -> Vec<HistorySample> {
+        let guard = self
+            .series
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(series) = guard.get(&sensor_id) else {
+            return Vec::new();
+        };
+        series
+            .iter()
+            .filter(|sample| from.map(|from| sample.timestamp >= from).unwrap_or(true))
+            .filter(|sample| to.map(|to| sample.timestamp <= to).unwrap_or(true))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ReadingStatus;
+    use std::time::Duration;
+
+    fn sample(sensor_id: SensorId, secs: u64) -> HistorySample {
+        HistorySample {
+            sensor_id,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            distance_mm: 100,
+            status: ReadingStatus::Ok {
+                range_status: crate::sensor::SensorRangeStatus::Valid,
+            },
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn query_is_scoped_to_sensor_id() {
+        let store = EmbeddedHistoryStore::new(RetentionConfig::default());
+        store.record(sample(1, 1));
+        store.record(sample(2, 1));
+
+        assert_eq!(store.query(1, None, None), vec![sample(1, 1)]);
+        assert_eq!(store.query(2, None, None), vec![sample(2, 1)]);
+        assert!(store.query(3, None, None).is_empty());
+    }
+
+    #[test]
+    fn query_filters_by_from_and_to() {
+        let store = EmbeddedHistoryStore::new(RetentionConfig::default());
+        for secs in 1..=5 {
+            store.record(sample(1, secs));
+        }
+
+        let results = store.query(
+            1,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(2)),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(4)),
+        );
+
+        assert_eq!(results, vec![sample(1, 2), sample(1, 3), sample(1, 4)]);
+    }
+
+    #[test]
+    fn retention_evicts_oldest_samples_per_sensor() {
+        let store = EmbeddedHistoryStore::new(RetentionConfig {
+            capacity_per_sensor: 2,
+        });
+        store.record(sample(1, 1));
+        store.record(sample(1, 2));
+        store.record(sample(1, 3));
+
+        assert_eq!(store.query(1, None, None), vec![sample(1, 2), sample(1, 3)]);
+    }
+}
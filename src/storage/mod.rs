@@ -0,0 +1,147 @@
+//! Pluggable time-series storage for per-sensor reading history.
+//!
+//! [`HistoryStore`] is the extension point - the default
+//! [`embedded::EmbeddedHistoryStore`] keeps everything in-process like
+//! `AppState`'s wait-time ring buffer, but a deployment that wants
+//! cross-restart retention can swap in an external collection (e.g.
+//! MongoDB, as home-anthill's sensors service uses) by implementing this
+//! trait instead of touching [`spawn_recorder`] or the `/api/sensors/{id}/history`
+//! handler.
+
+pub mod embedded;
+
+use crate::api::responses::SensorErrorCode;
+use crate::sensor::SensorId;
+use crate::state::{AppState, ReadingStatus};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+pub use embedded::EmbeddedHistoryStore;
+
+/// One persisted reading: the value, status, and mapped error code at the
+/// time it was recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySample {
+    pub sensor_id: SensorId,
+    pub timestamp: SystemTime,
+    pub distance_mm: u16,
+    pub status: ReadingStatus,
+    pub error_code: Option<SensorErrorCode>,
+}
+
+/// Per-sensor ring buffer capacity, so long-running devices don't grow
+/// their history unbounded.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionConfig {
+    pub capacity_per_sensor: usize,
+}
+
+/// ~4 hours of samples per sensor at the default 5s refresh interval, same
+/// horizon as [`crate::state::DEFAULT_HISTORY_CAPACITY`].
+pub const DEFAULT_CAPACITY_PER_SENSOR: usize = 2880;
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            capacity_per_sensor: DEFAULT_CAPACITY_PER_SENSOR,
+        }
+    }
+}
+
+/// A pluggable time-series backend for [`HistorySample`]s.
+pub trait HistoryStore: Send + Sync {
+    fn record(&self, sample: HistorySample);
+    fn query(&self, sensor_id: SensorId, from: Option<SystemTime>, to: Option<SystemTime>) -> Vec<HistorySample>;
+}
+
+/// Subscribes to [`AppState::subscribe_readings`] and persists every cycle's
+/// readings to `store`, keyed by sensor id. The timestamp on each
+/// `SensorReading` - the same clock `build_sensors_response` stamps its
+/// snapshot with - becomes the record time, so ingestion and query never
+/// drift apart.
+pub fn spawn_recorder(
+    state: Arc<RwLock<AppState>>,
+    store: Arc<dyn HistoryStore>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut readings_rx = {
+            let guard = state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+            guard.subscribe_readings()
+        };
+
+        loop {
+            let readings = readings_rx.borrow_and_update().clone();
+            for reading in &readings {
+                store.record(to_sample(reading));
+            }
+            if readings_rx.changed().await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+fn to_sample(reading: &crate::state::SensorReading) -> HistorySample {
+    let error_code = match &reading.status {
+        ReadingStatus::Ok { .. } => None,
+        ReadingStatus::Error { reason } => {
+            Some(crate::api::handlers::map_sensor_error_code_from_message(reason))
+        }
+    };
+    HistorySample {
+        sensor_id: reading.sensor_id,
+        timestamp: reading.timestamp,
+        distance_mm: reading.distance_mm,
+        status: reading.status.clone(),
+        error_code,
+    }
+}
+
+/// Downsamples `samples` to at most `max_samples` by taking an even stride
+/// through the series, the same approach as `AppState::history_query`, so
+/// the result still spans the full queried range instead of just its tail.
+pub fn downsample(samples: Vec<HistorySample>, max_samples: Option<usize>) -> Vec<HistorySample> {
+    let max_samples = max_samples.unwrap_or(samples.len()).max(1);
+    if samples.len() <= max_samples {
+        return samples;
+    }
+
+    let stride = samples.len() as f64 / max_samples as f64;
+    (0..max_samples)
+        .map(|i| samples[((i as f64 * stride) as usize).min(samples.len() - 1)].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(sensor_id: SensorId, secs: u64) -> HistorySample {
+        HistorySample {
+            sensor_id,
+            timestamp: SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs),
+            distance_mm: 250,
+            status: ReadingStatus::Ok {
+                range_status: crate::sensor::SensorRangeStatus::Valid,
+            },
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn downsample_keeps_everything_under_the_limit() {
+        let samples = vec![sample(1, 1), sample(1, 2)];
+        assert_eq!(downsample(samples.clone(), Some(5)), samples);
+    }
+
+    #[test]
+    fn downsample_spans_the_full_range() {
+        let samples: Vec<_> = (1..=10).map(|secs| sample(1, secs)).collect();
+
+        let result = downsample(samples, Some(5));
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(result.first().unwrap().timestamp, sample(1, 1).timestamp);
+        assert_eq!(result.last().unwrap().timestamp, sample(1, 9).timestamp);
+    }
+}
@@ -0,0 +1,12 @@
+//! Telemetry publishing subsystem.
+//!
+//! Bridges the `AppState` watch channels to outward-facing integrations (MQTT,
+//! SSE, metrics exporters, ...) so dashboards can consume live estimates
+//! without polling the HTTP API.
+
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod prometheus;
+
+#[cfg(feature = "otel_metrics")]
+pub mod otel;
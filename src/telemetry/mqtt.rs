@@ -0,0 +1,332 @@
+//! MQTT publisher that mirrors the `AppState` watch channels onto a broker.
+//!
+//! Every update to wait-time, readings, or obstructions is re-published as
+//! JSON under `<topic_prefix>/<site>/<channel>` so a dashboard can subscribe
+//! instead of polling the HTTP API. The `queue` and `sensors/<sensor_id>`
+//! topics reuse the same `QueueSuccessResponse`/`SensorStatusResponse`
+//! payload shapes as the `/api/queue` and `/api/sensors` HTTP endpoints, so
+//! a dashboard can treat MQTT as a push-based mirror of the JSON API rather
+//! than a separate schema. QoS and retain are configurable per deployment
+//! (`[mqtt].qos`/`[mqtt].retain`) and apply to every topic except `health`
+//! and `sensors/<sensor_id>/status`, which are always retained at QoS 1; a
+//! retained last-will marks the node offline if the connection drops
+//! uncleanly, and `sensors/<sensor_id>/status` mirrors just the
+//! `SensorErrorCode` as a bare retained string so a consumer can watch for
+//! faults without parsing the full sensor payload.
+
+use crate::api::handlers::{
+    HealthResponse, QueueResponse, build_health_response, build_queue_response, map_sensor_info,
+};
+use crate::api::responses::{HealthStatus, HealthSuccessResponse, SensorErrorCode};
+use crate::sensor::SensorInfo;
+use crate::state::{AppState, SensorObstruction, SensorReading, WaitTimeEstimate};
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tracing::{debug, error, info, warn};
+
+const HEALTH_TOPIC_SUFFIX: &str = "health";
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Prefix applied to every topic, e.g. `mariam`.
+    pub topic_prefix: String,
+    /// Site/device identifier used to namespace topics, e.g. `lobby-1`.
+    pub site: String,
+    pub client_id: String,
+    /// QoS applied to telemetry publishes (health always uses
+    /// [`QoS::AtLeastOnce`] regardless, so its Last-Will is honored).
+    pub qos: QoS,
+    /// Whether telemetry publishes are retained by the broker.
+    pub retain: bool,
+}
+
+impl MqttConfig {
+    fn topic(&self, channel: &str) -> String {
+        format!("{}/{}/{}", self.topic_prefix, self.site, channel)
+    }
+
+    fn health_topic(&self) -> String {
+        self.topic(HEALTH_TOPIC_SUFFIX)
+    }
+}
+
+/// Maps a config-file QoS level (0/1/2) to [`QoS`], falling back to
+/// at-least-once for anything else so a typo'd config doesn't silently
+/// drop telemetry at QoS 0.
+pub fn qos_from_level(level: u8) -> QoS {
+    match level {
+        0 => QoS::AtMostOnce,
+        2 => QoS::ExactlyOnce,
+        _ => QoS::AtLeastOnce,
+    }
+}
+
+/// Spawn the background MQTT publisher task.
+///
+/// The task reconnects with exponential backoff on disconnect and never
+/// returns on its own; drop the returned handle to stop it.
+pub fn spawn_mqtt_publisher(
+    state: Arc<RwLock<AppState>>,
+    config: MqttConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        run_publisher(state, config).await;
+    })
+}
+
+async fn run_publisher(state: Arc<RwLock<AppState>>, config: MqttConfig) {
+    let (wait_time_rx, readings_rx, obstructions_rx, sensors_rx) = {
+        let guard = match state.read() {
+            Ok(guard) => guard,
+            Err(_) => {
+                error!("state lock poisoned while starting MQTT publisher");
+                return;
+            }
+        };
+        (
+            guard.subscribe_wait_time(),
+            guard.subscribe_readings(),
+            guard.subscribe_obstructions(),
+            guard.subscribe_sensors(),
+        )
+    };
+
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        match run_connection(
+            &config,
+            Arc::clone(&state),
+            wait_time_rx.clone(),
+            readings_rx.clone(),
+            obstructions_rx.clone(),
+            sensors_rx.clone(),
+        )
+        .await
+        {
+            Ok(()) => {
+                // Connection closed cleanly (should not normally happen); reset backoff.
+                backoff = MIN_BACKOFF;
+            }
+            Err(err) => {
+                warn!(error = %err, backoff_secs = backoff.as_secs(), "MQTT connection lost, retrying");
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn run_connection(
+    config: &MqttConfig,
+    state: Arc<RwLock<AppState>>,
+    mut wait_time_rx: watch::Receiver<Option<WaitTimeEstimate>>,
+    mut readings_rx: watch::Receiver<Vec<SensorReading>>,
+    mut obstructions_rx: watch::Receiver<Vec<SensorObstruction>>,
+    mut sensors_rx: watch::Receiver<Vec<SensorInfo>>,
+) -> Result<(), rumqttc::ConnectionError> {
+    let mut options = MqttOptions::new(config.client_id.clone(), config.host.clone(), config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        options.set_credentials(username.clone(), password.clone());
+    }
+    options.set_last_will(LastWill::new(
+        config.health_topic(),
+        ko_health_payload(),
+        QoS::AtLeastOnce,
+        true,
+    ));
+
+    let (client, mut eventloop) = AsyncClient::new(options, 16);
+
+    publish_health(&client, config, &state).await;
+
+    loop {
+        tokio::select! {
+            changed = wait_time_rx.changed() => {
+                changed.map_err(|_| rumqttc::ConnectionError::RequestsDone)?;
+                let estimate = wait_time_rx.borrow_and_update().clone();
+                if let Some(estimate) = estimate {
+                    publish_json(&client, config, config.topic("wait_time"), &estimate).await;
+                }
+                if let QueueResponse::Success(body) = build_queue_response(Arc::clone(&state)) {
+                    publish_json(&client, config, config.topic("queue"), &body).await;
+                }
+            }
+            changed = readings_rx.changed() => {
+                changed.map_err(|_| rumqttc::ConnectionError::RequestsDone)?;
+                let readings = readings_rx.borrow_and_update().clone();
+                publish_json(&client, config, config.topic("readings"), &readings).await;
+            }
+            changed = obstructions_rx.changed() => {
+                changed.map_err(|_| rumqttc::ConnectionError::RequestsDone)?;
+                let obstructions = obstructions_rx.borrow_and_update().clone();
+                publish_json(&client, config, config.topic("obstructions"), &obstructions).await;
+            }
+            changed = sensors_rx.changed() => {
+                changed.map_err(|_| rumqttc::ConnectionError::RequestsDone)?;
+                let sensors = sensors_rx.borrow_and_update().clone();
+                for sensor in &sensors {
+                    if let Ok(status) = map_sensor_info(sensor) {
+                        let sensor_topic = config.topic(&format!("sensors/{}", sensor.sensor_id));
+                        publish_json(&client, config, sensor_topic.clone(), &status).await;
+                        publish_sensor_error_code(&client, &sensor_topic, status.error_code).await;
+                    }
+                }
+                // Sensor status changes are what drive derived health, so
+                // republish it alongside the per-sensor updates.
+                publish_health(&client, config, &state).await;
+            }
+            event = eventloop.poll() => {
+                match event? {
+                    Event::Incoming(Packet::Disconnect) => {
+                        return Ok(());
+                    }
+                    other => {
+                        debug!(?other, "MQTT event");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Republishes `/api/health`'s payload retained at QoS 1, so a consumer
+/// that connects after startup immediately sees current status instead of
+/// waiting for the next sensor-driven update.
+async fn publish_health(client: &AsyncClient, config: &MqttConfig, state: &Arc<RwLock<AppState>>) {
+    let body = match build_health_response(Arc::clone(state), SystemTime::now()) {
+        HealthResponse::Success { body, .. } => body,
+        HealthResponse::Error { .. } => HealthSuccessResponse {
+            status: HealthStatus::Ko,
+            timestamp: now_rfc3339_or_epoch(),
+        },
+    };
+
+    let payload = match serde_json::to_vec(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!(error = %err, "Failed to serialize health telemetry payload");
+            return;
+        }
+    };
+
+    if let Err(err) = client
+        .publish(config.health_topic(), QoS::AtLeastOnce, true, payload)
+        .await
+    {
+        warn!(error = %err, topic = config.health_topic(), "Failed to publish health update");
+    }
+}
+
+/// Last-Will payload delivered by the broker if this connection drops
+/// uncleanly - the same `HealthSuccessResponse` shape as `/api/health`,
+/// just pinned to `Ko` since we can't know the real status after the fact.
+fn ko_health_payload() -> Vec<u8> {
+    let body = HealthSuccessResponse {
+        status: HealthStatus::Ko,
+        timestamp: now_rfc3339_or_epoch(),
+    };
+    serde_json::to_vec(&body).unwrap_or_else(|_| b"{\"status\":\"ko\"}".to_vec())
+}
+
+fn now_rfc3339_or_epoch() -> String {
+    use time::OffsetDateTime;
+    use time::format_description::well_known::Rfc3339;
+    OffsetDateTime::from(SystemTime::now())
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+}
+
+async fn publish_json<T: Serialize>(client: &AsyncClient, config: &MqttConfig, topic: String, value: &T) {
+    let payload = match serde_json::to_vec(value) {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!(error = %err, topic, "Failed to serialize telemetry payload");
+            return;
+        }
+    };
+
+    if let Err(err) = client
+        .publish(topic.clone(), config.qos, config.retain, payload)
+        .await
+    {
+        warn!(error = %err, topic, "Failed to publish telemetry update");
+    } else {
+        info!(topic, "Published telemetry update");
+    }
+}
+
+/// Publishes `<sensor_topic>/status` retained so a consumer can react to a
+/// sensor fault by subscribing to this lightweight topic alone, without
+/// parsing the full `SensorStatusResponse` payload on `<sensor_topic>`. The
+/// payload is the bare `SensorErrorCode` string (no JSON wrapper); an empty
+/// retained message clears it once the sensor recovers.
+async fn publish_sensor_error_code(
+    client: &AsyncClient,
+    sensor_topic: &str,
+    error_code: Option<SensorErrorCode>,
+) {
+    let topic = format!("{sensor_topic}/status");
+    let payload = match error_code {
+        Some(code) => sensor_error_code_str(code).as_bytes().to_vec(),
+        None => Vec::new(),
+    };
+
+    if let Err(err) = client.publish(topic.clone(), QoS::AtLeastOnce, true, payload).await {
+        warn!(error = %err, topic, "Failed to publish sensor status update");
+    }
+}
+
+fn sensor_error_code_str(code: SensorErrorCode) -> &'static str {
+    match code {
+        SensorErrorCode::NoResponse => "NO_RESPONSE",
+        SensorErrorCode::I2cError => "I2C_ERROR",
+        SensorErrorCode::Timeout => "TIMEOUT",
+        SensorErrorCode::InvalidReading => "INVALID_READING",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> MqttConfig {
+        MqttConfig {
+            host: "broker.local".to_string(),
+            port: 1883,
+            username: None,
+            password: None,
+            topic_prefix: "mariam".to_string(),
+            site: "lobby-1".to_string(),
+            client_id: "mariam-flow-lobby-1".to_string(),
+            qos: QoS::AtLeastOnce,
+            retain: false,
+        }
+    }
+
+    #[test]
+    fn topics_are_namespaced_by_prefix_and_site() {
+        let config = test_config();
+
+        assert_eq!(config.topic("wait_time"), "mariam/lobby-1/wait_time");
+        assert_eq!(config.topic("readings"), "mariam/lobby-1/readings");
+        assert_eq!(config.health_topic(), "mariam/lobby-1/health");
+    }
+
+    #[test]
+    fn qos_from_level_falls_back_to_at_least_once_for_unknown_values() {
+        assert_eq!(qos_from_level(0), QoS::AtMostOnce);
+        assert_eq!(qos_from_level(1), QoS::AtLeastOnce);
+        assert_eq!(qos_from_level(2), QoS::ExactlyOnce);
+        assert_eq!(qos_from_level(99), QoS::AtLeastOnce);
+    }
+}
@@ -0,0 +1,151 @@
+//! OpenTelemetry metrics bridge, feature-gated behind `otel_metrics`.
+//!
+//! Mirrors the same wait-time/obstruction counts as
+//! [`crate::telemetry::prometheus::PrometheusMetrics`], but pushes them to
+//! an OTLP collector on an interval instead of waiting to be scraped. Pull
+//! this in for deployments that already run an OTel collector rather than a
+//! Prometheus server.
+
+use crate::state::{
+    AppState, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus,
+};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Gauge, Meter};
+use opentelemetry_otlp::WithExportConfig;
+use std::sync::{Arc, RwLock};
+
+/// Instruments for the estimation/sensor-health metric set, held for the
+/// lifetime of the meter provider.
+pub struct OtelMetrics {
+    wait_time_minutes: Gauge<f64>,
+    wait_time_status_total: Counter<u64>,
+    wait_time_error_code_total: Counter<u64>,
+    obstruction_sensors: Gauge<u64>,
+}
+
+impl OtelMetrics {
+    fn new(meter: &Meter) -> Self {
+        Self {
+            wait_time_minutes: meter
+                .f64_gauge("mariam_wait_time_minutes")
+                .with_description("Latest estimated wait time in minutes")
+                .build(),
+            wait_time_status_total: meter
+                .u64_counter("mariam_wait_time_status_total")
+                .with_description("Wait time estimates by status")
+                .build(),
+            wait_time_error_code_total: meter
+                .u64_counter("mariam_wait_time_error_code_total")
+                .with_description("Wait time estimates by error code")
+                .build(),
+            obstruction_sensors: meter
+                .u64_gauge("mariam_obstruction_sensors")
+                .with_description("Sensor counts from the latest obstruction snapshot")
+                .build(),
+        }
+    }
+
+    fn record_wait_time(&self, estimate: &WaitTimeEstimate) {
+        if let Some(minutes) = estimate.wait_time_minutes {
+            self.wait_time_minutes.record(minutes, &[]);
+        }
+
+        let status = match estimate.status {
+            WaitTimeStatus::Ok => "ok",
+            WaitTimeStatus::Degraded => "degraded",
+        };
+        self.wait_time_status_total
+            .add(1, &[KeyValue::new("status", status)]);
+
+        if let Some(code) = &estimate.error_code {
+            self.wait_time_error_code_total
+                .add(1, &[KeyValue::new("code", error_code_label(code))]);
+        }
+    }
+
+    fn record_obstructions(&self, obstructions: &[SensorObstruction]) {
+        let (mut obstructed, mut valid, mut errors) = (0u64, 0u64, 0u64);
+        for obstruction in obstructions {
+            match obstruction.obstructed {
+                Some(true) => {
+                    obstructed += 1;
+                    valid += 1;
+                }
+                Some(false) => valid += 1,
+                None => errors += 1,
+            }
+        }
+        self.obstruction_sensors
+            .record(obstructed, &[KeyValue::new("kind", "obstructed")]);
+        self.obstruction_sensors
+            .record(valid, &[KeyValue::new("kind", "valid")]);
+        self.obstruction_sensors
+            .record(errors, &[KeyValue::new("kind", "error")]);
+    }
+}
+
+fn error_code_label(code: &WaitTimeErrorCode) -> &'static str {
+    match code {
+        WaitTimeErrorCode::NoData => "no_data",
+    }
+}
+
+/// Builds an OTLP/gRPC meter provider pointed at `otlp_endpoint` and spawns a
+/// task that mirrors `state`'s wait-time and obstruction channels into it.
+///
+/// Returns `None` (logging the failure) if the exporter can't be built,
+/// since a broken metrics pipeline shouldn't take down the rest of the app.
+pub fn spawn_otel_metrics(
+    state: &Arc<RwLock<AppState>>,
+    otlp_endpoint: &str,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let exporter = match opentelemetry_otlp::MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(otlp_endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(err) => {
+            tracing::error!(error = %err, "Failed to build OTLP metric exporter");
+            return None;
+        }
+    };
+
+    let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter)
+        .build();
+    let meter = provider.meter("mariam-flow");
+    let metrics = Arc::new(OtelMetrics::new(&meter));
+
+    let (mut wait_time_rx, mut obstructions_rx) = {
+        let guard = state.read().expect("state lock poisoned");
+        (guard.subscribe_wait_time(), guard.subscribe_obstructions())
+    };
+
+    let handle = tokio::spawn(async move {
+        // Keep the provider alive for the lifetime of the task; dropping it
+        // would stop the periodic export.
+        let _provider = provider;
+        loop {
+            tokio::select! {
+                changed = wait_time_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    if let Some(estimate) = wait_time_rx.borrow_and_update().clone() {
+                        metrics.record_wait_time(&estimate);
+                    }
+                }
+                changed = obstructions_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let obstructions = obstructions_rx.borrow_and_update().clone();
+                    metrics.record_obstructions(&obstructions);
+                }
+            }
+        }
+    });
+
+    Some(handle)
+}
@@ -0,0 +1,273 @@
+//! Prometheus text-exposition metrics mirroring wait-time estimates and
+//! sensor obstruction counts from the `AppState` watch channels.
+//!
+//! Unlike `mqtt`, which re-publishes each update as it happens, this keeps a
+//! running snapshot that `/api/metrics` renders on demand when scraped.
+//! Every `EstimationModel` reports through the same
+//! `AppState::set_wait_time`/`set_obstructions` channels, so the metrics are
+//! populated identically regardless of which model produced the estimate.
+
+use crate::state::{
+    AppState, SensorObstruction, WaitTimeErrorCode, WaitTimeEstimate, WaitTimeStatus,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Running snapshot of estimation/sensor-health metrics, rendered on demand
+/// in Prometheus text-exposition format.
+#[derive(Debug, Default)]
+pub struct PrometheusMetrics {
+    wait_time_minutes: Mutex<Option<f64>>,
+    ok_total: AtomicU64,
+    degraded_total: AtomicU64,
+    error_code_total: Mutex<HashMap<&'static str, u64>>,
+    obstructed_count: AtomicU64,
+    valid_count: AtomicU64,
+    error_count: AtomicU64,
+}
+
+impl PrometheusMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the wait-time gauge and the status/error-code counters from
+    /// the latest estimate, whichever `EstimationModel` produced it.
+    pub fn record_wait_time(&self, estimate: &WaitTimeEstimate) {
+        *self
+            .wait_time_minutes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = estimate.wait_time_minutes;
+
+        match estimate.status {
+            WaitTimeStatus::Ok => self.ok_total.fetch_add(1, Ordering::Relaxed),
+            WaitTimeStatus::Degraded => self.degraded_total.fetch_add(1, Ordering::Relaxed),
+        };
+
+        if let Some(code) = &estimate.error_code {
+            let mut counts = self
+                .error_code_total
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            *counts.entry(error_code_label(code)).or_insert(0) += 1;
+        }
+    }
+
+    /// Replaces the obstructed/valid/error sensor gauges with the tallies
+    /// from the latest obstruction snapshot.
+    pub fn record_obstructions(&self, obstructions: &[SensorObstruction]) {
+        let (mut obstructed, mut valid, mut errors) = (0u64, 0u64, 0u64);
+        for obstruction in obstructions {
+            match obstruction.obstructed {
+                Some(true) => {
+                    obstructed += 1;
+                    valid += 1;
+                }
+                Some(false) => valid += 1,
+                None => errors += 1,
+            }
+        }
+        self.obstructed_count.store(obstructed, Ordering::Relaxed);
+        self.valid_count.store(valid, Ordering::Relaxed);
+        self.error_count.store(errors, Ordering::Relaxed);
+    }
+
+    /// Renders the current snapshot in Prometheus text-exposition format.
+    pub fn render(&self) -> String {
+        let wait_time_minutes = *self
+            .wait_time_minutes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let error_code_total = self
+            .error_code_total
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut out = String::new();
+
+        out.push_str("# HELP mariam_wait_time_minutes Latest estimated wait time in minutes.\n");
+        out.push_str("# TYPE mariam_wait_time_minutes gauge\n");
+        match wait_time_minutes {
+            Some(minutes) => out.push_str(&format!("mariam_wait_time_minutes {minutes}\n")),
+            None => out.push_str("mariam_wait_time_minutes NaN\n"),
+        }
+
+        out.push_str("# HELP mariam_wait_time_status_total Wait time estimates by status.\n");
+        out.push_str("# TYPE mariam_wait_time_status_total counter\n");
+        out.push_str(&format!(
+            "mariam_wait_time_status_total{{status=\"ok\"}} {}\n",
+            self.ok_total.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "mariam_wait_time_status_total{{status=\"degraded\"}} {}\n",
+            self.degraded_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str(
+            "# HELP mariam_obstruction_sensors Sensor counts from the latest obstruction snapshot.\n",
+        );
+        out.push_str("# TYPE mariam_obstruction_sensors gauge\n");
+        out.push_str(&format!(
+            "mariam_obstruction_sensors{{kind=\"obstructed\"}} {}\n",
+            self.obstructed_count.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "mariam_obstruction_sensors{{kind=\"valid\"}} {}\n",
+            self.valid_count.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "mariam_obstruction_sensors{{kind=\"error\"}} {}\n",
+            self.error_count.load(Ordering::Relaxed)
+        ));
+
+        out.push_str(
+            "# HELP mariam_wait_time_error_code_total Wait time estimates by error code.\n",
+        );
+        out.push_str("# TYPE mariam_wait_time_error_code_total counter\n");
+        for (code, count) in error_code_total.iter() {
+            out.push_str(&format!(
+                "mariam_wait_time_error_code_total{{code=\"{code}\"}} {count}\n"
+            ));
+        }
+
+        out
+    }
+
+    /// Spawn a task that mirrors `state`'s wait-time and obstruction
+    /// channels into this snapshot for the lifetime of the process.
+    pub fn spawn(
+        state: &Arc<RwLock<AppState>>,
+    ) -> (Arc<PrometheusMetrics>, tokio::task::JoinHandle<()>) {
+        let metrics = Arc::new(PrometheusMetrics::new());
+        let (mut wait_time_rx, mut obstructions_rx) = {
+            let guard = state.read().expect("state lock poisoned");
+            (guard.subscribe_wait_time(), guard.subscribe_obstructions())
+        };
+
+        let task_metrics = Arc::clone(&metrics);
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = wait_time_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        if let Some(estimate) = wait_time_rx.borrow_and_update().clone() {
+                            task_metrics.record_wait_time(&estimate);
+                        }
+                    }
+                    changed = obstructions_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        let obstructions = obstructions_rx.borrow_and_update().clone();
+                        task_metrics.record_obstructions(&obstructions);
+                    }
+                }
+            }
+        });
+
+        (metrics, handle)
+    }
+}
+
+fn error_code_label(code: &WaitTimeErrorCode) -> &'static str {
+    match code {
+        WaitTimeErrorCode::NoData => "no_data",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn estimate(status: WaitTimeStatus, error_code: Option<WaitTimeErrorCode>) -> WaitTimeEstimate {
+        WaitTimeEstimate {
+            wait_time_minutes: Some(5.0),
+            timestamp: UNIX_EPOCH,
+            status,
+            error_code,
+        }
+    }
+
+    fn obstruction(sensor_id: u32, obstructed: Option<bool>) -> SensorObstruction {
+        SensorObstruction {
+            sensor_id,
+            obstructed,
+            timestamp: UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn record_wait_time_updates_gauge_and_status_counters() {
+        let metrics = PrometheusMetrics::new();
+
+        metrics.record_wait_time(&estimate(WaitTimeStatus::Ok, None));
+        metrics.record_wait_time(&estimate(
+            WaitTimeStatus::Degraded,
+            Some(WaitTimeErrorCode::NoData),
+        ));
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("mariam_wait_time_minutes 5\n"));
+        assert!(rendered.contains("mariam_wait_time_status_total{status=\"ok\"} 1\n"));
+        assert!(rendered.contains("mariam_wait_time_status_total{status=\"degraded\"} 1\n"));
+        assert!(rendered.contains("mariam_wait_time_error_code_total{code=\"no_data\"} 1\n"));
+    }
+
+    #[test]
+    fn record_obstructions_tallies_by_classification() {
+        let metrics = PrometheusMetrics::new();
+
+        metrics.record_obstructions(&[
+            obstruction(1, Some(true)),
+            obstruction(2, Some(false)),
+            obstruction(3, None),
+        ]);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("mariam_obstruction_sensors{kind=\"obstructed\"} 1\n"));
+        assert!(rendered.contains("mariam_obstruction_sensors{kind=\"valid\"} 2\n"));
+        assert!(rendered.contains("mariam_obstruction_sensors{kind=\"error\"} 1\n"));
+    }
+
+    #[test]
+    fn render_reports_nan_gauge_before_any_estimate() {
+        let metrics = PrometheusMetrics::new();
+
+        assert!(metrics.render().contains("mariam_wait_time_minutes NaN\n"));
+    }
+
+    #[tokio::test]
+    async fn spawn_mirrors_state_updates_into_the_snapshot() {
+        let state = Arc::new(RwLock::new(AppState::new()));
+        let (metrics, _handle) = PrometheusMetrics::spawn(&state);
+
+        {
+            let mut guard = state.write().expect("state lock");
+            guard
+                .set_wait_time(estimate(WaitTimeStatus::Ok, None))
+                .expect("set wait time");
+            guard
+                .set_obstructions(vec![obstruction(1, Some(true))])
+                .expect("set obstructions");
+        }
+
+        // Give the spawned task a turn to observe the channel updates.
+        for _ in 0..50 {
+            if metrics
+                .render()
+                .contains("mariam_wait_time_status_total{status=\"ok\"} 1\n")
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("mariam_wait_time_status_total{status=\"ok\"} 1\n"));
+        assert!(rendered.contains("mariam_obstruction_sensors{kind=\"obstructed\"} 1\n"));
+    }
+}
@@ -0,0 +1,236 @@
+//! systemd readiness/watchdog integration, gated by sensor liveness.
+//!
+//! Reports `READY=1` once the first successful sensor discovery populates
+//! `AppState`, and keeps sending `WATCHDOG=1` on whatever cadence systemd
+//! configured via `WatchdogSec=` - but only while sensor acquisition looks
+//! healthy. If no sensor is [`DeviceSensorStatus::Ready`] (all in `Error`
+//! or still `Provisional`, or the sensor list is empty) for longer than
+//! `unhealthy_grace`, heartbeats stop
+//! so systemd's own watchdog timeout restarts the unit instead of us
+//! guessing at further self-repair. `STATUS=` lines summarize sensor count
+//! and error codes so `systemctl status` reflects live health. Outside a
+//! systemd unit (no `NOTIFY_SOCKET`), `sd_notify` silently drops every call
+//! here, so this is safe to always spawn.
+
+use crate::api::handlers::map_sensor_error_code;
+use crate::sensor::{DeviceSensorError, SensorAddress, SensorInfo, SensorStatus as DeviceSensorStatus};
+use crate::state::AppState;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy)]
+pub struct WatchdogConfig {
+    /// How long sensor acquisition may stay fully unhealthy before
+    /// watchdog heartbeats stop, letting systemd restart the unit.
+    pub unhealthy_grace: Duration,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            unhealthy_grace: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Spawn the systemd notification task.
+pub fn spawn_watchdog(
+    state: Arc<RwLock<AppState>>,
+    config: WatchdogConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        run_watchdog(state, config).await;
+    })
+}
+
+async fn run_watchdog(state: Arc<RwLock<AppState>>, config: WatchdogConfig) {
+    let mut sensors_rx = {
+        let guard = state.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard.subscribe_sensors()
+    };
+
+    // Hold off on READY=1 until discovery has actually populated a sensor
+    // set - a unit that's accepting connections before then isn't really up.
+    while sensors_rx.borrow_and_update().is_empty() {
+        if sensors_rx.changed().await.is_err() {
+            return;
+        }
+    }
+    notify_ready();
+
+    let mut ticker = watchdog_interval().map(tokio::time::interval);
+    let mut unhealthy_since: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            changed = sensors_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+            _ = tick(&mut ticker) => {}
+        }
+
+        let sensors = sensors_rx.borrow_and_update().clone();
+        let healthy = sensors_healthy(&sensors);
+        unhealthy_since = match (healthy, unhealthy_since) {
+            (true, _) => None,
+            (false, Some(since)) => Some(since),
+            (false, None) => Some(Instant::now()),
+        };
+
+        notify_status(&sensors);
+
+        let within_grace = unhealthy_since
+            .map(|since| since.elapsed() < config.unhealthy_grace)
+            .unwrap_or(true);
+
+        if within_grace {
+            notify_watchdog();
+        } else {
+            warn!("Sensor acquisition unhealthy past grace period - withholding watchdog heartbeat");
+        }
+    }
+}
+
+/// Awaits the next watchdog tick, or never resolves if systemd didn't
+/// configure a watchdog timeout - in that case the loop only wakes on
+/// sensor changes, same as if there were no ticker at all.
+async fn tick(ticker: &mut Option<tokio::time::Interval>) {
+    match ticker {
+        Some(ticker) => {
+            ticker.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+fn sensors_healthy(sensors: &[SensorInfo]) -> bool {
+    !sensors.is_empty()
+        && sensors
+            .iter()
+            .any(|sensor| matches!(sensor.status, DeviceSensorStatus::Ready))
+}
+
+/// Reads `WATCHDOG_USEC` via `sd_notify` and pings at half that interval,
+/// as systemd recommends. `None` if no watchdog timeout is configured.
+fn watchdog_interval() -> Option<Duration> {
+    let usec = sd_notify::watchdog_enabled(false);
+    if usec == 0 {
+        return None;
+    }
+    Some(Duration::from_micros(usec) / 2)
+}
+
+fn notify_ready() {
+    if let Err(err) = sd_notify::notify(false, &[sd_notify::NotifyState::Ready]) {
+        warn!(error = %err, "Failed to send systemd READY notification");
+        return;
+    }
+    info!("Sent systemd READY=1 after first sensor discovery");
+}
+
+fn notify_watchdog() {
+    if let Err(err) = sd_notify::notify(false, &[sd_notify::NotifyState::Watchdog]) {
+        warn!(error = %err, "Failed to send systemd WATCHDOG notification");
+    }
+}
+
+fn notify_status(sensors: &[SensorInfo]) {
+    if let Err(err) = sd_notify::notify(
+        false,
+        &[sd_notify::NotifyState::Status(&summarize_sensors(sensors))],
+    ) {
+        warn!(error = %err, "Failed to send systemd STATUS notification");
+    }
+}
+
+fn summarize_sensors(sensors: &[SensorInfo]) -> String {
+    if sensors.is_empty() {
+        return "no sensors discovered".to_string();
+    }
+
+    let mut ready = 0usize;
+    let mut provisional = 0usize;
+    let mut error_counts: HashMap<String, usize> = HashMap::new();
+    for sensor in sensors {
+        match &sensor.status {
+            DeviceSensorStatus::Ready => ready += 1,
+            DeviceSensorStatus::Provisional => provisional += 1,
+            DeviceSensorStatus::Error { error } => {
+                let code = format!("{:?}", map_sensor_error_code(error));
+                *error_counts.entry(code).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut line = format!("{ready}/{} sensors ready", sensors.len());
+    if provisional > 0 {
+        line.push_str(&format!(", {provisional} provisional"));
+    }
+    if !error_counts.is_empty() {
+        let mut errors: Vec<String> = error_counts
+            .into_iter()
+            .map(|(code, count)| format!("{count}x{code}"))
+            .collect();
+        errors.sort();
+        line.push_str(&format!(", errors: {}", errors.join(", ")));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(sensor_id: u32, status: DeviceSensorStatus) -> SensorInfo {
+        SensorInfo {
+            sensor_id,
+            xshut_pin: 0,
+            address: SensorAddress::I2c(0x29),
+            status,
+        }
+    }
+
+    #[test]
+    fn sensors_healthy_requires_at_least_one_ready_sensor() {
+        assert!(!sensors_healthy(&[]));
+        assert!(!sensors_healthy(&[sensor(
+            1,
+            DeviceSensorStatus::Error {
+                error: DeviceSensorError::Timeout
+            }
+        )]));
+        assert!(!sensors_healthy(&[sensor(1, DeviceSensorStatus::Provisional)]));
+        assert!(sensors_healthy(&[
+            sensor(
+                1,
+                DeviceSensorStatus::Error {
+                    error: DeviceSensorError::Timeout
+                }
+            ),
+            sensor(2, DeviceSensorStatus::Ready),
+        ]));
+    }
+
+    #[test]
+    fn summarize_sensors_reports_counts_and_error_codes() {
+        assert_eq!(summarize_sensors(&[]), "no sensors discovered");
+
+        let summary = summarize_sensors(&[
+            sensor(1, DeviceSensorStatus::Ready),
+            sensor(2, DeviceSensorStatus::Provisional),
+            sensor(
+                3,
+                DeviceSensorStatus::Error {
+                    error: DeviceSensorError::Other("i2c nack".to_string()),
+                },
+            ),
+        ]);
+        assert!(summary.contains("1/3 sensors ready"));
+        assert!(summary.contains("1 provisional"));
+        assert!(summary.contains("1xI2cError"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Property tests covering cross-cutting invariants every `EstimationModel`
+//! implementor must hold, regardless of its internal filtering/smoothing
+//! logic: the numeric result is never `NaN`/`inf`, it always respects the
+//! configured `min_wait_minutes`/`max_wait_minutes` bounds, `NoData` is
+//! returned exactly when there are no valid obstruction readings, and the
+//! status is `Degraded` whenever any sensor reported an error.
+//!
+//! `LinearV1Model`/`LinearV2Model` (readings-based) and `RemoteModel`
+//! (network I/O) aren't exercised here - the former don't implement the
+//! current obstructions-based `EstimationModel` shape and the latter has no
+//! pure data path to fuzz offline. `DeglitchModel` is also excluded: its
+//! `debounce()` step folds every `None` (sensor error) reading into an
+//! assumed `Some(bool)` via majority vote before occupancy is computed, so
+//! it structurally can't report `NoData`/`Degraded` for the per-reading
+//! errors these invariants key off of.
+
+use mariam_flow::estimation::ewma::{EwmaModel, EwmaParams};
+use mariam_flow::estimation::low_pass::{LowPassModel, LowPassParams};
+use mariam_flow::estimation::model::{EstimationModel, OccupancyConfig};
+use mariam_flow::estimation::obstruction_count_v1::{ObstructionCountModel, ObstructionCountParams};
+use mariam_flow::estimation::occupancy_smooth::{OccupancySmoothModel, OccupancySmoothParams};
+use mariam_flow::estimation::pid_obstruction::{PidObstructionModel, PidObstructionParams};
+use mariam_flow::estimation::smoothed_obstruction::{
+    SmoothedObstructionModel, SmoothedObstructionParams,
+};
+use mariam_flow::state::{SensorObstruction, WaitTimeErrorCode, WaitTimeStatus};
+use proptest::prelude::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MIN_WAIT_MINUTES: u32 = 1;
+const MAX_WAIT_MINUTES: u32 = 30;
+
+fn models() -> Vec<Box<dyn EstimationModel>> {
+    let occupancy_config = OccupancyConfig::default();
+    vec![
+        Box::new(ObstructionCountModel::new(
+            ObstructionCountParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..ObstructionCountParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(SmoothedObstructionModel::new(
+            SmoothedObstructionParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..SmoothedObstructionParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(PidObstructionModel::new(
+            PidObstructionParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..PidObstructionParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(EwmaModel::new(
+            EwmaParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..EwmaParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(LowPassModel::new(
+            LowPassParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..LowPassParams::default()
+            },
+            occupancy_config.clone(),
+        )),
+        Box::new(OccupancySmoothModel::new(
+            OccupancySmoothParams {
+                min_wait_minutes: Some(MIN_WAIT_MINUTES),
+                max_wait_minutes: Some(MAX_WAIT_MINUTES),
+                ..OccupancySmoothParams::default()
+            },
+            occupancy_config,
+        )),
+    ]
+}
+
+fn obstruction_strategy() -> impl Strategy<Value = Option<bool>> {
+    prop_oneof![Just(Some(true)), Just(Some(false)), Just(None)]
+}
+
+/// One ranging cycle: a handful of per-sensor obstruction flags plus the
+/// number of seconds elapsed since the previous cycle.
+fn batch_strategy() -> impl Strategy<Value = (Vec<Option<bool>>, u64)> {
+    (
+        prop::collection::vec(obstruction_strategy(), 0..8),
+        0u64..120,
+    )
+}
+
+fn to_obstructions(flags: &[Option<bool>], timestamp: SystemTime) -> Vec<SensorObstruction> {
+    flags
+        .iter()
+        .enumerate()
+        .map(|(sensor_id, &obstructed)| SensorObstruction {
+            sensor_id: sensor_id as u32,
+            obstructed,
+            timestamp,
+        })
+        .collect()
+}
+
+fn assert_invariants(flags: &[Option<bool>], estimate: &mariam_flow::state::WaitTimeEstimate) {
+    let valid_count = flags.iter().filter(|o| o.is_some()).count();
+    let error_count = flags.iter().filter(|o| o.is_none()).count();
+
+    if let Some(minutes) = estimate.wait_time_minutes {
+        assert!(
+            minutes.is_finite(),
+            "wait_time_minutes must never be NaN/inf, got {minutes}"
+        );
+        assert!(
+            minutes >= MIN_WAIT_MINUTES as f64 - 1e-9,
+            "wait_time_minutes {minutes} violates min_wait_minutes {MIN_WAIT_MINUTES}"
+        );
+        assert!(
+            minutes <= MAX_WAIT_MINUTES as f64 + 1e-9,
+            "wait_time_minutes {minutes} violates max_wait_minutes {MAX_WAIT_MINUTES}"
+        );
+    }
+
+    if valid_count == 0 {
+        assert_eq!(estimate.wait_time_minutes, None, "NoData must carry no value");
+        assert_eq!(estimate.status, WaitTimeStatus::Degraded);
+        assert_eq!(estimate.error_code, Some(WaitTimeErrorCode::NoData));
+    } else {
+        assert_eq!(
+            estimate.error_code, None,
+            "error_code is only set for NoData, not for partial sensor errors"
+        );
+        if error_count > 0 {
+            assert_eq!(
+                estimate.status,
+                WaitTimeStatus::Degraded,
+                "status must be Degraded whenever any sensor reported an error"
+            );
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn single_cycle_invariants_hold_for_every_model(flags in prop::collection::vec(obstruction_strategy(), 0..8)) {
+        let obstructions = to_obstructions(&flags, UNIX_EPOCH);
+        for model in models() {
+            let estimate = model.compute_wait_time(&obstructions, UNIX_EPOCH);
+            assert_invariants(&flags, &estimate);
+        }
+    }
+
+    #[test]
+    fn stateful_sequence_invariants_hold_for_every_model(
+        batches in prop::collection::vec(batch_strategy(), 1..20)
+    ) {
+        for model in models() {
+            let mut timestamp = UNIX_EPOCH;
+            for (flags, dt_secs) in &batches {
+                timestamp += Duration::from_secs(*dt_secs);
+                let obstructions = to_obstructions(flags, timestamp);
+                let estimate = model.compute_wait_time(&obstructions, timestamp);
+                assert_invariants(flags, &estimate);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use mariam_flow::bus::readings::read_and_store_distances;
 use mariam_flow::sensor::mock::{MockSensorBehavior, MockSensorFactory};
-use mariam_flow::sensor::{SensorInfo, SensorRangeStatus, SensorStatus};
+use mariam_flow::sensor::{SensorAddress, SensorInfo, SensorRangeStatus, SensorStatus};
 use mariam_flow::state::{AppState, ReadingStatus};
 use std::sync::{Arc, RwLock};
 
@@ -34,19 +34,19 @@ fn pipeline_mock_updates_state_for_all_sensors() -> Result<(), mariam_flow::erro
             SensorInfo {
                 sensor_id: 1,
                 xshut_pin: 17,
-                i2c_address: 0x30,
+                address: SensorAddress::I2c(0x30),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 2,
                 xshut_pin: 27,
-                i2c_address: 0x31,
+                address: SensorAddress::I2c(0x31),
                 status: SensorStatus::Ready,
             },
             SensorInfo {
                 sensor_id: 3,
                 xshut_pin: 22,
-                i2c_address: 0x32,
+                address: SensorAddress::I2c(0x32),
                 status: SensorStatus::Ready,
             },
         ])?;